@@ -5,7 +5,7 @@
 
 #![warn(missing_docs)]
 
-use meta_runtime::{opaque::Block, AccountId, Balance, Index};
+use meta_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Index};
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
@@ -31,9 +31,13 @@ where
   C: Send + Sync + 'static,
   C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
   C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+  C::Api: pallet_social_network_rpc::SocialNetworkRuntimeApi<Block, AccountId>,
+  C::Api: pallet_marketplace_rpc::MarketplaceRuntimeApi<Block, AccountId, Balance, BlockNumber>,
   C::Api: BlockBuilder<Block>,
   P: TransactionPool + 'static,
 {
+  use pallet_marketplace_rpc::{Marketplace, MarketplaceApi};
+  use pallet_social_network_rpc::{SocialNetwork, SocialNetworkApi};
   use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
   use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
@@ -44,6 +48,10 @@ where
 
   io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
 
+  io.extend_with(SocialNetworkApi::to_delegate(SocialNetwork::new(client.clone())));
+
+  io.extend_with(MarketplaceApi::to_delegate(Marketplace::new(client.clone())));
+
   // Extend this RPC with a custom API by using the following syntax.
   // `YourRpcStruct` should have a reference to a client, which is needed
   // to call into the runtime.
@@ -1,6 +1,7 @@
 use super::pallet::*;
 use crate::constants::{FETCH_TIMEOUT_PERIOD, HTTP_REMOTE_REQUEST, LOCK_BLOCK_EXPIRATION, LOCK_TIMEOUT_EXPIRATION, NUM_VEC_LEN};
-use crate::types::{HackerNewsInfo, Payload};
+use crate::traits::KeyProvider;
+use crate::types::{EncryptionKey, HackerNewsInfo, Payload};
 use core::convert::TryInto;
 use frame_system::offchain::{SendSignedTransaction, SendUnsignedTransaction, Signer, SubmitTransaction};
 use sp_runtime::{
@@ -218,3 +219,14 @@ impl<T: Config> BlockNumberProvider for Pallet<T> {
     <frame_system::Pallet<T>>::block_number()
   }
 }
+
+impl<T: Config> KeyProvider<T::AccountId> for Pallet<T> {
+  fn encryption_key_of(who: &T::AccountId) -> Option<EncryptionKey> {
+    EncryptionKeys::<T>::get(who)
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn register_encryption_key(who: &T::AccountId, key: EncryptionKey) {
+    EncryptionKeys::<T>::insert(who, key);
+  }
+}
@@ -4,6 +4,7 @@ pub use pallet::*;
 
 pub mod constants;
 pub mod crypto;
+pub mod traits;
 pub mod types;
 
 mod impls;
@@ -12,7 +13,7 @@ mod impls;
 pub mod pallet {
   //! A demonstration of an offchain worker that sends onchain callbacks
   use crate::constants::UNSIGNED_TXS_PRIORITY;
-  use crate::types::{Payload, TeeOracleURI};
+  use crate::types::{EncryptionKey, Payload, TeeOracleURI};
   use core::convert::TryInto;
   use frame_support::pallet_prelude::*;
   use frame_system::{
@@ -46,6 +47,11 @@ pub mod pallet {
   #[pallet::getter(fn custodians)]
   pub type TeeOracles<T: Config> = StorageValue<_, Vec<TeeOracleURI>, ValueQuery>;
 
+  /// The encryption key each account receives encrypted payloads with.
+  #[pallet::storage]
+  #[pallet::getter(fn encryption_keys)]
+  pub type EncryptionKeys<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, EncryptionKey>;
+
   // The pallet's runtime storage items.
   // https://substrate.dev/docs/en/knowledgebase/runtime/storage
   #[pallet::storage]
@@ -58,6 +64,10 @@ pub mod pallet {
   #[pallet::generate_deposit(pub(super) fn deposit_event)]
   pub enum Event<T: Config> {
     NewNumber(Option<T::AccountId>, u64),
+    /// An account registered or rotated its encryption key. \[who, key\]
+    EncryptionKeyRegistered(T::AccountId, EncryptionKey),
+    /// An account revoked its encryption key. \[who\]
+    EncryptionKeyRevoked(T::AccountId),
   }
 
   // Errors inform users that something went wrong.
@@ -80,6 +90,9 @@ pub mod pallet {
     HttpFetchingError,
     DeserializeToObjError,
     DeserializeToStrError,
+
+    // Error returned when revoking an encryption key that was never registered
+    EncryptionKeyNotExisted,
   }
 
   #[pallet::hooks]
@@ -151,6 +164,28 @@ pub mod pallet {
 
   #[pallet::call]
   impl<T: Config> Pallet<T> {
+    /// Register the key payloads addressed to the signer get encrypted to, replacing any previous one.
+    #[pallet::weight(10000)]
+    pub fn register_encryption_key(origin: OriginFor<T>, key: EncryptionKey) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      EncryptionKeys::<T>::insert(&who, key);
+
+      Self::deposit_event(Event::EncryptionKeyRegistered(who, key));
+      Ok(())
+    }
+
+    #[pallet::weight(10000)]
+    pub fn revoke_encryption_key(origin: OriginFor<T>) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+      ensure!(EncryptionKeys::<T>::contains_key(&who), Error::<T>::EncryptionKeyNotExisted);
+
+      EncryptionKeys::<T>::remove(&who);
+
+      Self::deposit_event(Event::EncryptionKeyRevoked(who));
+      Ok(())
+    }
+
     #[pallet::weight(10000)]
     pub fn submit_number_signed(origin: OriginFor<T>, number: u64) -> DispatchResult {
       let who = ensure_signed(origin)?;
@@ -0,0 +1,21 @@
+use crate::types::EncryptionKey;
+
+pub trait KeyProvider<AccountId> {
+  /// The key payloads addressed to `who` must be encrypted to, if it registered one.
+  fn encryption_key_of(who: &AccountId) -> Option<EncryptionKey>;
+
+  /// Register a key for `who`, so that benchmarks can address payloads to it.
+  #[cfg(feature = "runtime-benchmarks")]
+  fn register_encryption_key(who: &AccountId, key: EncryptionKey);
+}
+
+impl<AccountId> KeyProvider<AccountId> for () {
+  #[allow(unused_variables)]
+  fn encryption_key_of(who: &AccountId) -> Option<EncryptionKey> {
+    None
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  #[allow(unused_variables)]
+  fn register_encryption_key(who: &AccountId, key: EncryptionKey) {}
+}
@@ -6,6 +6,8 @@ use sp_runtime::RuntimeDebug;
 use sp_std::vec::Vec;
 
 pub type TeeOracleURI = BoundedVec<u8, ConstU32<TEE_ORACLE_MAX_URI_LENGTH>>;
+/// A public key other accounts encrypt payloads to, e.g. an x25519 key.
+pub type EncryptionKey = [u8; 32];
 
 // ref: https://serde.rs/container-attrs.html#crate
 #[derive(Deserialize, Encode, Decode, Default, RuntimeDebug, scale_info::TypeInfo)]
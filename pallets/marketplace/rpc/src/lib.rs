@@ -0,0 +1,272 @@
+//! JSON-RPC for browsing the marketplace through `MarketplaceApi`.
+
+use codec::Codec;
+use healthcare::{Role, TherapeuticArea};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_marketplace::types::{
+  CurrencyId, DataRequest, DataRequestCursor, DataRequestId, Listing, ListingFilter, Payouts, Purchase, PurchaseStatus, Purpose, RequestCriteria,
+  RevenueSplit,
+};
+pub use pallet_marketplace_runtime_api::MarketplaceApi as MarketplaceRuntimeApi;
+use pallet_meta_registry::types::RegistryId;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+/// The runtime API call failed.
+const RUNTIME_ERROR: i64 = 1;
+/// The cursor is not the id of a registry or of a data request.
+const INVALID_CURSOR: i64 = 2;
+
+/// A listing as returned over RPC, with its bounded fields unwrapped.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingView<Balance, BlockNumber> {
+  pub registry_id: Bytes,
+  pub price: Balance,
+  pub currency: CurrencyId,
+  pub licence: Bytes,
+  pub buyer_roles: Vec<Role>,
+  pub revenue_split: Option<RevenueSplit>,
+  pub listed_at: BlockNumber,
+}
+
+impl<Balance, BlockNumber> From<(RegistryId, Listing<Balance, BlockNumber>)> for ListingView<Balance, BlockNumber> {
+  fn from((registry_id, listing): (RegistryId, Listing<Balance, BlockNumber>)) -> Self {
+    ListingView {
+      registry_id: registry_id.into_inner().into(),
+      price: listing.price,
+      currency: listing.currency,
+      licence: listing.licence.into_inner().into(),
+      buyer_roles: listing.buyer_roles.into_inner(),
+      revenue_split: listing.revenue_split,
+      listed_at: listing.listed_at,
+    }
+  }
+}
+
+/// A purchase as returned over RPC, with its bounded fields unwrapped.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseView<AccountId, Balance, BlockNumber> {
+  pub registry_id: Bytes,
+  pub seller: AccountId,
+  pub price: Balance,
+  pub currency: CurrencyId,
+  pub licence: Bytes,
+  pub purchased_at: BlockNumber,
+  pub release_at: BlockNumber,
+  pub status: PurchaseStatus,
+  pub bundle: Option<Bytes>,
+  pub purpose: Purpose,
+  pub resharing: bool,
+  pub payouts: Payouts<AccountId, Balance>,
+}
+
+impl<AccountId, Balance, BlockNumber> From<(RegistryId, Purchase<AccountId, Balance, BlockNumber>)>
+  for PurchaseView<AccountId, Balance, BlockNumber>
+{
+  fn from((registry_id, purchase): (RegistryId, Purchase<AccountId, Balance, BlockNumber>)) -> Self {
+    PurchaseView {
+      registry_id: registry_id.into_inner().into(),
+      seller: purchase.seller,
+      price: purchase.price,
+      currency: purchase.currency,
+      licence: purchase.licence.into_inner().into(),
+      purchased_at: purchase.purchased_at,
+      release_at: purchase.release_at,
+      status: purchase.status,
+      bundle: purchase.bundle.map(|bundle_id| bundle_id.into_inner().into()),
+      purpose: purchase.purpose,
+      resharing: purchase.resharing,
+      payouts: purchase.payouts,
+    }
+  }
+}
+
+/// An open data request as returned over RPC, along with the registries of the patient it was
+/// looked up for that could fulfil it.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataRequestView<AccountId, Balance, BlockNumber> {
+  pub request_id: Bytes,
+  pub researcher: AccountId,
+  pub criteria: RequestCriteria,
+  pub price_per_record: Balance,
+  pub currency: CurrencyId,
+  pub min_records: u32,
+  pub max_records: u32,
+  pub licence: Bytes,
+  pub purpose: Purpose,
+  pub posted_at: BlockNumber,
+  pub deadline: BlockNumber,
+  /// The count of registries fulfilling the request so far
+  pub records: u32,
+  pub matching_registries: Vec<Bytes>,
+}
+
+impl<AccountId, Balance, BlockNumber> From<(DataRequestId, DataRequest<AccountId, Balance, BlockNumber>, Vec<RegistryId>)>
+  for DataRequestView<AccountId, Balance, BlockNumber>
+{
+  fn from((request_id, request, registries): (DataRequestId, DataRequest<AccountId, Balance, BlockNumber>, Vec<RegistryId>)) -> Self {
+    DataRequestView {
+      request_id: request_id.into_inner().into(),
+      researcher: request.researcher,
+      criteria: request.criteria,
+      price_per_record: request.price_per_record,
+      currency: request.currency,
+      min_records: request.min_records,
+      max_records: request.max_records,
+      licence: request.licence.into_inner().into(),
+      purpose: request.purpose,
+      posted_at: request.posted_at,
+      deadline: request.deadline,
+      records: request.records.len() as u32,
+      matching_registries: registries.into_iter().map(|registry_id| registry_id.into_inner().into()).collect(),
+    }
+  }
+}
+
+/// Where to resume browsing data requests: the therapeutic area and the id of the last request of
+/// the previous page.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestCursor {
+  pub therapeutic_area: Option<TherapeuticArea>,
+  pub request_id: Bytes,
+}
+
+#[rpc]
+pub trait MarketplaceApi<BlockHash, AccountId, Balance, BlockNumber> {
+  #[rpc(name = "marketplace_listings")]
+  fn listings(
+    &self,
+    filter: ListingFilter<Balance>,
+    cursor: Option<Bytes>,
+    limit: u32,
+    at: Option<BlockHash>,
+  ) -> Result<Vec<ListingView<Balance, BlockNumber>>>;
+
+  #[rpc(name = "marketplace_purchasesOf")]
+  fn purchases_of(
+    &self,
+    buyer: AccountId,
+    cursor: Option<Bytes>,
+    limit: u32,
+    at: Option<BlockHash>,
+  ) -> Result<Vec<PurchaseView<AccountId, Balance, BlockNumber>>>;
+
+  #[rpc(name = "marketplace_dataRequestsFor")]
+  fn data_requests_for(
+    &self,
+    patient: AccountId,
+    cursor: Option<RequestCursor>,
+    limit: u32,
+    at: Option<BlockHash>,
+  ) -> Result<Vec<DataRequestView<AccountId, Balance, BlockNumber>>>;
+}
+
+pub struct Marketplace<C, Block> {
+  client: Arc<C>,
+  _marker: PhantomData<Block>,
+}
+
+impl<C, Block> Marketplace<C, Block> {
+  pub fn new(client: Arc<C>) -> Self {
+    Marketplace {
+      client,
+      _marker: Default::default(),
+    }
+  }
+}
+
+fn runtime_error(err: impl std::fmt::Debug) -> RpcError {
+  RpcError {
+    code: ErrorCode::ServerError(RUNTIME_ERROR),
+    message: "Unable to query the marketplace.".into(),
+    data: Some(format!("{:?}", err).into()),
+  }
+}
+
+fn invalid_cursor() -> RpcError {
+  RpcError {
+    code: ErrorCode::ServerError(INVALID_CURSOR),
+    message: "The cursor is too long to be an id.".into(),
+    data: None,
+  }
+}
+
+fn registry_cursor(cursor: Option<Bytes>) -> Result<Option<RegistryId>> {
+  cursor
+    .map(|registry_id| registry_id.to_vec().try_into().map_err(|_| invalid_cursor()))
+    .transpose()
+}
+
+fn request_cursor(cursor: Option<RequestCursor>) -> Result<Option<DataRequestCursor>> {
+  cursor
+    .map(|cursor| {
+      let request_id = cursor.request_id.to_vec().try_into().map_err(|_| invalid_cursor())?;
+      Ok((cursor.therapeutic_area, request_id))
+    })
+    .transpose()
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber> MarketplaceApi<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber> for Marketplace<C, Block>
+where
+  Block: BlockT,
+  C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+  C::Api: MarketplaceRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+  AccountId: Codec,
+  Balance: Codec,
+  BlockNumber: Codec,
+{
+  fn listings(
+    &self,
+    filter: ListingFilter<Balance>,
+    cursor: Option<Bytes>,
+    limit: u32,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> Result<Vec<ListingView<Balance, BlockNumber>>> {
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+    let cursor = registry_cursor(cursor)?;
+    let listings = self.client.runtime_api().listings(&at, filter, cursor, limit).map_err(runtime_error)?;
+
+    Ok(listings.into_iter().map(Into::into).collect())
+  }
+
+  fn purchases_of(
+    &self,
+    buyer: AccountId,
+    cursor: Option<Bytes>,
+    limit: u32,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> Result<Vec<PurchaseView<AccountId, Balance, BlockNumber>>> {
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+    let cursor = registry_cursor(cursor)?;
+    let purchases = self.client.runtime_api().purchases_of(&at, buyer, cursor, limit).map_err(runtime_error)?;
+
+    Ok(purchases.into_iter().map(Into::into).collect())
+  }
+
+  fn data_requests_for(
+    &self,
+    patient: AccountId,
+    cursor: Option<RequestCursor>,
+    limit: u32,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> Result<Vec<DataRequestView<AccountId, Balance, BlockNumber>>> {
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+    let cursor = request_cursor(cursor)?;
+    let requests = self
+      .client
+      .runtime_api()
+      .data_requests_for(&at, patient, cursor, limit)
+      .map_err(runtime_error)?;
+
+    Ok(requests.into_iter().map(Into::into).collect())
+  }
+}
@@ -0,0 +1,28 @@
+//! Runtime API to browse the marketplace without walking raw storage keys.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_marketplace::types::{DataRequest, DataRequestCursor, DataRequestId, Listing, ListingFilter, Purchase};
+use pallet_meta_registry::types::RegistryId;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+  pub trait MarketplaceApi<AccountId, Balance, BlockNumber> where AccountId: Codec, Balance: Codec, BlockNumber: Codec {
+    /// The listings matching `filter` that can still be bought. Listings come after the registry
+    /// `cursor`, the last one of the previous page, and at most `limit` are returned.
+    fn listings(filter: ListingFilter<Balance>, cursor: Option<RegistryId>, limit: u32) -> Vec<(RegistryId, Listing<Balance, BlockNumber>)>;
+
+    /// The purchases of `buyer`, whatever their status, paginated like `listings`.
+    fn purchases_of(buyer: AccountId, cursor: Option<RegistryId>, limit: u32) -> Vec<(RegistryId, Purchase<AccountId, Balance, BlockNumber>)>;
+
+    /// The open data requests the registries of `patient` could fulfil, with those registries.
+    /// Requests come after `cursor`, the therapeutic area and id of the last request of the
+    /// previous page, and at most `limit` are returned.
+    fn data_requests_for(
+      patient: AccountId,
+      cursor: Option<DataRequestCursor>,
+      limit: u32,
+    ) -> Vec<(DataRequestId, DataRequest<AccountId, Balance, BlockNumber>, Vec<RegistryId>)>;
+  }
+}
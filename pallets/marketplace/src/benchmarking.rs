@@ -1,19 +1,643 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-marketplace
 
 use super::*;
-#[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use crate::{
+  constants::{
+    LICENCE_TERMS_MAX_LEN, MAX_BUNDLE_SIZE, MAX_BUYER_ROLES, MAX_FULFILLED_REQUESTS, MAX_PANEL_SIZE, MAX_PURPOSES, MAX_REQUESTED_RECORDS,
+    MAX_RESHARES, MAX_SEALED_BIDS,
+  },
+  types::{
+    AssetId, AuctionKind, BundleFilter, BundleId, BundleRegistries, BuyerRoles, CurrencyId, DataRequestId, LicenceTerms, PurchaseStatus, Purpose,
+    Purposes, RequestCriteria, RevenueSplit, Verdict,
+  },
+  Pallet as Marketplace,
+};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{
+  sp_runtime::{
+    traits::{Bounded, Hash, Saturating},
+    Permill,
+  },
+  traits::{
+    tokens::fungibles::{Create, Mutate},
+    Currency, Get,
+  },
+};
 use frame_system::RawOrigin;
+use healthcare::{RecordFormat, Role, TherapeuticArea};
+use pallet_meta_registry::{
+  traits::RegistryProvider,
+  types::{RegistryId, RegistryProfile},
+};
+use pallet_social_network::traits::{CustodianProvider, RoleProvider};
+use region::{Country, Region};
+use sp_std::{vec, vec::Vec};
+
+const SEED: u32 = 0;
+const ASSET: AssetId = 1;
+
+/// Make `who` hold `role`, and able to afford every price and deposit.
+fn funded<T: Config>(who: &T::AccountId, role: Role) {
+  T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+  T::Roles::assign_role(who, &role);
+}
+
+fn member<T: Config>(name: &'static str, index: u32, role: Role) -> T::AccountId {
+  let who = account(name, index, SEED);
+  funded::<T>(&who, role);
+  who
+}
+
+/// A price leaving every party a share above the existential deposit.
+fn price<T: Config>() -> BalanceOf<T> {
+  T::Currency::minimum_balance().saturating_mul(1_000u32.into()).max(1_000u32.into())
+}
+
+fn registry_id(index: u32) -> RegistryId {
+  [b"registry".as_ref(), &index.to_le_bytes()[..]].concat().try_into().unwrap()
+}
+
+fn licence(length: u32) -> LicenceTerms {
+  vec![0u8; length as usize].try_into().unwrap()
+}
+
+fn buyer_roles(count: u32) -> BuyerRoles {
+  let roles = [
+    Role::Researcher,
+    Role::Aggregator,
+    Role::Enterprise,
+    Role::EnterpriseEmployee,
+    Role::HealthFacility,
+    Role::MedicalProfessional,
+    Role::FamilyHealth,
+    Role::Patient,
+  ];
+  roles[..count as usize].to_vec().try_into().unwrap()
+}
+
+fn purposes(count: u32) -> Purposes {
+  let purposes = [
+    Purpose::AcademicResearch,
+    Purpose::CommercialResearch,
+    Purpose::ClinicalCare,
+    Purpose::AiTraining,
+  ];
+  purposes[..count as usize].to_vec().try_into().unwrap()
+}
+
+fn split() -> Option<RevenueSplit> {
+  Some(RevenueSplit {
+    issuer_share: Permill::from_percent(10),
+    aggregator_cut: Permill::from_percent(10),
+  })
+}
+
+/// A registry of `owner` holding cardiovascular FHIR R4 records from the Netherlands, consented for
+/// every purpose and to resharing.
+fn registry<T: Config>(owner: &T::AccountId, index: u32) -> RegistryId {
+  let registry_id = registry_id(index);
+  let issuer: T::AccountId = account("issuer", 0, SEED);
+  let profile = RegistryProfile {
+    therapeutic_area: Some(TherapeuticArea::Cardiovascular),
+    format: RecordFormat::FhirR4,
+    country: Country::Netherlands,
+    region: Region::Europe,
+  };
+  T::Registries::register_registry(&registry_id, owner, &issuer, &profile);
+  Marketplace::<T>::consent(owner, &registry_id, &purposes(MAX_PURPOSES), &None, &true).unwrap();
+  registry_id
+}
+
+/// A registry of `owner` listed for researchers, which aggregators may bundle.
+fn listed<T: Config>(owner: &T::AccountId, index: u32) -> RegistryId {
+  let registry_id = registry::<T>(owner, index);
+  Marketplace::<T>::list(
+    owner,
+    &registry_id,
+    &price::<T>(),
+    &CurrencyId::Native,
+    &Default::default(),
+    &buyer_roles(1),
+    &None,
+  )
+  .unwrap();
+  Marketplace::<T>::consent_to_aggregation(owner, &registry_id, true).unwrap();
+  registry_id
+}
+
+fn bundle_id() -> BundleId {
+  b"bundle".to_vec().try_into().unwrap()
+}
+
+fn filter() -> BundleFilter {
+  BundleFilter {
+    therapeutic_area: Some(TherapeuticArea::Cardiovascular),
+    format: None,
+    region: Some(Region::Europe),
+  }
+}
+
+/// A bundle of `registries` priced so that each share of it is worth the price of a registry.
+fn bundle<T: Config>(aggregator: &T::AccountId, registries: Vec<RegistryId>) {
+  let registries: BundleRegistries = registries.try_into().unwrap();
+  Marketplace::<T>::bundle(
+    aggregator,
+    &bundle_id(),
+    &filter(),
+    &registries,
+    &price::<T>().saturating_mul((registries.len() as u32).into()),
+    &CurrencyId::Native,
+    &Default::default(),
+    &buyer_roles(1),
+  )
+  .unwrap();
+}
+
+/// A registry of `owner` `buyer` bought in a bundle, so that paying its price out pays an
+/// aggregator too.
+fn bought<T: Config>(owner: &T::AccountId, buyer: &T::AccountId) -> RegistryId {
+  let registry_id = listed::<T>(owner, 0);
+  let aggregator = member::<T>("aggregator", 0, Role::Aggregator);
+  bundle::<T>(&aggregator, vec![registry_id.clone()]);
+  Marketplace::<T>::purchase_bundled(buyer, &bundle_id(), &Purpose::AcademicResearch).unwrap();
+  registry_id
+}
+
+/// Register enough custodians to fill a dispute panel, and open a dispute on what `buyer` bought.
+fn disputed<T: Config>(owner: &T::AccountId, buyer: &T::AccountId) -> RegistryId {
+  for index in 0..MAX_PANEL_SIZE {
+    T::Custodians::register_custodian(&account("custodian", index, SEED));
+  }
+  let registry_id = bought::<T>(owner, buyer);
+  Marketplace::<T>::dispute(buyer, &registry_id, &Default::default()).unwrap();
+  registry_id
+}
+
+fn request_id(index: u32) -> DataRequestId {
+  [b"request".as_ref(), &index.to_le_bytes()[..]].concat().try_into().unwrap()
+}
+
+/// A data request of `researcher` for up to `MAX_REQUESTED_RECORDS` registries like those of
+/// `registry`, open for 10 blocks.
+fn requested<T: Config>(researcher: &T::AccountId, index: u32) -> DataRequestId {
+  let request_id = request_id(index);
+  let deadline = frame_system::Pallet::<T>::block_number() + 10u32.into();
+  Marketplace::<T>::post_request(
+    researcher,
+    &request_id,
+    &criteria(),
+    &price::<T>(),
+    &CurrencyId::Native,
+    &1,
+    &MAX_REQUESTED_RECORDS,
+    &Default::default(),
+    &Purpose::AcademicResearch,
+    &deadline,
+  )
+  .unwrap();
+  request_id
+}
+
+fn criteria() -> RequestCriteria {
+  RequestCriteria {
+    therapeutic_area: Some(TherapeuticArea::Cardiovascular),
+    country: Some(Country::Netherlands),
+    ..Default::default()
+  }
+}
+
+/// Add `registry_id` of `owner` to a data request without settling it, even once it is full.
+fn add_record<T: Config>(request_id: &DataRequestId, registry_id: &RegistryId, owner: &T::AccountId) {
+  DataRequests::<T>::mutate(request_id, |maybe_request| {
+    maybe_request
+      .as_mut()
+      .unwrap()
+      .records
+      .try_push((registry_id.clone(), owner.clone()))
+      .unwrap();
+  });
+  Fulfilments::<T>::mutate(registry_id, |request_ids| request_ids.try_push(request_id.clone()).unwrap());
+}
+
+/// An auction of a registry of `owner` ending in 10 blocks, at a reserve price of `price`.
+fn auctioned<T: Config>(owner: &T::AccountId, kind: AuctionKind<T::BlockNumber>) -> RegistryId {
+  let registry_id = registry::<T>(owner, 0);
+  Marketplace::<T>::auction(
+    owner,
+    &registry_id,
+    &kind,
+    &price::<T>(),
+    &CurrencyId::Native,
+    &Default::default(),
+    &buyer_roles(1),
+    &None,
+    &10u32.into(),
+  )
+  .unwrap();
+  registry_id
+}
+
+fn sealed_bid_auction<T: Config>(owner: &T::AccountId) -> RegistryId {
+  auctioned::<T>(owner, AuctionKind::SealedBid { reveal_period: 5u32.into() })
+}
+
+fn commitment<T: Config>(bidder: &T::AccountId, amount: &BalanceOf<T>) -> T::Hash {
+  T::Hashing::hash_of(&(bidder, amount, [0u8; 32]))
+}
+
+fn commit<T: Config>(bidder: &T::AccountId, registry_id: &RegistryId, amount: &BalanceOf<T>) {
+  Marketplace::<T>::commit(bidder, registry_id, &commitment::<T>(bidder, amount), amount, &Purpose::AcademicResearch).unwrap();
+}
+
+fn ends_at<T: Config>(registry_id: &RegistryId) -> T::BlockNumber {
+  Auctions::<T>::get(registry_id).unwrap().ends_at
+}
 
 benchmarks! {
-    do_something {
-        let s in 0 .. 100;
-        let caller: T::AccountId = whitelisted_caller();
-    }: _(RawOrigin::Signed(caller), s)
-    verify {
-        assert_eq!(Something::<T>::get(), Some(s));
+  where_clause { where T::Assets: Create<T::AccountId> + Mutate<T::AccountId> }
+
+  list_registry {
+    let l in 0 .. LICENCE_TERMS_MAX_LEN;
+    let r in 1 .. MAX_BUYER_ROLES;
+
+    let owner: T::AccountId = whitelisted_caller();
+    funded::<T>(&owner, Role::Patient);
+    let registry_id = registry::<T>(&owner, 0);
+  }: _(RawOrigin::Signed(owner), registry_id.clone(), price::<T>(), CurrencyId::Native, licence(l), buyer_roles(r), split())
+  verify {
+    assert!(Listings::<T>::contains_key(&registry_id));
+  }
+
+  delist_registry {
+    let owner: T::AccountId = whitelisted_caller();
+    funded::<T>(&owner, Role::Patient);
+    let registry_id = listed::<T>(&owner, 0);
+  }: _(RawOrigin::Signed(owner), registry_id.clone())
+  verify {
+    assert!(!Listings::<T>::contains_key(&registry_id));
+  }
+
+  purchase_registry {
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let registry_id = listed::<T>(&owner, 0);
+    let buyer: T::AccountId = whitelisted_caller();
+    funded::<T>(&buyer, Role::Researcher);
+  }: _(RawOrigin::Signed(buyer.clone()), registry_id.clone(), Purpose::AcademicResearch)
+  verify {
+    assert!(Purchases::<T>::contains_key(&buyer, &registry_id));
+  }
+
+  confirm_purchase {
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let buyer: T::AccountId = whitelisted_caller();
+    funded::<T>(&buyer, Role::Researcher);
+    let registry_id = bought::<T>(&owner, &buyer);
+  }: _(RawOrigin::Signed(buyer.clone()), registry_id.clone())
+  verify {
+    assert_eq!(Purchases::<T>::get(&buyer, &registry_id).unwrap().status, PurchaseStatus::Released);
+  }
+
+  release_escrow {
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let buyer = member::<T>("buyer", 0, Role::Researcher);
+    let registry_id = bought::<T>(&owner, &buyer);
+    let caller: T::AccountId = whitelisted_caller();
+    frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + T::EscrowPeriod::get());
+  }: _(RawOrigin::Signed(caller), buyer.clone(), registry_id.clone())
+  verify {
+    assert_eq!(Purchases::<T>::get(&buyer, &registry_id).unwrap().status, PurchaseStatus::Released);
+  }
+
+  refund_purchase {
+    let s in 0 .. MAX_RESHARES;
+
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let buyer: T::AccountId = whitelisted_caller();
+    funded::<T>(&buyer, Role::Researcher);
+    let registry_id = bought::<T>(&owner, &buyer);
+    for index in 0 .. s {
+      Marketplace::<T>::reshare(&buyer, &registry_id, &account("recipient", index, SEED))?;
+    }
+    T::Registries::break_registry(&registry_id);
+  }: _(RawOrigin::Signed(buyer.clone()), registry_id.clone())
+  verify {
+    assert_eq!(Purchases::<T>::get(&buyer, &registry_id).unwrap().status, PurchaseStatus::Refunded);
+  }
+
+  set_aggregation_consent {
+    let owner: T::AccountId = whitelisted_caller();
+    funded::<T>(&owner, Role::Patient);
+    let registry_id = registry::<T>(&owner, 0);
+  }: _(RawOrigin::Signed(owner), registry_id.clone(), true)
+  verify {
+    assert!(AggregationConsents::<T>::get(&registry_id));
+  }
+
+  create_bundle {
+    let b in 1 .. MAX_BUNDLE_SIZE;
+    let l in 0 .. LICENCE_TERMS_MAX_LEN;
+    let r in 1 .. MAX_BUYER_ROLES;
+
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let registries: BundleRegistries = (0 .. b).map(|index| listed::<T>(&owner, index)).collect::<Vec<_>>().try_into().unwrap();
+    let aggregator: T::AccountId = whitelisted_caller();
+    funded::<T>(&aggregator, Role::Aggregator);
+  }: _(RawOrigin::Signed(aggregator), bundle_id(), filter(), registries, price::<T>(), CurrencyId::Native, licence(l), buyer_roles(r))
+  verify {
+    assert!(Bundles::<T>::contains_key(&bundle_id()));
+  }
+
+  remove_bundle {
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let registry_id = listed::<T>(&owner, 0);
+    let aggregator: T::AccountId = whitelisted_caller();
+    funded::<T>(&aggregator, Role::Aggregator);
+    bundle::<T>(&aggregator, vec![registry_id]);
+  }: _(RawOrigin::Signed(aggregator), bundle_id())
+  verify {
+    assert!(!Bundles::<T>::contains_key(&bundle_id()));
+  }
+
+  purchase_bundle {
+    let b in 1 .. MAX_BUNDLE_SIZE;
+
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let registries: Vec<RegistryId> = (0 .. b).map(|index| listed::<T>(&owner, index)).collect();
+    let aggregator = member::<T>("aggregator", 0, Role::Aggregator);
+    bundle::<T>(&aggregator, registries.clone());
+    let buyer: T::AccountId = whitelisted_caller();
+    funded::<T>(&buyer, Role::Researcher);
+  }: _(RawOrigin::Signed(buyer.clone()), bundle_id(), Purpose::AcademicResearch)
+  verify {
+    assert!(registries.iter().all(|registry_id| Purchases::<T>::contains_key(&buyer, registry_id)));
+  }
+
+  post_data_request {
+    let l in 0 .. LICENCE_TERMS_MAX_LEN;
+
+    let researcher: T::AccountId = whitelisted_caller();
+    funded::<T>(&researcher, Role::Researcher);
+    let deadline = frame_system::Pallet::<T>::block_number() + 10u32.into();
+  }: _(
+    RawOrigin::Signed(researcher),
+    request_id(0),
+    criteria(),
+    price::<T>(),
+    CurrencyId::Native,
+    1,
+    MAX_REQUESTED_RECORDS,
+    licence(l),
+    Purpose::AcademicResearch,
+    deadline
+  )
+  verify {
+    assert!(DataRequests::<T>::contains_key(&request_id(0)));
+  }
+
+  // The request stays one record short of being settled, which is weighed on its own.
+  fulfil_data_request {
+    let researcher = member::<T>("researcher", 0, Role::Researcher);
+    let request_id = requested::<T>(&researcher, 0);
+    let other = member::<T>("other", 0, Role::Patient);
+    for index in 1 .. MAX_REQUESTED_RECORDS - 1 {
+      add_record::<T>(&request_id, &registry::<T>(&other, index), &other);
+    }
+    let owner: T::AccountId = whitelisted_caller();
+    funded::<T>(&owner, Role::Patient);
+    let registry_id = registry::<T>(&owner, 0);
+  }: _(RawOrigin::Signed(owner), request_id.clone(), registry_id.clone())
+  verify {
+    assert!(DataRequests::<T>::get(&request_id).unwrap().records.iter().any(|(id, _)| *id == registry_id));
+  }
+
+  cancel_data_request {
+    let researcher: T::AccountId = whitelisted_caller();
+    funded::<T>(&researcher, Role::Researcher);
+    let request_id = requested::<T>(&researcher, 0);
+  }: _(RawOrigin::Signed(researcher), request_id.clone())
+  verify {
+    assert!(!DataRequests::<T>::contains_key(&request_id));
+  }
+
+  give_consent {
+    let p in 1 .. MAX_PURPOSES;
+
+    let owner: T::AccountId = whitelisted_caller();
+    funded::<T>(&owner, Role::Patient);
+    let registry_id = registry::<T>(&owner, 0);
+  }: _(RawOrigin::Signed(owner), registry_id.clone(), purposes(p), None, true)
+  verify {
+    assert_eq!(Consents::<T>::get(&registry_id).unwrap().purposes, purposes(p));
+  }
+
+  withdraw_consent {
+    let s in 0 .. MAX_SEALED_BIDS;
+    let f in 0 .. MAX_FULFILLED_REQUESTS;
+
+    let owner: T::AccountId = whitelisted_caller();
+    funded::<T>(&owner, Role::Patient);
+    let registry_id = sealed_bid_auction::<T>(&owner);
+    for index in 0 .. s {
+      commit::<T>(&member::<T>("bidder", index, Role::Researcher), &registry_id, &price::<T>());
+    }
+    for index in 0 .. f {
+      let request_id = requested::<T>(&member::<T>("researcher", index, Role::Researcher), index);
+      Marketplace::<T>::fulfil_request(&owner, &request_id, &registry_id)?;
+    }
+  }: _(RawOrigin::Signed(owner), registry_id.clone())
+  verify {
+    assert!(!Consents::<T>::contains_key(&registry_id));
+    assert!(!Auctions::<T>::contains_key(&registry_id));
+  }
+
+  reshare_purchase {
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let buyer: T::AccountId = whitelisted_caller();
+    funded::<T>(&buyer, Role::Researcher);
+    let registry_id = bought::<T>(&owner, &buyer);
+    for index in 1 .. MAX_RESHARES {
+      Marketplace::<T>::reshare(&buyer, &registry_id, &account("recipient", index, SEED))?;
+    }
+    let recipient: T::AccountId = account("recipient", 0, SEED);
+  }: _(RawOrigin::Signed(buyer.clone()), registry_id.clone(), recipient.clone())
+  verify {
+    assert!(Reshares::<T>::get(&buyer, &registry_id).contains(&recipient));
+  }
+
+  claim_refund {
+    let who: T::AccountId = whitelisted_caller();
+    let amount: BalanceOf<T> = price::<T>();
+    let _ = T::Assets::create(ASSET, Marketplace::<T>::account_id(), true, 1u32.into());
+    T::Assets::mint_into(ASSET, &Marketplace::<T>::account_id(), amount)?;
+    UnclaimedRefunds::<T>::insert(&who, ASSET, amount);
+  }: _(RawOrigin::Signed(who.clone()), ASSET)
+  verify {
+    assert!(!UnclaimedRefunds::<T>::contains_key(&who, ASSET));
+  }
+
+  create_auction {
+    let l in 0 .. LICENCE_TERMS_MAX_LEN;
+    let r in 1 .. MAX_BUYER_ROLES;
+
+    let owner: T::AccountId = whitelisted_caller();
+    funded::<T>(&owner, Role::Patient);
+    let registry_id = registry::<T>(&owner, 0);
+  }: _(
+    RawOrigin::Signed(owner),
+    registry_id.clone(),
+    AuctionKind::English { extension: 5u32.into() },
+    price::<T>(),
+    CurrencyId::Native,
+    licence(l),
+    buyer_roles(r),
+    split(),
+    10u32.into()
+  )
+  verify {
+    assert!(Auctions::<T>::contains_key(&registry_id));
+  }
+
+  cancel_auction {
+    let owner: T::AccountId = whitelisted_caller();
+    funded::<T>(&owner, Role::Patient);
+    let registry_id = auctioned::<T>(&owner, AuctionKind::English { extension: 5u32.into() });
+  }: _(RawOrigin::Signed(owner), registry_id.clone())
+  verify {
+    assert!(!Auctions::<T>::contains_key(&registry_id));
+  }
+
+  // The bid outbids another bidder and extends the auction.
+  place_bid {
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let registry_id = auctioned::<T>(&owner, AuctionKind::English { extension: 20u32.into() });
+    Marketplace::<T>::bid(&member::<T>("bidder", 0, Role::Researcher), &registry_id, &price::<T>(), &Purpose::AcademicResearch)?;
+    let bidder: T::AccountId = whitelisted_caller();
+    funded::<T>(&bidder, Role::Researcher);
+    let amount = price::<T>().saturating_mul(2u32.into());
+  }: _(RawOrigin::Signed(bidder.clone()), registry_id.clone(), amount, Purpose::AcademicResearch)
+  verify {
+    assert_eq!(Auctions::<T>::get(&registry_id).unwrap().highest_bid.unwrap().bidder, bidder);
+  }
+
+  commit_bid {
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let registry_id = sealed_bid_auction::<T>(&owner);
+    let bidder: T::AccountId = whitelisted_caller();
+    funded::<T>(&bidder, Role::Researcher);
+    let commitment = commitment::<T>(&bidder, &price::<T>());
+  }: _(RawOrigin::Signed(bidder.clone()), registry_id.clone(), commitment, price::<T>(), Purpose::AcademicResearch)
+  verify {
+    assert!(SealedBids::<T>::contains_key(&registry_id, &bidder));
+  }
+
+  // The revealed bid is above the one revealed before.
+  reveal_bid {
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let registry_id = sealed_bid_auction::<T>(&owner);
+    let other = member::<T>("bidder", 0, Role::Researcher);
+    commit::<T>(&other, &registry_id, &price::<T>());
+    let bidder: T::AccountId = whitelisted_caller();
+    funded::<T>(&bidder, Role::Researcher);
+    let amount = price::<T>().saturating_mul(2u32.into());
+    commit::<T>(&bidder, &registry_id, &amount);
+    frame_system::Pallet::<T>::set_block_number(ends_at::<T>(&registry_id));
+    Marketplace::<T>::reveal(&other, &registry_id, &price::<T>(), &[0u8; 32])?;
+  }: _(RawOrigin::Signed(bidder.clone()), registry_id.clone(), amount, [0u8; 32])
+  verify {
+    assert_eq!(Auctions::<T>::get(&registry_id).unwrap().highest_bid.unwrap().bidder, bidder);
+  }
+
+  open_dispute {
+    for index in 0 .. MAX_PANEL_SIZE {
+      T::Custodians::register_custodian(&account("custodian", index, SEED));
+    }
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let buyer: T::AccountId = whitelisted_caller();
+    funded::<T>(&buyer, Role::Researcher);
+    let registry_id = bought::<T>(&owner, &buyer);
+  }: _(RawOrigin::Signed(buyer.clone()), registry_id.clone(), Default::default())
+  verify {
+    assert!(Disputes::<T>::contains_key(&buyer, &registry_id));
+  }
+
+  answer_dispute {
+    let owner: T::AccountId = whitelisted_caller();
+    funded::<T>(&owner, Role::Patient);
+    let buyer = member::<T>("buyer", 0, Role::Researcher);
+    let registry_id = disputed::<T>(&owner, &buyer);
+  }: _(RawOrigin::Signed(owner), buyer.clone(), registry_id.clone(), Default::default())
+  verify {
+    assert!(Disputes::<T>::get(&buyer, &registry_id).unwrap().response.is_some());
+  }
+
+  // The vote makes a majority of the panel, which releases the price to the seller.
+  vote_on_dispute {
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let buyer = member::<T>("buyer", 0, Role::Researcher);
+    let registry_id = disputed::<T>(&owner, &buyer);
+    let panel = Disputes::<T>::get(&buyer, &registry_id).unwrap().panel;
+    let majority = panel.len() / 2;
+    for custodian in panel[..majority].iter() {
+      Marketplace::<T>::vote(custodian, &buyer, &registry_id, &Verdict::Release)?;
+    }
+    let custodian = panel[majority].clone();
+  }: _(RawOrigin::Signed(custodian), buyer.clone(), registry_id.clone(), Verdict::Release)
+  verify {
+    assert!(!Disputes::<T>::contains_key(&buyer, &registry_id));
+  }
+
+  settle_auction {
+    let s in 0 .. MAX_SEALED_BIDS;
+
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let registry_id = sealed_bid_auction::<T>(&owner);
+    let bidders: Vec<(T::AccountId, BalanceOf<T>)> = (0 .. s)
+      .map(|index| (member::<T>("bidder", index, Role::Researcher), price::<T>().saturating_add(index.into())))
+      .collect();
+    for (bidder, amount) in bidders.iter() {
+      commit::<T>(bidder, &registry_id, amount);
+    }
+    let settles_at = Auctions::<T>::get(&registry_id).unwrap().settles_at();
+    frame_system::Pallet::<T>::set_block_number(ends_at::<T>(&registry_id));
+    for (bidder, amount) in bidders.iter() {
+      Marketplace::<T>::reveal(bidder, &registry_id, amount, &[0u8; 32])?;
+    }
+  }: {
+    Marketplace::<T>::settle_auction(&registry_id, &settles_at);
+  }
+  verify {
+    assert!(!Auctions::<T>::contains_key(&registry_id));
+  }
+
+  settle_data_request {
+    let r in 1 .. MAX_REQUESTED_RECORDS;
+
+    let researcher = member::<T>("researcher", 0, Role::Researcher);
+    let request_id = requested::<T>(&researcher, 0);
+    let owner = member::<T>("owner", 0, Role::Patient);
+    for index in 0 .. r {
+      add_record::<T>(&request_id, &registry::<T>(&owner, index), &owner);
     }
+    let deadline = DataRequests::<T>::get(&request_id).unwrap().deadline;
+  }: {
+    Marketplace::<T>::settle_data_request(&request_id, &deadline);
+  }
+  verify {
+    assert!(!DataRequests::<T>::contains_key(&request_id));
+  }
+
+  // Nobody voted, so the price is split.
+  settle_dispute {
+    let owner = member::<T>("owner", 0, Role::Patient);
+    let buyer = member::<T>("buyer", 0, Role::Researcher);
+    let registry_id = disputed::<T>(&owner, &buyer);
+    let decide_by = Disputes::<T>::get(&buyer, &registry_id).unwrap().decide_by;
+  }: {
+    Marketplace::<T>::settle_dispute(&buyer, &registry_id, &decide_by);
+  }
+  verify {
+    assert!(!Disputes::<T>::contains_key(&buyer, &registry_id));
+  }
 }
 
-impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+impl_benchmark_test_suite!(Marketplace, crate::mock::new_test_ext(), crate::mock::Test);
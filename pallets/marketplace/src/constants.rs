@@ -0,0 +1,15 @@
+pub const LICENCE_TERMS_MAX_LEN: u32 = 512;
+pub const MAX_BUYER_ROLES: u32 = 8;
+pub const BUNDLE_ID_MAX_LEN: u32 = 64;
+pub const MAX_BUNDLE_SIZE: u32 = 100;
+pub const DATA_REQUEST_ID_MAX_LEN: u32 = 64;
+pub const MAX_REQUESTED_RECORDS: u32 = 100;
+pub const MAX_DEADLINES_PER_BLOCK: u32 = 50;
+pub const MAX_PURPOSES: u32 = 4;
+pub const MAX_AUCTIONS_PER_BLOCK: u32 = 50;
+pub const MAX_SEALED_BIDS: u32 = 100;
+pub const MAX_PANEL_SIZE: u32 = 8;
+pub const MAX_DISPUTES_PER_BLOCK: u32 = 50;
+pub const MAX_PAGE_SIZE: u32 = 100;
+pub const MAX_FULFILLED_REQUESTS: u32 = 20;
+pub const MAX_RESHARES: u32 = 8;
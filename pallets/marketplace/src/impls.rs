@@ -0,0 +1,1583 @@
+use super::pallet::*;
+use crate::constants::{
+  MAX_AUCTIONS_PER_BLOCK, MAX_DEADLINES_PER_BLOCK, MAX_DISPUTES_PER_BLOCK, MAX_FULFILLED_REQUESTS, MAX_PAGE_SIZE, MAX_PANEL_SIZE,
+  MAX_REQUESTED_RECORDS, MAX_RESHARES, MAX_SEALED_BIDS,
+};
+use crate::types::{
+  AssetId, Auction, AuctionKind, Bid, Bundle, BundleFilter, BundleId, BundleRegistries, BuyerRoles, Consent, CurrencyId, DataRequest,
+  DataRequestCursor, DataRequestId, Dispute, LicenceTerms, Listing, ListingFilter, Panel, Payouts, Purchase, PurchaseStatus, Purpose, Purposes,
+  RequestCriteria, RevenueSplit, SealedBid, Verdict,
+};
+use frame_support::{
+  ensure,
+  sp_runtime::{
+    traits::{AccountIdConversion, CheckedMul, Hash, Saturating, Zero},
+    Permill, TransactionOutcome,
+  },
+  storage::with_transaction,
+  traits::{
+    tokens::fungibles::{Inspect, Transfer},
+    BalanceStatus, Get, ReservableCurrency,
+  },
+};
+use healthcare::{Role, TherapeuticArea};
+use pallet_meta_registry::{
+  traits::RegistryProvider,
+  types::{AccessType, RegistryId, RegistryProfile},
+};
+use pallet_social_network::traits::{CustodianProvider, OnAccountRecovered, RoleProvider};
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+impl<T: Config> Pallet<T> {
+  pub fn list(
+    seller_id: &T::AccountId,
+    registry_id: &RegistryId,
+    price: &BalanceOf<T>,
+    currency: &CurrencyId,
+    licence: &LicenceTerms,
+    buyer_roles: &BuyerRoles,
+    revenue_split: &Option<RevenueSplit>,
+  ) -> Result<(), Error<T>> {
+    let maybe_owner = T::Registries::owner_of(registry_id);
+
+    ensure!(maybe_owner.is_some(), Error::<T>::RegistryNotExisted);
+    ensure!(maybe_owner.unwrap() == *seller_id, Error::<T>::OnlyOwnerAllowed);
+    ensure!(!Listings::<T>::contains_key(registry_id), Error::<T>::ListingAlreadyExisted);
+    ensure!(!Auctions::<T>::contains_key(registry_id), Error::<T>::AuctionAlreadyExisted);
+    ensure!(Consents::<T>::contains_key(registry_id), Error::<T>::ConsentNotExisted);
+    Self::ensure_currency(currency)?;
+    Self::ensure_buyer_roles(buyer_roles)?;
+    Self::ensure_revenue_split(revenue_split)?;
+    let profile = T::Registries::profile_of(registry_id).ok_or(Error::<T>::RegistryNotExisted)?;
+
+    T::Registries::set_salable(registry_id, true).map_err(|_| Error::<T>::RegistryNotExisted)?;
+    Self::index_listing(registry_id, &profile);
+    Listings::<T>::insert(
+      registry_id,
+      Listing {
+        price: *price,
+        currency: *currency,
+        licence: licence.clone(),
+        buyer_roles: buyer_roles.clone(),
+        revenue_split: *revenue_split,
+        listed_at: <frame_system::Pallet<T>>::block_number(),
+      },
+    );
+
+    Self::deposit_event(Event::RegistryListed(registry_id.clone(), seller_id.clone(), *price, *currency));
+
+    Ok(())
+  }
+
+  pub fn delist(seller_id: &T::AccountId, registry_id: &RegistryId) -> Result<(), Error<T>> {
+    ensure!(Listings::<T>::contains_key(registry_id), Error::<T>::ListingNotExisted);
+    ensure!(
+      T::Registries::owner_of(registry_id).as_ref() == Some(seller_id),
+      Error::<T>::OnlyOwnerAllowed
+    );
+
+    Self::remove_listing(registry_id)
+  }
+
+  fn remove_listing(registry_id: &RegistryId) -> Result<(), Error<T>> {
+    T::Registries::set_salable(registry_id, false).map_err(|_| Error::<T>::RegistryNotExisted)?;
+    if let Some(profile) = T::Registries::profile_of(registry_id) {
+      Self::unindex_listing(registry_id, &profile);
+    }
+    Listings::<T>::remove(registry_id);
+
+    Self::deposit_event(Event::RegistryDelisted(registry_id.clone()));
+
+    Ok(())
+  }
+
+  /// Index a listing by the profile of its registry, so that clients browse listings without
+  /// walking all of them.
+  fn index_listing(registry_id: &RegistryId, profile: &RegistryProfile) {
+    if let Some(area) = profile.therapeutic_area {
+      ListingsByTherapeuticArea::<T>::insert(area, registry_id, ());
+    }
+    ListingsByFormat::<T>::insert(profile.format, registry_id, ());
+    ListingsByRegion::<T>::insert(&profile.region, registry_id, ());
+  }
+
+  fn unindex_listing(registry_id: &RegistryId, profile: &RegistryProfile) {
+    if let Some(area) = profile.therapeutic_area {
+      ListingsByTherapeuticArea::<T>::remove(area, registry_id);
+    }
+    ListingsByFormat::<T>::remove(profile.format, registry_id);
+    ListingsByRegion::<T>::remove(&profile.region, registry_id);
+  }
+
+  /// Put the price of a listed registry in escrow and grant the buyer `AccessType::Buyer`. The owner
+  /// at the time of the purchase is the seller, so that a recovered owner gets paid on its new key.
+  pub fn purchase(buyer_id: &T::AccountId, registry_id: &RegistryId, purpose: &Purpose) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_listing = Listings::<T>::get(registry_id);
+    let maybe_seller = T::Registries::owner_of(registry_id);
+
+    ensure!(maybe_listing.is_some(), Error::<T>::ListingNotExisted);
+    ensure!(maybe_seller.is_some(), Error::<T>::RegistryNotExisted);
+
+    let listing = maybe_listing.unwrap();
+    let seller_id = maybe_seller.unwrap();
+    ensure!(seller_id != *buyer_id, Error::<T>::OwnerCannotPurchase);
+    ensure!(
+      Purchases::<T>::get(buyer_id, registry_id).map_or(true, |purchase| purchase.status == PurchaseStatus::Refunded),
+      Error::<T>::AlreadyPurchased
+    );
+    ensure!(
+      T::Registries::granted_access(registry_id, buyer_id).is_none(),
+      Error::<T>::AlreadyAccessible
+    );
+    ensure!(
+      listing.buyer_roles.iter().any(|role| T::Roles::has_role(buyer_id, role)),
+      Error::<T>::BuyerRoleNotAllowed
+    );
+    let consent = Self::consent_for(registry_id, purpose, &now)?;
+    let payouts = Self::payouts_of(registry_id, &listing.price, &Self::split_of(&listing.revenue_split), None)?;
+
+    Self::reserve(buyer_id, &listing.currency, listing.price)?;
+    T::Registries::grant_access(registry_id, buyer_id, &AccessType::Buyer).map_err(|_| Error::<T>::RegistryNotExisted)?;
+
+    let purchase = Purchase {
+      seller: seller_id.clone(),
+      price: listing.price,
+      currency: listing.currency,
+      licence: listing.licence,
+      purchased_at: now,
+      release_at: now + T::EscrowPeriod::get(),
+      status: PurchaseStatus::Escrowed,
+      bundle: None,
+      purpose: *purpose,
+      resharing: consent.resharing,
+      payouts,
+    };
+    Self::index_escrow(buyer_id, registry_id, &purchase);
+    Purchases::<T>::insert(buyer_id, registry_id, &purchase);
+
+    Self::deposit_event(Event::RegistryPurchased(registry_id.clone(), buyer_id.clone(), seller_id, listing.price));
+
+    Ok(())
+  }
+
+  /// Pay the escrowed price to the seller and the other parties sharing it, either on the confirmation
+  /// of the buyer or once the escrow period is over.
+  pub fn release(buyer_id: &T::AccountId, registry_id: &RegistryId, confirmed: bool) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_purchase = Purchases::<T>::get(buyer_id, registry_id);
+
+    ensure!(maybe_purchase.is_some(), Error::<T>::PurchaseNotExisted);
+
+    let mut purchase = maybe_purchase.unwrap();
+    ensure!(purchase.status == PurchaseStatus::Escrowed, Error::<T>::NotEscrowed);
+    ensure!(confirmed || purchase.release_at <= now, Error::<T>::EscrowNotExpired);
+
+    Self::pay_out(buyer_id, &purchase)?;
+    Self::unindex_escrow(buyer_id, registry_id, &purchase);
+    purchase.status = PurchaseStatus::Released;
+    Purchases::<T>::insert(buyer_id, registry_id, &purchase);
+
+    if confirmed {
+      Self::deposit_event(Event::PurchaseConfirmed(registry_id.clone(), buyer_id.clone()));
+    }
+    Self::deposit_event(Event::RevenueShared(registry_id.clone(), buyer_id.clone(), purchase.payouts));
+    Self::deposit_event(Event::EscrowReleased(
+      registry_id.clone(),
+      buyer_id.clone(),
+      purchase.seller,
+      purchase.price,
+    ));
+
+    Ok(())
+  }
+
+  /// Give the escrowed price back to the buyer and take back its access, because the delivery
+  /// network reported the chunks of the registry as broken.
+  pub fn refund(buyer_id: &T::AccountId, registry_id: &RegistryId) -> Result<(), Error<T>> {
+    let maybe_purchase = Purchases::<T>::get(buyer_id, registry_id);
+
+    ensure!(maybe_purchase.is_some(), Error::<T>::PurchaseNotExisted);
+
+    let mut purchase = maybe_purchase.unwrap();
+    ensure!(purchase.status == PurchaseStatus::Escrowed, Error::<T>::NotEscrowed);
+    ensure!(T::Registries::is_broken(registry_id), Error::<T>::RegistryNotBroken);
+
+    Self::revoke_purchase(buyer_id, registry_id)?;
+    Self::unreserve(buyer_id, &purchase.currency, purchase.price);
+    Self::unindex_escrow(buyer_id, registry_id, &purchase);
+    purchase.status = PurchaseStatus::Refunded;
+    Purchases::<T>::insert(buyer_id, registry_id, &purchase);
+
+    Self::deposit_event(Event::PurchaseRefunded(registry_id.clone(), buyer_id.clone(), purchase.price));
+
+    Ok(())
+  }
+
+  pub fn consent_to_aggregation(owner_id: &T::AccountId, registry_id: &RegistryId, consent: bool) -> Result<(), Error<T>> {
+    let maybe_owner = T::Registries::owner_of(registry_id);
+
+    ensure!(maybe_owner.is_some(), Error::<T>::RegistryNotExisted);
+    ensure!(maybe_owner.unwrap() == *owner_id, Error::<T>::OnlyOwnerAllowed);
+
+    if consent {
+      AggregationConsents::<T>::insert(registry_id, true);
+    } else {
+      AggregationConsents::<T>::remove(registry_id);
+    }
+
+    Self::deposit_event(Event::AggregationConsentChanged(registry_id.clone(), consent));
+
+    Ok(())
+  }
+
+  pub fn bundle(
+    aggregator_id: &T::AccountId,
+    bundle_id: &BundleId,
+    filter: &BundleFilter,
+    registries: &BundleRegistries,
+    price: &BalanceOf<T>,
+    currency: &CurrencyId,
+    licence: &LicenceTerms,
+    buyer_roles: &BuyerRoles,
+  ) -> Result<(), Error<T>> {
+    ensure!(T::Roles::has_role(aggregator_id, &Role::Aggregator), Error::<T>::OnlyAggregatorAllowed);
+    ensure!(!Bundles::<T>::contains_key(bundle_id), Error::<T>::BundleAlreadyExisted);
+    ensure!(!registries.is_empty(), Error::<T>::InvalidBundle);
+    Self::ensure_currency(currency)?;
+    Self::ensure_buyer_roles(buyer_roles)?;
+
+    for (index, registry_id) in registries.iter().enumerate() {
+      ensure!(!registries[index + 1..].contains(registry_id), Error::<T>::InvalidBundle);
+      ensure!(Listings::<T>::contains_key(registry_id), Error::<T>::ListingNotExisted);
+
+      let profile = T::Registries::profile_of(registry_id).ok_or(Error::<T>::RegistryNotExisted)?;
+      ensure!(
+        AggregationConsents::<T>::get(registry_id) && filter.matches(&profile),
+        Error::<T>::RegistryNotAggregatable
+      );
+    }
+
+    Bundles::<T>::insert(
+      bundle_id,
+      Bundle {
+        aggregator: aggregator_id.clone(),
+        filter: filter.clone(),
+        registries: registries.clone(),
+        price: *price,
+        currency: *currency,
+        licence: licence.clone(),
+        buyer_roles: buyer_roles.clone(),
+        created_at: <frame_system::Pallet<T>>::block_number(),
+      },
+    );
+
+    Self::deposit_event(Event::BundleCreated(bundle_id.clone(), aggregator_id.clone(), *price, *currency));
+
+    Ok(())
+  }
+
+  pub fn unbundle(aggregator_id: &T::AccountId, bundle_id: &BundleId) -> Result<(), Error<T>> {
+    let maybe_bundle = Bundles::<T>::get(bundle_id);
+
+    ensure!(maybe_bundle.is_some(), Error::<T>::BundleNotExisted);
+    ensure!(maybe_bundle.unwrap().aggregator == *aggregator_id, Error::<T>::OnlyAggregatorAllowed);
+
+    Bundles::<T>::remove(bundle_id);
+
+    Self::deposit_event(Event::BundleRemoved(bundle_id.clone()));
+
+    Ok(())
+  }
+
+  /// Buy the registries of a bundle the buyer could buy on their own, each of them escrowed with an
+  /// equal share of the price. The part of the price that cannot be shared equally is not charged.
+  /// The aggregator gets a cut of each share, as set by the listing of the registry.
+  pub fn purchase_bundled(buyer_id: &T::AccountId, bundle_id: &BundleId, purpose: &Purpose) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_bundle = Bundles::<T>::get(bundle_id);
+
+    ensure!(maybe_bundle.is_some(), Error::<T>::BundleNotExisted);
+
+    let bundle = maybe_bundle.unwrap();
+    ensure!(
+      bundle.buyer_roles.iter().any(|role| T::Roles::has_role(buyer_id, role)),
+      Error::<T>::BuyerRoleNotAllowed
+    );
+
+    let eligible: Vec<(RegistryId, T::AccountId, bool, RevenueSplit)> = bundle
+      .registries
+      .iter()
+      .filter_map(|registry_id| {
+        let listing = Listings::<T>::get(registry_id)?;
+        let seller_id = T::Registries::owner_of(registry_id)?;
+        let consent = Self::consent_for(registry_id, purpose, &now).ok()?;
+        let purchasable = seller_id != *buyer_id
+          && AggregationConsents::<T>::get(registry_id)
+          && listing.buyer_roles.iter().any(|role| T::Roles::has_role(buyer_id, role))
+          && T::Registries::granted_access(registry_id, buyer_id).is_none()
+          && Purchases::<T>::get(buyer_id, registry_id).map_or(true, |purchase| purchase.status == PurchaseStatus::Refunded);
+        if purchasable {
+          Some((registry_id.clone(), seller_id, consent.resharing, Self::split_of(&listing.revenue_split)))
+        } else {
+          None
+        }
+      })
+      .collect();
+    ensure!(!eligible.is_empty(), Error::<T>::EmptyBundle);
+
+    let count: BalanceOf<T> = (eligible.len() as u32).into();
+    let share = bundle.price / count;
+    let mut purchases = Vec::new();
+    for (registry_id, seller_id, resharing, split) in eligible {
+      let payouts = Self::payouts_of(&registry_id, &share, &split, Some(&bundle.aggregator))?;
+      purchases.push((
+        registry_id,
+        Purchase {
+          seller: seller_id,
+          price: share,
+          currency: bundle.currency,
+          licence: bundle.licence.clone(),
+          purchased_at: now,
+          release_at: now + T::EscrowPeriod::get(),
+          status: PurchaseStatus::Escrowed,
+          bundle: Some(bundle_id.clone()),
+          purpose: *purpose,
+          resharing,
+          payouts,
+        },
+      ));
+    }
+
+    let paid = purchases.iter().fold(Zero::zero(), |paid: BalanceOf<T>, _| paid.saturating_add(share));
+    Self::reserve(buyer_id, &bundle.currency, paid)?;
+
+    for (registry_id, purchase) in purchases {
+      T::Registries::grant_access(&registry_id, buyer_id, &AccessType::Buyer).map_err(|_| Error::<T>::RegistryNotExisted)?;
+      Self::index_escrow(buyer_id, &registry_id, &purchase);
+      Purchases::<T>::insert(buyer_id, &registry_id, &purchase);
+
+      Self::deposit_event(Event::RegistryPurchased(registry_id, buyer_id.clone(), purchase.seller, share));
+    }
+
+    Self::deposit_event(Event::BundlePurchased(bundle_id.clone(), buyer_id.clone(), paid));
+
+    Ok(())
+  }
+
+  pub fn post_request(
+    researcher_id: &T::AccountId,
+    request_id: &DataRequestId,
+    criteria: &RequestCriteria,
+    price_per_record: &BalanceOf<T>,
+    currency: &CurrencyId,
+    min_records: &u32,
+    max_records: &u32,
+    licence: &LicenceTerms,
+    purpose: &Purpose,
+    deadline: &T::BlockNumber,
+  ) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+
+    ensure!(T::Roles::has_role(researcher_id, &Role::Researcher), Error::<T>::OnlyResearcherAllowed);
+    ensure!(!DataRequests::<T>::contains_key(request_id), Error::<T>::DataRequestAlreadyExisted);
+    ensure!(
+      0 < *min_records && min_records <= max_records && *max_records <= MAX_REQUESTED_RECORDS && now < *deadline,
+      Error::<T>::InvalidDataRequest
+    );
+    Self::ensure_currency(currency)?;
+    ensure!(
+      DataRequestDeadlines::<T>::decode_len(deadline).unwrap_or(0) < MAX_DEADLINES_PER_BLOCK as usize,
+      Error::<T>::TooManyDataRequests
+    );
+
+    let budget = price_per_record
+      .checked_mul(&(*max_records).into())
+      .ok_or(Error::<T>::InvalidDataRequest)?;
+    Self::reserve(researcher_id, currency, budget)?;
+
+    DataRequestDeadlines::<T>::mutate(deadline, |request_ids| request_ids.try_push(request_id.clone()))
+      .map_err(|_| Error::<T>::TooManyDataRequests)?;
+    DataRequestsByTherapeuticArea::<T>::insert(criteria.therapeutic_area, request_id, ());
+    DataRequests::<T>::insert(
+      request_id,
+      DataRequest {
+        researcher: researcher_id.clone(),
+        criteria: criteria.clone(),
+        price_per_record: *price_per_record,
+        currency: *currency,
+        min_records: *min_records,
+        max_records: *max_records,
+        licence: licence.clone(),
+        purpose: *purpose,
+        posted_at: now,
+        deadline: *deadline,
+        records: Default::default(),
+      },
+    );
+
+    Self::deposit_event(Event::DataRequestPosted(
+      request_id.clone(),
+      researcher_id.clone(),
+      *price_per_record,
+      *max_records,
+    ));
+
+    Ok(())
+  }
+
+  /// Add a registry of a patient to a data request, and settle the request if it has all the
+  /// records it asked for.
+  pub fn fulfil_request(owner_id: &T::AccountId, request_id: &DataRequestId, registry_id: &RegistryId) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_request = DataRequests::<T>::get(request_id);
+    let maybe_owner = T::Registries::owner_of(registry_id);
+
+    ensure!(maybe_request.is_some(), Error::<T>::DataRequestNotExisted);
+    ensure!(maybe_owner.is_some(), Error::<T>::RegistryNotExisted);
+    ensure!(maybe_owner.unwrap() == *owner_id, Error::<T>::OnlyOwnerAllowed);
+    ensure!(T::Roles::has_role(owner_id, &Role::Patient), Error::<T>::OnlyPatientAllowed);
+
+    let mut request = maybe_request.unwrap();
+    ensure!(now < request.deadline, Error::<T>::DataRequestClosed);
+    ensure!(request.researcher != *owner_id, Error::<T>::OwnerCannotPurchase);
+    ensure!(
+      T::Registries::profile_of(registry_id).map_or(false, |profile| request.criteria.matches(&profile)),
+      Error::<T>::RegistryNotMatching
+    );
+    Self::consent_for(registry_id, &request.purpose, &now)?;
+    ensure!(!request.records.iter().any(|(id, _)| id == registry_id), Error::<T>::AlreadyFulfilled);
+    ensure!(
+      Purchases::<T>::get(&request.researcher, registry_id).map_or(true, |purchase| purchase.status == PurchaseStatus::Refunded),
+      Error::<T>::AlreadyPurchased
+    );
+    ensure!(
+      T::Registries::granted_access(registry_id, &request.researcher).is_none(),
+      Error::<T>::AlreadyAccessible
+    );
+    ensure!(
+      Fulfilments::<T>::decode_len(registry_id).unwrap_or(0) < MAX_FULFILLED_REQUESTS as usize,
+      Error::<T>::TooManyFulfilments
+    );
+
+    request
+      .records
+      .try_push((registry_id.clone(), owner_id.clone()))
+      .map_err(|_| Error::<T>::DataRequestClosed)?;
+    let fulfilled = request.records.len() as u32 >= request.max_records;
+    let deadline = request.deadline;
+    Fulfilments::<T>::mutate(registry_id, |request_ids| request_ids.try_push(request_id.clone())).map_err(|_| Error::<T>::TooManyFulfilments)?;
+    DataRequests::<T>::insert(request_id, request);
+
+    Self::deposit_event(Event::DataRequestFulfilled(request_id.clone(), registry_id.clone(), owner_id.clone()));
+
+    if fulfilled {
+      DataRequestDeadlines::<T>::mutate(deadline, |request_ids| request_ids.retain(|id| id != request_id));
+      Self::settle_data_request(request_id, &now);
+    }
+
+    Ok(())
+  }
+
+  pub fn cancel_request(researcher_id: &T::AccountId, request_id: &DataRequestId) -> Result<(), Error<T>> {
+    let maybe_request = DataRequests::<T>::get(request_id);
+
+    ensure!(maybe_request.is_some(), Error::<T>::DataRequestNotExisted);
+
+    let request = maybe_request.unwrap();
+    ensure!(request.researcher == *researcher_id, Error::<T>::OnlyResearcherAllowed);
+    ensure!(request.records.is_empty(), Error::<T>::DataRequestHasRecords);
+
+    Self::unreserve(researcher_id, &request.currency, Self::budget_of(&request));
+    DataRequestDeadlines::<T>::mutate(request.deadline, |request_ids| request_ids.retain(|id| id != request_id));
+    DataRequestsByTherapeuticArea::<T>::remove(request.criteria.therapeutic_area, request_id);
+    DataRequests::<T>::remove(request_id);
+
+    Self::deposit_event(Event::DataRequestCancelled(request_id.clone()));
+
+    Ok(())
+  }
+
+  /// Pay the owner of every registry fulfilling a data request, and grant the researcher access to
+  /// them, when the request has at least `min_records` still consented for its purpose. The rest of
+  /// the budget goes back to the researcher either way. Each registry paid for is recorded as a
+  /// released purchase, and those the researcher was granted access to since they were added are
+  /// neither counted nor paid for.
+  pub fn settle_data_request(request_id: &DataRequestId, now: &T::BlockNumber) {
+    let request = match DataRequests::<T>::take(request_id) {
+      Some(request) => request,
+      None => return,
+    };
+    DataRequestsByTherapeuticArea::<T>::remove(request.criteria.therapeutic_area, request_id);
+    for (registry_id, _) in request.records.iter() {
+      Fulfilments::<T>::mutate(registry_id, |request_ids| request_ids.retain(|id| id != request_id));
+    }
+    let researcher_id = &request.researcher;
+    let budget = Self::budget_of(&request);
+    let purchases: Vec<(RegistryId, Purchase<T::AccountId, BalanceOf<T>, T::BlockNumber>)> = request
+      .records
+      .iter()
+      .filter(|(registry_id, _)| T::Registries::granted_access(registry_id, researcher_id).is_none())
+      .filter_map(|(registry_id, owner_id)| {
+        let consent = Self::consent_for(registry_id, &request.purpose, now).ok()?;
+        let split = Self::split_of(&Listings::<T>::get(registry_id).and_then(|listing| listing.revenue_split));
+        let payouts = Self::payouts_of(registry_id, &request.price_per_record, &split, None).ok()?;
+        let purchase = Purchase {
+          seller: owner_id.clone(),
+          price: request.price_per_record,
+          currency: request.currency,
+          licence: request.licence.clone(),
+          purchased_at: *now,
+          release_at: *now,
+          status: PurchaseStatus::Released,
+          bundle: None,
+          purpose: request.purpose,
+          resharing: consent.resharing,
+          payouts,
+        };
+        Some((registry_id.clone(), purchase))
+      })
+      .collect();
+
+    if (purchases.len() as u32) < request.min_records {
+      Self::unreserve(researcher_id, &request.currency, budget);
+
+      Self::deposit_event(Event::DataRequestExpired(request_id.clone()));
+      return;
+    }
+
+    let mut paid: BalanceOf<T> = Zero::zero();
+    let mut records = 0u32;
+    for (registry_id, purchase) in purchases {
+      if T::Registries::grant_access(&registry_id, researcher_id, &AccessType::Buyer).is_err() {
+        continue;
+      }
+      if Self::pay_out(researcher_id, &purchase).is_err() {
+        let _ = T::Registries::revoke_access(&registry_id, researcher_id, &AccessType::Buyer);
+        continue;
+      }
+
+      Purchases::<T>::insert(researcher_id, &registry_id, &purchase);
+      Self::deposit_event(Event::RevenueShared(registry_id, researcher_id.clone(), purchase.payouts));
+      paid = paid.saturating_add(request.price_per_record);
+      records += 1;
+    }
+
+    Self::unreserve(researcher_id, &request.currency, budget.saturating_sub(paid));
+
+    Self::deposit_event(Event::DataRequestSettled(request_id.clone(), records, paid));
+  }
+
+  pub fn consent(
+    owner_id: &T::AccountId,
+    registry_id: &RegistryId,
+    purposes: &Purposes,
+    expires_at: &Option<T::BlockNumber>,
+    resharing: &bool,
+  ) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_owner = T::Registries::owner_of(registry_id);
+
+    ensure!(maybe_owner.is_some(), Error::<T>::RegistryNotExisted);
+    ensure!(maybe_owner.unwrap() == *owner_id, Error::<T>::OnlyOwnerAllowed);
+    ensure!(!purposes.is_empty(), Error::<T>::InvalidConsent);
+    for (index, purpose) in purposes.iter().enumerate() {
+      ensure!(!purposes[index + 1..].contains(purpose), Error::<T>::InvalidConsent);
+    }
+    ensure!(expires_at.map_or(true, |expires_at| now < expires_at), Error::<T>::InvalidConsent);
+
+    Consents::<T>::insert(
+      registry_id,
+      Consent {
+        purposes: purposes.clone(),
+        expires_at: *expires_at,
+        resharing: *resharing,
+        given_at: now,
+      },
+    );
+
+    Self::deposit_event(Event::ConsentGiven(registry_id.clone(), purposes.clone()));
+
+    Ok(())
+  }
+
+  /// Withdraw the consent of a registry, delist it, cancel its auction and take it out of the data
+  /// requests it fulfils, so that nobody gets a new access to it.
+  pub fn withdraw(owner_id: &T::AccountId, registry_id: &RegistryId) -> Result<(), Error<T>> {
+    ensure!(Consents::<T>::contains_key(registry_id), Error::<T>::ConsentNotExisted);
+    ensure!(
+      T::Registries::owner_of(registry_id).as_ref() == Some(owner_id),
+      Error::<T>::OnlyOwnerAllowed
+    );
+
+    if Listings::<T>::contains_key(registry_id) {
+      Self::remove_listing(registry_id)?;
+    }
+    if let Some(auction) = Auctions::<T>::get(registry_id) {
+      Self::remove_auction(registry_id, &auction)?;
+    }
+    for request_id in Fulfilments::<T>::take(registry_id) {
+      DataRequests::<T>::mutate(&request_id, |maybe_request| {
+        if let Some(request) = maybe_request {
+          request.records.retain(|(id, _)| id != registry_id);
+        }
+      });
+    }
+    Consents::<T>::remove(registry_id);
+
+    Self::deposit_event(Event::ConsentWithdrawn(registry_id.clone()));
+
+    Ok(())
+  }
+
+  /// Grant `recipient_id` access to a registry `buyer_id` bought, when the consent of the owner
+  /// allowed resharing at the time of the purchase and still covers its purpose.
+  pub fn reshare(buyer_id: &T::AccountId, registry_id: &RegistryId, recipient_id: &T::AccountId) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_purchase = Purchases::<T>::get(buyer_id, registry_id);
+
+    ensure!(maybe_purchase.is_some(), Error::<T>::PurchaseNotExisted);
+
+    let purchase = maybe_purchase.unwrap();
+    ensure!(
+      purchase.resharing && purchase.status != PurchaseStatus::Refunded,
+      Error::<T>::ResharingNotAllowed
+    );
+    Self::consent_for(registry_id, &purchase.purpose, &now)?;
+    ensure!(
+      T::Registries::granted_access(registry_id, recipient_id).is_none(),
+      Error::<T>::AlreadyAccessible
+    );
+    ensure!(
+      Reshares::<T>::decode_len(buyer_id, registry_id).unwrap_or(0) < MAX_RESHARES as usize,
+      Error::<T>::TooManyReshares
+    );
+
+    T::Registries::grant_access(registry_id, recipient_id, &AccessType::Accessor).map_err(|_| Error::<T>::RegistryNotExisted)?;
+    Reshares::<T>::mutate(buyer_id, registry_id, |recipients| recipients.try_push(recipient_id.clone())).map_err(|_| Error::<T>::TooManyReshares)?;
+
+    Self::deposit_event(Event::PurchaseReshared(registry_id.clone(), buyer_id.clone(), recipient_id.clone()));
+
+    Ok(())
+  }
+
+  /// Take back the access of a buyer to a registry, along with the accesses it reshared.
+  fn revoke_purchase(buyer_id: &T::AccountId, registry_id: &RegistryId) -> Result<(), Error<T>> {
+    T::Registries::revoke_access(registry_id, buyer_id, &AccessType::Buyer).map_err(|_| Error::<T>::RegistryNotExisted)?;
+    for recipient_id in Reshares::<T>::take(buyer_id, registry_id) {
+      T::Registries::revoke_access(registry_id, &recipient_id, &AccessType::Accessor).map_err(|_| Error::<T>::RegistryNotExisted)?;
+    }
+
+    Ok(())
+  }
+
+  pub fn auction(
+    seller_id: &T::AccountId,
+    registry_id: &RegistryId,
+    kind: &AuctionKind<T::BlockNumber>,
+    reserve_price: &BalanceOf<T>,
+    currency: &CurrencyId,
+    licence: &LicenceTerms,
+    buyer_roles: &BuyerRoles,
+    revenue_split: &Option<RevenueSplit>,
+    duration: &T::BlockNumber,
+  ) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_owner = T::Registries::owner_of(registry_id);
+
+    ensure!(maybe_owner.is_some(), Error::<T>::RegistryNotExisted);
+    ensure!(maybe_owner.unwrap() == *seller_id, Error::<T>::OnlyOwnerAllowed);
+    ensure!(!Listings::<T>::contains_key(registry_id), Error::<T>::ListingAlreadyExisted);
+    ensure!(!Auctions::<T>::contains_key(registry_id), Error::<T>::AuctionAlreadyExisted);
+    ensure!(Consents::<T>::contains_key(registry_id), Error::<T>::ConsentNotExisted);
+    Self::ensure_currency(currency)?;
+    Self::ensure_buyer_roles(buyer_roles)?;
+    Self::ensure_revenue_split(revenue_split)?;
+    ensure!(
+      !duration.is_zero()
+        && match kind {
+          AuctionKind::English { .. } => true,
+          AuctionKind::SealedBid { reveal_period } => !reveal_period.is_zero(),
+        },
+      Error::<T>::InvalidAuction
+    );
+
+    let auction = Auction {
+      kind: *kind,
+      reserve_price: *reserve_price,
+      currency: *currency,
+      licence: licence.clone(),
+      buyer_roles: buyer_roles.clone(),
+      revenue_split: *revenue_split,
+      started_at: now,
+      ends_at: now + *duration,
+      highest_bid: None,
+      sealed_bids: 0,
+    };
+    ensure!(
+      AuctionEnds::<T>::decode_len(auction.settles_at()).unwrap_or(0) < MAX_AUCTIONS_PER_BLOCK as usize,
+      Error::<T>::TooManyAuctions
+    );
+
+    T::Registries::set_salable(registry_id, true).map_err(|_| Error::<T>::RegistryNotExisted)?;
+    AuctionEnds::<T>::mutate(auction.settles_at(), |registry_ids| registry_ids.try_push(registry_id.clone()))
+      .map_err(|_| Error::<T>::TooManyAuctions)?;
+    Auctions::<T>::insert(registry_id, &auction);
+
+    Self::deposit_event(Event::AuctionCreated(
+      registry_id.clone(),
+      seller_id.clone(),
+      *reserve_price,
+      auction.ends_at,
+    ));
+
+    Ok(())
+  }
+
+  pub fn cancel_auctioned(seller_id: &T::AccountId, registry_id: &RegistryId) -> Result<(), Error<T>> {
+    let maybe_auction = Auctions::<T>::get(registry_id);
+
+    ensure!(maybe_auction.is_some(), Error::<T>::AuctionNotExisted);
+    ensure!(
+      T::Registries::owner_of(registry_id).as_ref() == Some(seller_id),
+      Error::<T>::OnlyOwnerAllowed
+    );
+
+    let auction = maybe_auction.unwrap();
+    ensure!(auction.highest_bid.is_none() && auction.sealed_bids == 0, Error::<T>::AuctionHasBids);
+
+    Self::remove_auction(registry_id, &auction)
+  }
+
+  /// Take a registry off auction before it ends, refunding every bid.
+  fn remove_auction(registry_id: &RegistryId, auction: &Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>) -> Result<(), Error<T>> {
+    T::Registries::set_salable(registry_id, false).map_err(|_| Error::<T>::RegistryNotExisted)?;
+    for (bidder_id, sealed_bid) in SealedBids::<T>::drain_prefix(registry_id) {
+      Self::unreserve(&bidder_id, &auction.currency, sealed_bid.deposit);
+    }
+    if let (AuctionKind::English { .. }, Some(bid)) = (&auction.kind, &auction.highest_bid) {
+      Self::unreserve(&bid.bidder, &auction.currency, bid.amount);
+    }
+    AuctionEnds::<T>::mutate(auction.settles_at(), |registry_ids| registry_ids.retain(|id| id != registry_id));
+    Auctions::<T>::remove(registry_id);
+
+    Self::deposit_event(Event::AuctionCancelled(registry_id.clone()));
+
+    Ok(())
+  }
+
+  /// Raise the highest bid of an English auction, refunding the bidder it outbids. A bid placed less
+  /// than `extension` blocks before the end pushes the end back.
+  pub fn bid(bidder_id: &T::AccountId, registry_id: &RegistryId, amount: &BalanceOf<T>, purpose: &Purpose) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_auction = Auctions::<T>::get(registry_id);
+
+    ensure!(maybe_auction.is_some(), Error::<T>::AuctionNotExisted);
+
+    let mut auction = maybe_auction.unwrap();
+    let extension = match auction.kind {
+      AuctionKind::English { extension } => extension,
+      AuctionKind::SealedBid { .. } => return Err(Error::<T>::NotEnglishAuction),
+    };
+    ensure!(now < auction.ends_at, Error::<T>::AuctionClosed);
+    Self::ensure_can_bid(bidder_id, registry_id, &auction, purpose, &now)?;
+    ensure!(
+      *amount >= auction.reserve_price && auction.highest_bid.as_ref().map_or(true, |bid| *amount > bid.amount),
+      Error::<T>::BidTooLow
+    );
+
+    let ends_at = auction.ends_at.max(now + extension);
+    let extended = ends_at != auction.ends_at;
+    ensure!(
+      !extended || AuctionEnds::<T>::decode_len(ends_at).unwrap_or(0) < MAX_AUCTIONS_PER_BLOCK as usize,
+      Error::<T>::TooManyAuctions
+    );
+
+    let outbid = auction.highest_bid.take();
+    let raise = match &outbid {
+      Some(bid) if bid.bidder == *bidder_id => amount.saturating_sub(bid.amount),
+      _ => *amount,
+    };
+    Self::reserve(bidder_id, &auction.currency, raise)?;
+    if let Some(bid) = outbid.filter(|bid| bid.bidder != *bidder_id) {
+      Self::unreserve(&bid.bidder, &auction.currency, bid.amount);
+    }
+
+    if extended {
+      AuctionEnds::<T>::mutate(auction.ends_at, |registry_ids| registry_ids.retain(|id| id != registry_id));
+      AuctionEnds::<T>::mutate(ends_at, |registry_ids| registry_ids.try_push(registry_id.clone())).map_err(|_| Error::<T>::TooManyAuctions)?;
+      auction.ends_at = ends_at;
+    }
+    auction.highest_bid = Some(Bid {
+      bidder: bidder_id.clone(),
+      amount: *amount,
+      purpose: *purpose,
+    });
+    Auctions::<T>::insert(registry_id, &auction);
+
+    Self::deposit_event(Event::BidPlaced(registry_id.clone(), bidder_id.clone(), *amount));
+    if extended {
+      Self::deposit_event(Event::AuctionExtended(registry_id.clone(), ends_at));
+    }
+
+    Ok(())
+  }
+
+  pub fn commit(
+    bidder_id: &T::AccountId,
+    registry_id: &RegistryId,
+    commitment: &T::Hash,
+    deposit: &BalanceOf<T>,
+    purpose: &Purpose,
+  ) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_auction = Auctions::<T>::get(registry_id);
+
+    ensure!(maybe_auction.is_some(), Error::<T>::AuctionNotExisted);
+
+    let mut auction = maybe_auction.unwrap();
+    ensure!(matches!(auction.kind, AuctionKind::SealedBid { .. }), Error::<T>::NotSealedBidAuction);
+    ensure!(now < auction.ends_at, Error::<T>::AuctionClosed);
+    Self::ensure_can_bid(bidder_id, registry_id, &auction, purpose, &now)?;
+    ensure!(!SealedBids::<T>::contains_key(registry_id, bidder_id), Error::<T>::BidAlreadyExisted);
+    ensure!(auction.sealed_bids < MAX_SEALED_BIDS, Error::<T>::TooManyBids);
+    ensure!(*deposit >= auction.reserve_price, Error::<T>::BidTooLow);
+
+    Self::reserve(bidder_id, &auction.currency, *deposit)?;
+    SealedBids::<T>::insert(
+      registry_id,
+      bidder_id,
+      SealedBid {
+        commitment: *commitment,
+        deposit: *deposit,
+        purpose: *purpose,
+        revealed: None,
+      },
+    );
+    auction.sealed_bids += 1;
+    Auctions::<T>::insert(registry_id, &auction);
+
+    Self::deposit_event(Event::BidCommitted(registry_id.clone(), bidder_id.clone()));
+
+    Ok(())
+  }
+
+  /// Reveal a sealed bid, which becomes the highest bid when it is above every bid revealed before.
+  pub fn reveal(bidder_id: &T::AccountId, registry_id: &RegistryId, amount: &BalanceOf<T>, salt: &[u8; 32]) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_auction = Auctions::<T>::get(registry_id);
+
+    ensure!(maybe_auction.is_some(), Error::<T>::AuctionNotExisted);
+
+    let mut auction = maybe_auction.unwrap();
+    ensure!(matches!(auction.kind, AuctionKind::SealedBid { .. }), Error::<T>::NotSealedBidAuction);
+    ensure!(auction.ends_at <= now && now < auction.settles_at(), Error::<T>::AuctionNotRevealing);
+
+    let maybe_sealed_bid = SealedBids::<T>::get(registry_id, bidder_id);
+    ensure!(maybe_sealed_bid.is_some(), Error::<T>::BidNotExisted);
+
+    let mut sealed_bid = maybe_sealed_bid.unwrap();
+    ensure!(
+      sealed_bid.revealed.is_none() && sealed_bid.commitment == T::Hashing::hash_of(&(bidder_id, amount, salt)) && *amount <= sealed_bid.deposit,
+      Error::<T>::InvalidReveal
+    );
+    ensure!(*amount >= auction.reserve_price, Error::<T>::BidTooLow);
+
+    sealed_bid.revealed = Some(*amount);
+    SealedBids::<T>::insert(registry_id, bidder_id, &sealed_bid);
+    if auction.highest_bid.as_ref().map_or(true, |bid| *amount > bid.amount) {
+      auction.highest_bid = Some(Bid {
+        bidder: bidder_id.clone(),
+        amount: *amount,
+        purpose: sealed_bid.purpose,
+      });
+      Auctions::<T>::insert(registry_id, &auction);
+    }
+
+    Self::deposit_event(Event::BidRevealed(registry_id.clone(), bidder_id.clone(), *amount));
+
+    Ok(())
+  }
+
+  /// End an auction, escrowing the highest bid as the price of a purchase and refunding every
+  /// other bid. When the highest bid cannot be escrowed, because the consent of the owner does not
+  /// cover it anymore for instance, it is refunded too and the auction fails.
+  pub fn settle_auction(registry_id: &RegistryId, now: &T::BlockNumber) {
+    let auction = match Auctions::<T>::get(registry_id) {
+      Some(auction) if auction.settles_at() == *now => auction,
+      _ => return,
+    };
+    Auctions::<T>::remove(registry_id);
+    let _ = T::Registries::set_salable(registry_id, false);
+
+    let winner = auction.highest_bid.clone();
+    for (bidder_id, sealed_bid) in SealedBids::<T>::drain_prefix(registry_id) {
+      let kept = match &winner {
+        Some(bid) if bid.bidder == bidder_id => bid.amount,
+        _ => Zero::zero(),
+      };
+      Self::unreserve(&bidder_id, &auction.currency, sealed_bid.deposit.saturating_sub(kept));
+    }
+
+    match winner {
+      Some(bid) => {
+        if Self::escrow_winning_bid(registry_id, &auction, &bid, now).is_err() {
+          Self::unreserve(&bid.bidder, &auction.currency, bid.amount);
+          Self::deposit_event(Event::AuctionFailed(registry_id.clone()));
+        }
+      },
+      None => Self::deposit_event(Event::AuctionFailed(registry_id.clone())),
+    }
+  }
+
+  fn escrow_winning_bid(
+    registry_id: &RegistryId,
+    auction: &Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+    bid: &Bid<T::AccountId, BalanceOf<T>>,
+    now: &T::BlockNumber,
+  ) -> Result<(), Error<T>> {
+    let seller_id = T::Registries::owner_of(registry_id).ok_or(Error::<T>::RegistryNotExisted)?;
+    let consent = Self::consent_for(registry_id, &bid.purpose, now)?;
+    let payouts = Self::payouts_of(registry_id, &bid.amount, &Self::split_of(&auction.revenue_split), None)?;
+
+    T::Registries::grant_access(registry_id, &bid.bidder, &AccessType::Buyer).map_err(|_| Error::<T>::RegistryNotExisted)?;
+    let purchase = Purchase {
+      seller: seller_id.clone(),
+      price: bid.amount,
+      currency: auction.currency,
+      licence: auction.licence.clone(),
+      purchased_at: *now,
+      release_at: *now + T::EscrowPeriod::get(),
+      status: PurchaseStatus::Escrowed,
+      bundle: None,
+      purpose: bid.purpose,
+      resharing: consent.resharing,
+      payouts,
+    };
+    Self::index_escrow(&bid.bidder, registry_id, &purchase);
+    Purchases::<T>::insert(&bid.bidder, registry_id, &purchase);
+
+    Self::deposit_event(Event::RegistryPurchased(registry_id.clone(), bid.bidder.clone(), seller_id, bid.amount));
+    Self::deposit_event(Event::AuctionWon(registry_id.clone(), bid.bidder.clone(), bid.amount));
+
+    Ok(())
+  }
+
+  pub fn dispute(buyer_id: &T::AccountId, registry_id: &RegistryId, evidence: &T::Hash) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_purchase = Purchases::<T>::get(buyer_id, registry_id);
+
+    ensure!(maybe_purchase.is_some(), Error::<T>::PurchaseNotExisted);
+
+    let mut purchase = maybe_purchase.unwrap();
+    ensure!(purchase.status == PurchaseStatus::Escrowed, Error::<T>::NotEscrowed);
+
+    let panel: Panel<T::AccountId> = T::Custodians::custodians()
+      .into_iter()
+      .filter(|custodian_id| custodian_id != buyer_id && *custodian_id != purchase.seller)
+      .take(MAX_PANEL_SIZE as usize)
+      .collect::<Vec<_>>()
+      .try_into()
+      .map_err(|_| Error::<T>::NotEnoughCustodians)?;
+    ensure!(!panel.is_empty(), Error::<T>::NotEnoughCustodians);
+
+    let decide_by = now + T::DisputePeriod::get();
+    ensure!(
+      DisputeDeadlines::<T>::decode_len(decide_by).unwrap_or(0) < MAX_DISPUTES_PER_BLOCK as usize,
+      Error::<T>::TooManyDisputes
+    );
+
+    let deposit = T::DisputeDeposit::get();
+    T::Currency::reserve(buyer_id, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+    DisputeDeadlines::<T>::mutate(decide_by, |disputes| disputes.try_push((buyer_id.clone(), registry_id.clone())))
+      .map_err(|_| Error::<T>::TooManyDisputes)?;
+    Disputes::<T>::insert(
+      buyer_id,
+      registry_id,
+      Dispute {
+        evidence: *evidence,
+        response: None,
+        deposit,
+        panel,
+        votes: Default::default(),
+        opened_at: now,
+        decide_by,
+      },
+    );
+    purchase.status = PurchaseStatus::Disputed;
+    Purchases::<T>::insert(buyer_id, registry_id, &purchase);
+
+    Self::deposit_event(Event::DisputeOpened(registry_id.clone(), buyer_id.clone(), *evidence));
+
+    Ok(())
+  }
+
+  pub fn answer(seller_id: &T::AccountId, buyer_id: &T::AccountId, registry_id: &RegistryId, evidence: &T::Hash) -> Result<(), Error<T>> {
+    let maybe_dispute = Disputes::<T>::get(buyer_id, registry_id);
+
+    ensure!(maybe_dispute.is_some(), Error::<T>::DisputeNotExisted);
+    ensure!(
+      Purchases::<T>::get(buyer_id, registry_id).map_or(false, |purchase| purchase.seller == *seller_id),
+      Error::<T>::OnlySellerAllowed
+    );
+
+    let mut dispute = maybe_dispute.unwrap();
+    ensure!(dispute.response.is_none(), Error::<T>::ResponseAlreadyExisted);
+
+    dispute.response = Some(*evidence);
+    Disputes::<T>::insert(buyer_id, registry_id, &dispute);
+
+    Self::deposit_event(Event::DisputeAnswered(registry_id.clone(), buyer_id.clone(), *evidence));
+
+    Ok(())
+  }
+
+  pub fn vote(custodian_id: &T::AccountId, buyer_id: &T::AccountId, registry_id: &RegistryId, verdict: &Verdict) -> Result<(), Error<T>> {
+    let maybe_dispute = Disputes::<T>::get(buyer_id, registry_id);
+
+    ensure!(maybe_dispute.is_some(), Error::<T>::DisputeNotExisted);
+
+    let mut dispute = maybe_dispute.unwrap();
+    ensure!(dispute.panel.contains(custodian_id), Error::<T>::OnlyPanelAllowed);
+    ensure!(
+      !dispute.votes.iter().any(|(voter_id, _)| voter_id == custodian_id),
+      Error::<T>::AlreadyVoted
+    );
+
+    dispute
+      .votes
+      .try_push((custodian_id.clone(), *verdict))
+      .map_err(|_| Error::<T>::OnlyPanelAllowed)?;
+
+    Self::deposit_event(Event::DisputeVoted(registry_id.clone(), buyer_id.clone(), custodian_id.clone(), *verdict));
+
+    if dispute.votes_for(verdict) * 2 > dispute.panel.len() {
+      DisputeDeadlines::<T>::mutate(dispute.decide_by, |disputes| {
+        disputes.retain(|(disputer_id, disputed_id)| disputer_id != buyer_id || disputed_id != registry_id)
+      });
+      Self::resolve(buyer_id, registry_id, &dispute, verdict);
+    } else {
+      Disputes::<T>::insert(buyer_id, registry_id, &dispute);
+    }
+
+    Ok(())
+  }
+
+  /// Settle a dispute no majority of the panel agreed on, with the verdict that got the most votes.
+  /// Ties, and disputes no custodian voted on, are split.
+  pub fn settle_dispute(buyer_id: &T::AccountId, registry_id: &RegistryId, now: &T::BlockNumber) {
+    let dispute = match Disputes::<T>::get(buyer_id, registry_id) {
+      Some(dispute) if dispute.decide_by == *now => dispute,
+      _ => return,
+    };
+
+    let refund = dispute.votes_for(&Verdict::Refund);
+    let release = dispute.votes_for(&Verdict::Release);
+    let verdict = if refund > release && refund > dispute.votes_for(&Verdict::Split) {
+      Verdict::Refund
+    } else if release > refund && release > dispute.votes_for(&Verdict::Split) {
+      Verdict::Release
+    } else {
+      Verdict::Split
+    };
+
+    Self::resolve(buyer_id, registry_id, &dispute, &verdict);
+  }
+
+  /// Apply the verdict of custodians to a disputed purchase. The buyer gets its deposit back unless
+  /// the price is released to the seller, in which case it is paid to the treasury.
+  fn resolve(
+    buyer_id: &T::AccountId,
+    registry_id: &RegistryId,
+    dispute: &Dispute<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>,
+    verdict: &Verdict,
+  ) {
+    Disputes::<T>::remove(buyer_id, registry_id);
+
+    let mut purchase = match Purchases::<T>::get(buyer_id, registry_id) {
+      Some(purchase) => purchase,
+      None => return,
+    };
+    let two: BalanceOf<T> = 2u32.into();
+    let refunded = match verdict {
+      Verdict::Refund => purchase.price,
+      Verdict::Release => Zero::zero(),
+      Verdict::Split => purchase.price / two,
+    };
+    if *verdict == Verdict::Split {
+      purchase.price = purchase.price.saturating_sub(refunded);
+      purchase.payouts = Self::halve(&purchase.payouts, purchase.price);
+    }
+
+    if *verdict != Verdict::Refund && Self::pay_out(buyer_id, &purchase).is_err() {
+      // Nothing was paid, so the purchase goes back to escrow as it was before the dispute.
+      Purchases::<T>::mutate(buyer_id, registry_id, |maybe_purchase| {
+        if let Some(escrowed) = maybe_purchase {
+          escrowed.status = PurchaseStatus::Escrowed;
+        }
+      });
+      T::Currency::unreserve(buyer_id, dispute.deposit);
+      return;
+    }
+    Self::unreserve(buyer_id, &purchase.currency, refunded);
+    Self::unindex_escrow(buyer_id, registry_id, &purchase);
+
+    match verdict {
+      Verdict::Refund => {
+        let _ = Self::revoke_purchase(buyer_id, registry_id);
+        purchase.status = PurchaseStatus::Refunded;
+      },
+      Verdict::Release | Verdict::Split => {
+        purchase.status = PurchaseStatus::Released;
+        Self::deposit_event(Event::RevenueShared(registry_id.clone(), buyer_id.clone(), purchase.payouts.clone()));
+      },
+    }
+    if *verdict == Verdict::Release {
+      // The buyer loses its deposit either way, which is burnt when the treasury cannot receive it.
+      let unpaid =
+        T::Currency::repatriate_reserved(buyer_id, &T::TreasuryAccount::get(), dispute.deposit, BalanceStatus::Free).unwrap_or(dispute.deposit);
+      let (_burnt, _) = T::Currency::slash_reserved(buyer_id, unpaid);
+    } else {
+      T::Currency::unreserve(buyer_id, dispute.deposit);
+    }
+    Purchases::<T>::insert(buyer_id, registry_id, &purchase);
+
+    Self::deposit_event(Event::DisputeResolved(registry_id.clone(), buyer_id.clone(), *verdict));
+  }
+
+  /// Halve the shares of `payouts` for a split purchase, of which only `paid` is paid out. The owner
+  /// gets the rest of `paid`, including what halving the other shares rounds down.
+  fn halve(payouts: &Payouts<T::AccountId, BalanceOf<T>>, paid: BalanceOf<T>) -> Payouts<T::AccountId, BalanceOf<T>> {
+    let two: BalanceOf<T> = 2u32.into();
+    let issuer_share = payouts.issuer_share / two;
+    let treasury_fee = payouts.treasury_fee / two;
+    let aggregator_cut = payouts.aggregator_cut / two;
+    let owner_share = paid
+      .saturating_sub(issuer_share)
+      .saturating_sub(treasury_fee)
+      .saturating_sub(aggregator_cut);
+
+    Payouts {
+      owner_share,
+      issuer: payouts.issuer.clone(),
+      issuer_share,
+      treasury_fee,
+      aggregator: payouts.aggregator.clone(),
+      aggregator_cut,
+      residue: owner_share.saturating_sub(payouts.owner_share.saturating_sub(payouts.residue) / two),
+    }
+  }
+
+  /// The listings matching `filter` whose registry can still be sold under the consent of its
+  /// owner. Listings are walked through the index of the therapeutic area, format or region of
+  /// `filter`, in this order of preference, and from after the registry `cursor` when it is set. At
+  /// most `limit` are returned, capped at `MAX_PAGE_SIZE`.
+  pub fn listings_matching(
+    filter: &ListingFilter<BalanceOf<T>>,
+    cursor: &Option<RegistryId>,
+    limit: u32,
+  ) -> Vec<(RegistryId, Listing<BalanceOf<T>, T::BlockNumber>)> {
+    let now = <frame_system::Pallet<T>>::block_number();
+
+    let registry_ids: Box<dyn Iterator<Item = RegistryId>> = if let Some(area) = filter.therapeutic_area {
+      let entries = match cursor {
+        Some(registry_id) => {
+          ListingsByTherapeuticArea::<T>::iter_prefix_from(area, ListingsByTherapeuticArea::<T>::hashed_key_for(area, registry_id))
+        },
+        None => ListingsByTherapeuticArea::<T>::iter_prefix(area),
+      };
+      Box::new(entries.map(|(registry_id, _)| registry_id))
+    } else if let Some(format) = filter.format {
+      let entries = match cursor {
+        Some(registry_id) => ListingsByFormat::<T>::iter_prefix_from(format, ListingsByFormat::<T>::hashed_key_for(format, registry_id)),
+        None => ListingsByFormat::<T>::iter_prefix(format),
+      };
+      Box::new(entries.map(|(registry_id, _)| registry_id))
+    } else if let Some(region) = &filter.region {
+      let entries = match cursor {
+        Some(registry_id) => ListingsByRegion::<T>::iter_prefix_from(region, ListingsByRegion::<T>::hashed_key_for(region, registry_id)),
+        None => ListingsByRegion::<T>::iter_prefix(region),
+      };
+      Box::new(entries.map(|(registry_id, _)| registry_id))
+    } else {
+      let entries = match cursor {
+        Some(registry_id) => Listings::<T>::iter_from(Listings::<T>::hashed_key_for(registry_id)),
+        None => Listings::<T>::iter(),
+      };
+      Box::new(entries.map(|(registry_id, _)| registry_id))
+    };
+
+    registry_ids
+      .filter_map(|registry_id| Listings::<T>::get(&registry_id).map(|listing| (registry_id, listing)))
+      .filter(|(registry_id, listing)| {
+        Consents::<T>::get(registry_id).map_or(false, |consent| consent.expires_at.map_or(true, |expires_at| now < expires_at))
+          && T::Registries::profile_of(registry_id).map_or(false, |profile| filter.matches(&profile, listing))
+      })
+      .take(limit.min(MAX_PAGE_SIZE) as usize)
+      .collect()
+  }
+
+  /// The purchases of `buyer_id`, whatever their status, from after the registry `cursor` when it
+  /// is set. At most `limit` are returned, capped at `MAX_PAGE_SIZE`.
+  pub fn purchases_of(
+    buyer_id: &T::AccountId,
+    cursor: &Option<RegistryId>,
+    limit: u32,
+  ) -> Vec<(RegistryId, Purchase<T::AccountId, BalanceOf<T>, T::BlockNumber>)> {
+    let purchases = match cursor {
+      Some(registry_id) => Purchases::<T>::iter_prefix_from(buyer_id, Purchases::<T>::hashed_key_for(buyer_id, registry_id)),
+      None => Purchases::<T>::iter_prefix(buyer_id),
+    };
+
+    purchases.take(limit.min(MAX_PAGE_SIZE) as usize).collect()
+  }
+
+  /// The open data requests `patient_id` could fulfil, along with the registries of the patient
+  /// each of them would take. Only the requests asking for the therapeutic area of one of these
+  /// registries, or for any, are walked, from after `cursor` when it is set. At most `limit` are
+  /// returned, capped at `MAX_PAGE_SIZE`.
+  pub fn data_requests_for(
+    patient_id: &T::AccountId,
+    cursor: &Option<DataRequestCursor>,
+    limit: u32,
+  ) -> Vec<(DataRequestId, DataRequest<T::AccountId, BalanceOf<T>, T::BlockNumber>, Vec<RegistryId>)> {
+    let now = <frame_system::Pallet<T>>::block_number();
+
+    if !T::Roles::has_role(patient_id, &Role::Patient) {
+      return Vec::new();
+    }
+
+    let registries: Vec<(RegistryId, RegistryProfile)> = T::Registries::registries_of(patient_id)
+      .into_iter()
+      .filter_map(|registry_id| T::Registries::profile_of(&registry_id).map(|profile| (registry_id, profile)))
+      .collect();
+
+    let mut areas: Vec<Option<TherapeuticArea>> = Vec::new();
+    for (_, profile) in registries.iter().filter(|(_, profile)| profile.therapeutic_area.is_some()) {
+      if !areas.contains(&profile.therapeutic_area) {
+        areas.push(profile.therapeutic_area);
+      }
+    }
+    areas.push(None);
+
+    let (first, start) = match cursor {
+      Some((area, request_id)) => match areas.iter().position(|other| other == area) {
+        Some(first) => (first, Some(DataRequestsByTherapeuticArea::<T>::hashed_key_for(area, request_id))),
+        None => return Vec::new(),
+      },
+      None => (0, None),
+    };
+
+    areas[first..]
+      .iter()
+      .enumerate()
+      .flat_map(|(index, area)| {
+        let entries = match &start {
+          Some(start) if index == 0 => DataRequestsByTherapeuticArea::<T>::iter_prefix_from(area, start.clone()),
+          _ => DataRequestsByTherapeuticArea::<T>::iter_prefix(area),
+        };
+        entries.map(|(request_id, _)| request_id)
+      })
+      .filter_map(|request_id| DataRequests::<T>::get(&request_id).map(|request| (request_id, request)))
+      .filter(|(_, request)| now < request.deadline && request.researcher != *patient_id)
+      .filter_map(|(request_id, request)| {
+        let matching: Vec<RegistryId> = registries
+          .iter()
+          .filter(|(registry_id, profile)| {
+            request.criteria.matches(profile)
+              && !request.records.iter().any(|(id, _)| id == registry_id)
+              && Self::consent_for(registry_id, &request.purpose, &now).is_ok()
+              && Purchases::<T>::get(&request.researcher, registry_id).map_or(true, |purchase| purchase.status == PurchaseStatus::Refunded)
+          })
+          .map(|(registry_id, _)| registry_id.clone())
+          .collect();
+
+        if matching.is_empty() {
+          None
+        } else {
+          Some((request_id, request, matching))
+        }
+      })
+      .take(limit.min(MAX_PAGE_SIZE) as usize)
+      .collect()
+  }
+
+  /// Whether `bidder_id` could buy the registry on auction for `purpose`.
+  fn ensure_can_bid(
+    bidder_id: &T::AccountId,
+    registry_id: &RegistryId,
+    auction: &Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+    purpose: &Purpose,
+    now: &T::BlockNumber,
+  ) -> Result<(), Error<T>> {
+    ensure!(
+      T::Registries::owner_of(registry_id).as_ref() != Some(bidder_id),
+      Error::<T>::OwnerCannotPurchase
+    );
+    ensure!(
+      Purchases::<T>::get(bidder_id, registry_id).map_or(true, |purchase| purchase.status == PurchaseStatus::Refunded),
+      Error::<T>::AlreadyPurchased
+    );
+    ensure!(
+      T::Registries::granted_access(registry_id, bidder_id).is_none(),
+      Error::<T>::AlreadyAccessible
+    );
+    ensure!(
+      auction.buyer_roles.iter().any(|role| T::Roles::has_role(bidder_id, role)),
+      Error::<T>::BuyerRoleNotAllowed
+    );
+    Self::consent_for(registry_id, purpose, now)?;
+
+    Ok(())
+  }
+
+  /// The consent of a registry, if it lets a buyer declare `purpose` at block `now`.
+  fn consent_for(registry_id: &RegistryId, purpose: &Purpose, now: &T::BlockNumber) -> Result<Consent<T::BlockNumber>, Error<T>> {
+    let consent = Consents::<T>::get(registry_id).ok_or(Error::<T>::ConsentNotExisted)?;
+
+    ensure!(consent.covers(purpose, now), Error::<T>::PurposeNotConsented);
+
+    Ok(consent)
+  }
+
+  /// The revenue split of a listing, or the defaults of the runtime.
+  pub(crate) fn split_of(revenue_split: &Option<RevenueSplit>) -> RevenueSplit {
+    revenue_split.unwrap_or(RevenueSplit {
+      issuer_share: T::IssuerShare::get(),
+      aggregator_cut: T::AggregatorCut::get(),
+    })
+  }
+
+  /// Share `price` between the issuer of a registry, the treasury, the aggregator if any, and the
+  /// owner who gets the rest. Rounding each share down leaves a residue to the owner, which is
+  /// recorded so that payouts can be audited.
+  fn payouts_of(
+    registry_id: &RegistryId,
+    price: &BalanceOf<T>,
+    split: &RevenueSplit,
+    aggregator: Option<&T::AccountId>,
+  ) -> Result<Payouts<T::AccountId, BalanceOf<T>>, Error<T>> {
+    let issuer_id = T::Registries::issuer_of(registry_id).ok_or(Error::<T>::RegistryNotExisted)?;
+    let aggregator_cut = aggregator.map_or(Permill::zero(), |_| split.aggregator_cut);
+    let owner_cut = Permill::one()
+      .saturating_sub(split.issuer_share)
+      .saturating_sub(T::TreasuryFee::get())
+      .saturating_sub(aggregator_cut);
+
+    let issuer_share = split.issuer_share.mul_floor(*price);
+    let treasury_fee = T::TreasuryFee::get().mul_floor(*price);
+    let aggregator_share = aggregator_cut.mul_floor(*price);
+    let owner_share = price
+      .saturating_sub(issuer_share)
+      .saturating_sub(treasury_fee)
+      .saturating_sub(aggregator_share);
+
+    Ok(Payouts {
+      owner_share,
+      issuer: issuer_id,
+      issuer_share,
+      treasury_fee,
+      aggregator: aggregator.cloned(),
+      aggregator_cut: aggregator_share,
+      residue: owner_share.saturating_sub(owner_cut.mul_floor(*price)),
+    })
+  }
+
+  /// The accounts a purchase pays a share of its price to, other than the treasury.
+  fn payees_of(purchase: &Purchase<T::AccountId, BalanceOf<T>, T::BlockNumber>) -> Vec<T::AccountId> {
+    let mut payees = vec![purchase.seller.clone(), purchase.payouts.issuer.clone()];
+    if let Some(aggregator_id) = &purchase.payouts.aggregator {
+      payees.push(aggregator_id.clone());
+    }
+
+    payees
+  }
+
+  fn index_escrow(buyer_id: &T::AccountId, registry_id: &RegistryId, purchase: &Purchase<T::AccountId, BalanceOf<T>, T::BlockNumber>) {
+    for payee_id in Self::payees_of(purchase) {
+      EscrowedPayouts::<T>::insert(payee_id, (buyer_id.clone(), registry_id.clone()), ());
+    }
+  }
+
+  fn unindex_escrow(buyer_id: &T::AccountId, registry_id: &RegistryId, purchase: &Purchase<T::AccountId, BalanceOf<T>, T::BlockNumber>) {
+    for payee_id in Self::payees_of(purchase) {
+      EscrowedPayouts::<T>::remove(payee_id, (buyer_id.clone(), registry_id.clone()));
+    }
+  }
+
+  /// Pay every share of an escrowed price at once, or none of them.
+  fn pay_out(buyer_id: &T::AccountId, purchase: &Purchase<T::AccountId, BalanceOf<T>, T::BlockNumber>) -> Result<(), Error<T>> {
+    let payouts = &purchase.payouts;
+    let mut shares = vec![
+      (purchase.seller.clone(), payouts.owner_share),
+      (payouts.issuer.clone(), payouts.issuer_share),
+      (T::TreasuryAccount::get(), payouts.treasury_fee),
+    ];
+    if let Some(aggregator_id) = &payouts.aggregator {
+      shares.push((aggregator_id.clone(), payouts.aggregator_cut));
+    }
+
+    with_transaction(|| {
+      for (beneficiary_id, amount) in shares.iter().filter(|(_, amount)| !amount.is_zero()) {
+        if let Err(error) = Self::repatriate(buyer_id, beneficiary_id, &purchase.currency, *amount) {
+          return TransactionOutcome::Rollback(Err(error));
+        }
+      }
+
+      TransactionOutcome::Commit(Ok(()))
+    })
+  }
+
+  /// Set `amount` aside from the free balance of `who` until it is paid or given back. Assets are
+  /// moved to the account of the pallet, as they cannot be reserved.
+  fn reserve(who: &T::AccountId, currency: &CurrencyId, amount: BalanceOf<T>) -> Result<(), Error<T>> {
+    match currency {
+      CurrencyId::Native => T::Currency::reserve(who, amount).map_err(|_| Error::<T>::InsufficientBalance),
+      CurrencyId::Asset(_) if amount.is_zero() => Ok(()),
+      CurrencyId::Asset(asset_id) => T::Assets::transfer(*asset_id, who, &Self::account_id(), amount, true)
+        .map(|_| ())
+        .map_err(|_| Error::<T>::InsufficientBalance),
+    }
+  }
+
+  /// Give back `amount` set aside from the balance of `who`. Assets the account of `who` cannot
+  /// receive, because the asset is frozen for instance, are kept for `who` to claim later.
+  fn unreserve(who: &T::AccountId, currency: &CurrencyId, amount: BalanceOf<T>) {
+    match currency {
+      CurrencyId::Native => {
+        T::Currency::unreserve(who, amount);
+      },
+      CurrencyId::Asset(_) if amount.is_zero() => (),
+      CurrencyId::Asset(asset_id) => {
+        if T::Assets::transfer(*asset_id, &Self::account_id(), who, amount, false).is_err() {
+          UnclaimedRefunds::<T>::mutate(who, asset_id, |unclaimed| *unclaimed = unclaimed.saturating_add(amount));
+
+          Self::deposit_event(Event::RefundDeferred(who.clone(), *asset_id, amount));
+        }
+      },
+    }
+  }
+
+  /// Pay `who` the refunds in `asset_id` it could not receive when they were given back.
+  pub fn claim(who: &T::AccountId, asset_id: &AssetId) -> Result<(), Error<T>> {
+    let amount = UnclaimedRefunds::<T>::get(who, asset_id);
+
+    ensure!(!amount.is_zero(), Error::<T>::RefundNotExisted);
+
+    T::Assets::transfer(*asset_id, &Self::account_id(), who, amount, false).map_err(|_| Error::<T>::RefundFailed)?;
+    UnclaimedRefunds::<T>::remove(who, asset_id);
+
+    Self::deposit_event(Event::RefundClaimed(who.clone(), *asset_id, amount));
+
+    Ok(())
+  }
+
+  /// Pay `amount` set aside from the balance of `from` to `to`.
+  fn repatriate(from: &T::AccountId, to: &T::AccountId, currency: &CurrencyId, amount: BalanceOf<T>) -> Result<(), Error<T>> {
+    let paid = match currency {
+      CurrencyId::Native => T::Currency::repatriate_reserved(from, to, amount, BalanceStatus::Free).map_or(false, |unpaid| unpaid.is_zero()),
+      CurrencyId::Asset(asset_id) => T::Assets::transfer(*asset_id, &Self::account_id(), to, amount, false).is_ok(),
+    };
+    ensure!(paid, Error::<T>::InsufficientBalance);
+
+    Ok(())
+  }
+
+  /// The account holding the assets in escrow.
+  pub fn account_id() -> T::AccountId {
+    T::PalletId::get().into_account()
+  }
+
+  fn ensure_currency(currency: &CurrencyId) -> Result<(), Error<T>> {
+    match currency {
+      CurrencyId::Native => Ok(()),
+      CurrencyId::Asset(asset_id) => {
+        ensure!(!T::Assets::minimum_balance(*asset_id).is_zero(), Error::<T>::CurrencyNotSupported);
+        Ok(())
+      },
+    }
+  }
+
+  /// The budget reserved for a request, which cannot overflow as posting rejects such requests.
+  fn budget_of(request: &DataRequest<T::AccountId, BalanceOf<T>, T::BlockNumber>) -> BalanceOf<T> {
+    request.price_per_record.saturating_mul(request.max_records.into())
+  }
+
+  fn ensure_revenue_split(revenue_split: &Option<RevenueSplit>) -> Result<(), Error<T>> {
+    ensure!(
+      revenue_split.map_or(true, |split| Self::fits_in_price(&split)),
+      Error::<T>::InvalidRevenueSplit
+    );
+
+    Ok(())
+  }
+
+  /// Whether the shares of `split` and the treasury fee add up to at most the whole price.
+  pub(crate) fn fits_in_price(split: &RevenueSplit) -> bool {
+    let shares = split.issuer_share.deconstruct() as u64 + split.aggregator_cut.deconstruct() as u64 + T::TreasuryFee::get().deconstruct() as u64;
+
+    shares <= Permill::one().deconstruct() as u64
+  }
+
+  fn ensure_buyer_roles(buyer_roles: &BuyerRoles) -> Result<(), Error<T>> {
+    ensure!(!buyer_roles.is_empty(), Error::<T>::InvalidBuyerRoles);
+    for (index, role) in buyer_roles.iter().enumerate() {
+      ensure!(!buyer_roles[index + 1..].contains(role), Error::<T>::InvalidBuyerRoles);
+    }
+
+    Ok(())
+  }
+}
+
+impl<T: Config> OnAccountRecovered<T::AccountId> for Pallet<T> {
+  /// Move the purchases, disputes, reshares and unclaimed refunds of `lost` to `new`, along with
+  /// what is reserved for them, and pay the escrowed shares owed to `lost` to `new` instead.
+  fn on_account_recovered(lost: &T::AccountId, new: &T::AccountId) {
+    for ((buyer_id, registry_id), _) in EscrowedPayouts::<T>::drain_prefix(lost) {
+      Purchases::<T>::mutate(&buyer_id, &registry_id, |maybe_purchase| {
+        if let Some(purchase) = maybe_purchase {
+          if purchase.seller == *lost {
+            purchase.seller = new.clone();
+          }
+          if purchase.payouts.issuer == *lost {
+            purchase.payouts.issuer = new.clone();
+          }
+          if purchase.payouts.aggregator.as_ref() == Some(lost) {
+            purchase.payouts.aggregator = Some(new.clone());
+          }
+        }
+      });
+      EscrowedPayouts::<T>::insert(new, (buyer_id, registry_id), ());
+    }
+
+    for (registry_id, purchase) in Purchases::<T>::drain_prefix(lost) {
+      if matches!(purchase.status, PurchaseStatus::Escrowed | PurchaseStatus::Disputed) {
+        if purchase.currency == CurrencyId::Native {
+          let _ = T::Currency::repatriate_reserved(lost, new, purchase.price, BalanceStatus::Reserved);
+        }
+        Self::unindex_escrow(lost, &registry_id, &purchase);
+        Self::index_escrow(new, &registry_id, &purchase);
+      }
+      if let Some(dispute) = Disputes::<T>::take(lost, &registry_id) {
+        let _ = T::Currency::repatriate_reserved(lost, new, dispute.deposit, BalanceStatus::Reserved);
+        DisputeDeadlines::<T>::mutate(dispute.decide_by, |disputes| {
+          for (disputer_id, disputed_id) in disputes.iter_mut() {
+            if disputer_id == lost && *disputed_id == registry_id {
+              *disputer_id = new.clone();
+            }
+          }
+        });
+        Disputes::<T>::insert(new, &registry_id, dispute);
+      }
+      let recipients = Reshares::<T>::take(lost, &registry_id);
+      if !recipients.is_empty() {
+        Reshares::<T>::insert(new, &registry_id, recipients);
+      }
+      Purchases::<T>::insert(new, &registry_id, purchase);
+    }
+
+    for (asset_id, amount) in UnclaimedRefunds::<T>::drain_prefix(lost) {
+      UnclaimedRefunds::<T>::mutate(new, asset_id, |unclaimed| *unclaimed = unclaimed.saturating_add(amount));
+    }
+  }
+}
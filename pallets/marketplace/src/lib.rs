@@ -1,10 +1,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-/// Edit this file to define custom logic or remove it if it is not needed.
-/// Learn more about FRAME and the core library of Substrate FRAME pallets:
-/// <https://substrate.dev/docs/en/knowledgebase/runtime/frame>
 pub use pallet::*;
 
+pub mod constants;
+pub mod types;
+pub mod weights;
+
+mod impls;
+
 #[cfg(test)]
 mod mock;
 
@@ -16,87 +19,731 @@ mod benchmarking;
 
 #[frame_support::pallet]
 pub mod pallet {
-  use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
+  use crate::types::{
+    AssetId, Auction, AuctionKind, AuctionedRegistries, Bundle, BundleFilter, BundleId, BundleRegistries, BuyerRoles, Consent, CurrencyId,
+    DataRequest, DataRequestId, DataRequestIds, Dispute, DisputedPurchases, FulfilledRequests, LicenceTerms, Listing, Payouts, Purchase, Purpose,
+    Purposes, Recipients, RequestCriteria, RevenueSplit, SealedBid, Verdict,
+  };
+  use crate::{
+    constants::{MAX_BUNDLE_SIZE, MAX_FULFILLED_REQUESTS, MAX_REQUESTED_RECORDS, MAX_RESHARES, MAX_SEALED_BIDS},
+    weights::WeightInfo,
+  };
+  use frame_support::{
+    pallet_prelude::*,
+    sp_runtime::Permill,
+    traits::{tokens::fungibles, Currency, ReservableCurrency},
+    PalletId,
+  };
   use frame_system::pallet_prelude::*;
+  use healthcare::{RecordFormat, TherapeuticArea};
+  use pallet_meta_registry::{traits::RegistryProvider, types::RegistryId};
+  use pallet_social_network::traits::{CustodianProvider, RoleProvider};
+  use region::Region;
 
   /// Configure the pallet by specifying the parameters and types on which it depends.
   #[pallet::config]
   pub trait Config: frame_system::Config {
     /// Because this pallet emits events, it depends on the runtime's definition of an event.
     type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+    /// The currency listings are paid in, reserved from buyers while their purchase is in escrow.
+    type Currency: ReservableCurrency<Self::AccountId>;
+
+    /// The assets prices can be set in besides the native currency. Assets cannot be reserved, so
+    /// they are held by the account of the pallet while in escrow.
+    type Assets: fungibles::Transfer<Self::AccountId, AssetId = AssetId, Balance = BalanceOf<Self>>;
+
+    /// The id of the pallet, whose account holds the assets in escrow.
+    #[pallet::constant]
+    type PalletId: Get<PalletId>;
+
+    /// How long a purchase stays in escrow when the buyer neither confirms it nor gets refunded.
+    #[pallet::constant]
+    type EscrowPeriod: Get<Self::BlockNumber>;
+
+    /// Where the registries on sale are kept, and buyers are granted access.
+    type Registries: RegistryProvider<Self::AccountId>;
+
+    /// Tells whether a buyer holds one of the roles a listing is open to.
+    type Roles: RoleProvider<Self::AccountId>;
+
+    /// The share of the price paid to the issuer of a registry, unless its listing sets another.
+    #[pallet::constant]
+    type IssuerShare: Get<Permill>;
+
+    /// The share of the price paid to the aggregator of a bundle, unless the listing of the registry
+    /// bought with it sets another.
+    #[pallet::constant]
+    type AggregatorCut: Get<Permill>;
+
+    /// The share of the price of every sale paid to the treasury.
+    #[pallet::constant]
+    type TreasuryFee: Get<Permill>;
+
+    /// The account treasury fees are paid to.
+    #[pallet::constant]
+    type TreasuryAccount: Get<Self::AccountId>;
+
+    /// The custodians disputes are arbitrated by.
+    type Custodians: CustodianProvider<Self::AccountId>;
+
+    /// Reserved from buyers opening a dispute, and paid to the treasury when custodians side with
+    /// the seller.
+    #[pallet::constant]
+    type DisputeDeposit: Get<BalanceOf<Self>>;
+
+    /// How long custodians have to vote on a dispute. It must not be zero, so that every dispute
+    /// is settled in a later block than the one it was opened in.
+    #[pallet::constant]
+    type DisputePeriod: Get<Self::BlockNumber>;
+
+    /// Weight information for the extrinsics of this pallet.
+    type WeightInfo: WeightInfo;
   }
 
+  pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
   #[pallet::pallet]
   #[pallet::generate_store(pub(super) trait Store)]
   pub struct Pallet<T>(_);
 
-  // The pallet's runtime storage items.
-  // https://substrate.dev/docs/en/knowledgebase/runtime/storage
+  /// The registries on sale, at most one listing per registry.
+  #[pallet::storage]
+  #[pallet::getter(fn listings)]
+  pub type Listings<T: Config> = StorageMap<_, Blake2_128Concat, RegistryId, Listing<BalanceOf<T>, T::BlockNumber>>;
+
+  /// Listed registries holding records of a therapeutic area, keyed by therapeutic area then registry.
+  #[pallet::storage]
+  #[pallet::getter(fn listings_by_therapeutic_area)]
+  pub type ListingsByTherapeuticArea<T: Config> = StorageDoubleMap<_, Twox64Concat, TherapeuticArea, Blake2_128Concat, RegistryId, ()>;
+
+  /// Listed registries holding records in a format, keyed by format then registry.
+  #[pallet::storage]
+  #[pallet::getter(fn listings_by_format)]
+  pub type ListingsByFormat<T: Config> = StorageDoubleMap<_, Twox64Concat, RecordFormat, Blake2_128Concat, RegistryId, ()>;
+
+  /// Listed registries located in a region, keyed by region then registry.
+  #[pallet::storage]
+  #[pallet::getter(fn listings_by_region)]
+  pub type ListingsByRegion<T: Config> = StorageDoubleMap<_, Twox64Concat, Region, Blake2_128Concat, RegistryId, ()>;
+
+  /// The registries each account bought, keyed by buyer then registry.
+  #[pallet::storage]
+  #[pallet::getter(fn purchases)]
+  pub type Purchases<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, RegistryId, Purchase<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+
+  /// The purchases in escrow each account is paid a share of, as seller, issuer or aggregator, keyed
+  /// by payee then buyer and registry.
+  #[pallet::storage]
+  #[pallet::getter(fn escrowed_payouts)]
+  pub type EscrowedPayouts<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, (T::AccountId, RegistryId), ()>;
+
+  /// The accounts each buyer shared a registry it bought with, keyed by buyer then registry. They
+  /// lose their access along with the buyer.
+  #[pallet::storage]
+  #[pallet::getter(fn reshares)]
+  pub type Reshares<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, RegistryId, Recipients<T::AccountId>, ValueQuery>;
+
+  /// What the owner of each registry allows buyers to do with it. Registries without a consent
+  /// cannot be sold.
+  #[pallet::storage]
+  #[pallet::getter(fn consents)]
+  pub type Consents<T: Config> = StorageMap<_, Blake2_128Concat, RegistryId, Consent<T::BlockNumber>>;
+
+  /// The registries whose owner agreed to have them bundled by aggregators.
+  #[pallet::storage]
+  #[pallet::getter(fn aggregation_consents)]
+  pub type AggregationConsents<T: Config> = StorageMap<_, Blake2_128Concat, RegistryId, bool, ValueQuery>;
+
+  /// The bundles of registries on sale, put together by aggregators.
+  #[pallet::storage]
+  #[pallet::getter(fn bundles)]
+  pub type Bundles<T: Config> = StorageMap<_, Blake2_128Concat, BundleId, Bundle<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+
+  /// The registries researchers offer to pay for, whose budget is reserved until they are settled.
+  #[pallet::storage]
+  #[pallet::getter(fn data_requests)]
+  pub type DataRequests<T: Config> = StorageMap<_, Blake2_128Concat, DataRequestId, DataRequest<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+
+  /// Data requests not settled yet, keyed by the therapeutic area they ask for, `None` for any,
+  /// then request.
+  #[pallet::storage]
+  #[pallet::getter(fn data_requests_by_therapeutic_area)]
+  pub type DataRequestsByTherapeuticArea<T: Config> = StorageDoubleMap<_, Twox64Concat, Option<TherapeuticArea>, Blake2_128Concat, DataRequestId, ()>;
+
+  /// The data requests each registry fulfils, until they are settled.
+  #[pallet::storage]
+  #[pallet::getter(fn fulfilments)]
+  pub type Fulfilments<T: Config> = StorageMap<_, Blake2_128Concat, RegistryId, FulfilledRequests, ValueQuery>;
+
+  /// The refunds in assets each account could not receive when they were given back, keyed by
+  /// account then asset.
+  #[pallet::storage]
+  #[pallet::getter(fn unclaimed_refunds)]
+  pub type UnclaimedRefunds<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, AssetId, BalanceOf<T>, ValueQuery>;
+
+  /// The registries on auction, at most one auction per registry.
+  #[pallet::storage]
+  #[pallet::getter(fn auctions)]
+  pub type Auctions<T: Config> = StorageMap<_, Blake2_128Concat, RegistryId, Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+
+  /// The bids committed to sealed-bid auctions, keyed by registry then bidder.
   #[pallet::storage]
-  #[pallet::getter(fn something)]
-  // Learn more about declaring storage items:
-  // https://substrate.dev/docs/en/knowledgebase/runtime/storage#declaring-storage-items
-  pub type Something<T> = StorageValue<_, u32>;
+  #[pallet::getter(fn sealed_bids)]
+  pub type SealedBids<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, RegistryId, Blake2_128Concat, T::AccountId, SealedBid<T::Hash, BalanceOf<T>>>;
+
+  /// The auctions to settle at the end of each block.
+  #[pallet::storage]
+  #[pallet::getter(fn auction_ends)]
+  pub type AuctionEnds<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, AuctionedRegistries, ValueQuery>;
+
+  /// The data requests to settle at the end of each block.
+  #[pallet::storage]
+  #[pallet::getter(fn data_request_deadlines)]
+  pub type DataRequestDeadlines<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, DataRequestIds, ValueQuery>;
+
+  /// The disputes on escrowed purchases, keyed by buyer then registry.
+  #[pallet::storage]
+  #[pallet::getter(fn disputes)]
+  pub type Disputes<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, RegistryId, Dispute<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>>;
+
+  /// The disputes to settle at the end of each block.
+  #[pallet::storage]
+  #[pallet::getter(fn dispute_deadlines)]
+  pub type DisputeDeadlines<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, DisputedPurchases<T::AccountId>, ValueQuery>;
 
   // Pallets use events to inform users when important changes are made.
   // https://substrate.dev/docs/en/knowledgebase/runtime/events
   #[pallet::event]
   #[pallet::generate_deposit(pub(super) fn deposit_event)]
   pub enum Event<T: Config> {
-    /// Event documentation should end with an array that provides descriptive names for event
-    /// parameters. [something, who]
-    SomethingStored(u32, T::AccountId),
+    /// A registry was put on sale. \[registry_id, seller, price, currency\]
+    RegistryListed(RegistryId, T::AccountId, BalanceOf<T>, CurrencyId),
+    /// A registry was taken off sale. \[registry_id\]
+    RegistryDelisted(RegistryId),
+    /// A registry was bought, and its price put in escrow. \[registry_id, buyer, seller, price\]
+    RegistryPurchased(RegistryId, T::AccountId, T::AccountId, BalanceOf<T>),
+    /// The buyer confirmed it can access the registry it bought. \[registry_id, buyer\]
+    PurchaseConfirmed(RegistryId, T::AccountId),
+    /// The escrow of a purchase was paid to the seller. \[registry_id, buyer, seller, price\]
+    EscrowReleased(RegistryId, T::AccountId, T::AccountId, BalanceOf<T>),
+    /// The escrowed price of a registry was shared between its owner, issuer, the treasury and the
+    /// aggregator who bundled it. \[registry_id, buyer, payouts\]
+    RevenueShared(RegistryId, T::AccountId, Payouts<T::AccountId, BalanceOf<T>>),
+    /// The escrow of a purchase went back to the buyer. \[registry_id, buyer, price\]
+    PurchaseRefunded(RegistryId, T::AccountId, BalanceOf<T>),
+    /// The owner of a registry agreed, or not anymore, to have it bundled. \[registry_id, consent\]
+    AggregationConsentChanged(RegistryId, bool),
+    /// An aggregator put a bundle of registries on sale. \[bundle_id, aggregator, price, currency\]
+    BundleCreated(BundleId, T::AccountId, BalanceOf<T>, CurrencyId),
+    /// A bundle was taken off sale. \[bundle_id\]
+    BundleRemoved(BundleId),
+    /// A bundle was bought, and the share of each registry put in escrow. \[bundle_id, buyer, paid\]
+    BundlePurchased(BundleId, T::AccountId, BalanceOf<T>),
+    /// A researcher offered to pay for registries matching its criteria. \[request_id, researcher, price_per_record, max_records\]
+    DataRequestPosted(DataRequestId, T::AccountId, BalanceOf<T>, u32),
+    /// The owner of a matching registry agreed to fulfil a data request. \[request_id, registry_id, owner\]
+    DataRequestFulfilled(DataRequestId, RegistryId, T::AccountId),
+    /// The owners who fulfilled a data request were paid, and the researcher got access to their
+    /// registries. \[request_id, records, paid\]
+    DataRequestSettled(DataRequestId, u32, BalanceOf<T>),
+    /// A data request was not fulfilled enough before its deadline, and its budget went back to the
+    /// researcher. \[request_id\]
+    DataRequestExpired(DataRequestId),
+    /// A researcher withdrew a data request nobody fulfilled yet. \[request_id\]
+    DataRequestCancelled(DataRequestId),
+    /// The owner of a registry set what buyers may do with it. \[registry_id, purposes\]
+    ConsentGiven(RegistryId, Purposes),
+    /// The owner of a registry withdrew its consent, so the registry cannot be sold anymore. \[registry_id\]
+    ConsentWithdrawn(RegistryId),
+    /// A buyer shared a registry it bought, as the consent of the owner allowed. \[registry_id, buyer, recipient\]
+    PurchaseReshared(RegistryId, T::AccountId, T::AccountId),
+    /// An asset given back to an account it could not be transferred to was kept for it to claim.
+    /// \[who, asset_id, amount\]
+    RefundDeferred(T::AccountId, AssetId, BalanceOf<T>),
+    /// An account claimed the refunds it could not receive before. \[who, asset_id, amount\]
+    RefundClaimed(T::AccountId, AssetId, BalanceOf<T>),
+    /// A registry was put on auction. \[registry_id, seller, reserve_price, ends_at\]
+    AuctionCreated(RegistryId, T::AccountId, BalanceOf<T>, T::BlockNumber),
+    /// A bid raised the highest bid of an English auction. \[registry_id, bidder, amount\]
+    BidPlaced(RegistryId, T::AccountId, BalanceOf<T>),
+    /// A late bid pushed back the end of an English auction. \[registry_id, ends_at\]
+    AuctionExtended(RegistryId, T::BlockNumber),
+    /// A bid was committed to a sealed-bid auction. \[registry_id, bidder\]
+    BidCommitted(RegistryId, T::AccountId),
+    /// A sealed bid was revealed. \[registry_id, bidder, amount\]
+    BidRevealed(RegistryId, T::AccountId, BalanceOf<T>),
+    /// The highest bid won an auction, and was put in escrow. \[registry_id, winner, amount\]
+    AuctionWon(RegistryId, T::AccountId, BalanceOf<T>),
+    /// An auction ended without a bid that could win, and every bid was refunded. \[registry_id\]
+    AuctionFailed(RegistryId),
+    /// A registry was taken off auction before it ended, and every bid was refunded. \[registry_id\]
+    AuctionCancelled(RegistryId),
+    /// A buyer disputed a purchase still in escrow. \[registry_id, buyer, evidence\]
+    DisputeOpened(RegistryId, T::AccountId, T::Hash),
+    /// The seller answered a dispute. \[registry_id, buyer, evidence\]
+    DisputeAnswered(RegistryId, T::AccountId, T::Hash),
+    /// A custodian voted on a dispute. \[registry_id, buyer, custodian, verdict\]
+    DisputeVoted(RegistryId, T::AccountId, T::AccountId, Verdict),
+    /// A dispute was settled. \[registry_id, buyer, verdict\]
+    DisputeResolved(RegistryId, T::AccountId, Verdict),
   }
 
   // Errors inform users that something went wrong.
   #[pallet::error]
   pub enum Error<T> {
-    /// Error names should be descriptive.
-    NoneValue,
-    /// Errors should have helpful documentation associated with them.
-    StorageOverflow,
+    AlreadyAccessible,
+    AlreadyFulfilled,
+    AlreadyPurchased,
+    AlreadyVoted,
+    AuctionAlreadyExisted,
+    AuctionClosed,
+    AuctionHasBids,
+    AuctionNotExisted,
+    AuctionNotRevealing,
+    BidAlreadyExisted,
+    BidNotExisted,
+    BidTooLow,
+    BundleAlreadyExisted,
+    BundleNotExisted,
+    BuyerRoleNotAllowed,
+    ConsentNotExisted,
+    CurrencyNotSupported,
+    DataRequestAlreadyExisted,
+    DataRequestClosed,
+    DataRequestHasRecords,
+    DataRequestNotExisted,
+    DisputeNotExisted,
+    EmptyBundle,
+    EscrowNotExpired,
+    InsufficientBalance,
+    InvalidAuction,
+    InvalidBundle,
+    InvalidBuyerRoles,
+    InvalidConsent,
+    InvalidDataRequest,
+    InvalidReveal,
+    InvalidRevenueSplit,
+    ListingAlreadyExisted,
+    ListingNotExisted,
+    NotEnglishAuction,
+    NotEnoughCustodians,
+    NotEscrowed,
+    NotSealedBidAuction,
+    OnlyAggregatorAllowed,
+    OnlyOwnerAllowed,
+    OnlyPanelAllowed,
+    OnlyPatientAllowed,
+    OnlyResearcherAllowed,
+    OnlySellerAllowed,
+    OwnerCannotPurchase,
+    PurchaseNotExisted,
+    PurposeNotConsented,
+    RefundFailed,
+    RefundNotExisted,
+    RegistryNotAggregatable,
+    RegistryNotBroken,
+    RegistryNotExisted,
+    RegistryNotMatching,
+    ResharingNotAllowed,
+    ResponseAlreadyExisted,
+    TooManyAuctions,
+    TooManyBids,
+    TooManyDataRequests,
+    TooManyDisputes,
+    TooManyFulfilments,
+    TooManyReshares,
+  }
+
+  #[pallet::hooks]
+  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    /// The weight of settling in `on_finalize` what is due at the current block. Every deadline is
+    /// set in a later block than the call setting it, so nothing is added to them during the block.
+    fn on_initialize(now: T::BlockNumber) -> Weight {
+      let auctions = AuctionEnds::<T>::get(now);
+      let requests = DataRequestDeadlines::<T>::get(now);
+      let disputes = DisputeDeadlines::<T>::decode_len(now).unwrap_or(0) as Weight;
+
+      let mut weight = T::DbWeight::get().reads(3 + auctions.len() as Weight + requests.len() as Weight);
+      for registry_id in auctions.iter() {
+        let sealed_bids = Auctions::<T>::get(registry_id).map_or(0, |auction| auction.sealed_bids);
+        weight = weight.saturating_add(T::WeightInfo::settle_auction(sealed_bids));
+      }
+      for request_id in requests.iter() {
+        let records = DataRequests::<T>::get(request_id).map_or(0, |request| request.records.len() as u32);
+        weight = weight.saturating_add(T::WeightInfo::settle_data_request(records));
+      }
+
+      weight.saturating_add(T::WeightInfo::settle_dispute().saturating_mul(disputes))
+    }
+
+    /// The default shares and the treasury fee must leave the owner a non-negative rest, or every
+    /// sale of a listing without its own split would short-change the owner.
+    fn integrity_test() {
+      assert!(
+        Self::fits_in_price(&Self::split_of(&None)),
+        "IssuerShare, AggregatorCut and TreasuryFee must not add up to more than 100%"
+      );
+    }
+
+    /// Settle the auctions and data requests whose deadline is the current block.
+    fn on_finalize(now: T::BlockNumber) {
+      for registry_id in AuctionEnds::<T>::take(now) {
+        Self::settle_auction(&registry_id, &now);
+      }
+      for request_id in DataRequestDeadlines::<T>::take(now) {
+        Self::settle_data_request(&request_id, &now);
+      }
+      for (buyer_id, registry_id) in DisputeDeadlines::<T>::take(now) {
+        Self::settle_dispute(&buyer_id, &registry_id, &now);
+      }
+    }
   }
 
-  // Dispatchable functions allows users to interact with the pallet and invoke state changes.
-  // These functions materialize as "extrinsics", which are often compared to transactions.
-  // Dispatchable functions must be annotated with a weight and must return a DispatchResult.
   #[pallet::call]
   impl<T: Config> Pallet<T> {
-    /// An example dispatchable that takes a singles value as a parameter, writes the value to
-    /// storage and emits an event. This function must be dispatched by a signed extrinsic.
-    #[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-    pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
-      // Check that the extrinsic was signed and get the signer.
-      // This function will return an error if the extrinsic is not signed.
-      // https://substrate.dev/docs/en/knowledgebase/runtime/origin
+    /// Put a registry of the signer on sale. Only accounts holding one of `buyer_roles` can buy it,
+    /// and the registry cannot be deleted until it is delisted. `revenue_split` overrides the shares
+    /// of the issuer and aggregators set by the runtime.
+    #[pallet::weight(T::WeightInfo::list_registry(licence.len() as u32, buyer_roles.len() as u32))]
+    pub fn list_registry(
+      origin: OriginFor<T>,
+      registry_id: RegistryId,
+      price: BalanceOf<T>,
+      currency: CurrencyId,
+      licence: LicenceTerms,
+      buyer_roles: BuyerRoles,
+      revenue_split: Option<RevenueSplit>,
+    ) -> DispatchResult {
+      let seller = ensure_signed(origin)?;
+
+      Self::list(&seller, &registry_id, &price, &currency, &licence, &buyer_roles, &revenue_split)?;
+
+      Ok(())
+    }
+
+    /// Take a registry off sale. Buyers keep the access they paid for.
+    #[pallet::weight(T::WeightInfo::delist_registry())]
+    pub fn delist_registry(origin: OriginFor<T>, registry_id: RegistryId) -> DispatchResult {
+      let seller = ensure_signed(origin)?;
+
+      Self::delist(&seller, &registry_id)?;
+
+      Ok(())
+    }
+
+    /// Buy a listed registry for a `purpose` its owner consented to, and get `AccessType::Buyer` on
+    /// it. The price stays in escrow until the buyer confirms the purchase, or `EscrowPeriod` passes.
+    #[pallet::weight(T::WeightInfo::purchase_registry())]
+    pub fn purchase_registry(origin: OriginFor<T>, registry_id: RegistryId, purpose: Purpose) -> DispatchResult {
+      let buyer = ensure_signed(origin)?;
+
+      Self::purchase(&buyer, &registry_id, &purpose)?;
+
+      Ok(())
+    }
+
+    /// Confirm the registry bought can be accessed, which pays the seller at once.
+    #[pallet::weight(T::WeightInfo::confirm_purchase())]
+    pub fn confirm_purchase(origin: OriginFor<T>, registry_id: RegistryId) -> DispatchResult {
+      let buyer = ensure_signed(origin)?;
+
+      Self::release(&buyer, &registry_id, true)?;
+
+      Ok(())
+    }
+
+    /// Pay the seller of a purchase the buyer did not confirm, once its escrow period is over. Anyone
+    /// can release an expired escrow.
+    #[pallet::weight(T::WeightInfo::release_escrow())]
+    pub fn release_escrow(origin: OriginFor<T>, buyer: T::AccountId, registry_id: RegistryId) -> DispatchResult {
+      ensure_signed(origin)?;
+
+      Self::release(&buyer, &registry_id, false)?;
+
+      Ok(())
+    }
+
+    /// Get the escrowed price back, and give up the access, when the delivery network reported the
+    /// chunks of the registry bought as broken.
+    #[pallet::weight(T::WeightInfo::refund_purchase(MAX_RESHARES))]
+    pub fn refund_purchase(origin: OriginFor<T>, registry_id: RegistryId) -> DispatchResult {
+      let buyer = ensure_signed(origin)?;
+
+      Self::refund(&buyer, &registry_id)?;
+
+      Ok(())
+    }
+
+    /// Agree, or not anymore, to have a registry of the signer bundled by aggregators. Bundles
+    /// already created keep the registry, but it is left out of their purchases without consent.
+    #[pallet::weight(T::WeightInfo::set_aggregation_consent())]
+    pub fn set_aggregation_consent(origin: OriginFor<T>, registry_id: RegistryId, consent: bool) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::consent_to_aggregation(&owner, &registry_id, consent)?;
+
+      Ok(())
+    }
+
+    /// Put a bundle of listed registries on sale. Only aggregators can bundle, and only the
+    /// registries whose owner consented and which match `filter`.
+    #[pallet::weight(T::WeightInfo::create_bundle(registries.len() as u32, licence.len() as u32, buyer_roles.len() as u32))]
+    pub fn create_bundle(
+      origin: OriginFor<T>,
+      bundle_id: BundleId,
+      filter: BundleFilter,
+      registries: BundleRegistries,
+      price: BalanceOf<T>,
+      currency: CurrencyId,
+      licence: LicenceTerms,
+      buyer_roles: BuyerRoles,
+    ) -> DispatchResult {
+      let aggregator = ensure_signed(origin)?;
+
+      Self::bundle(&aggregator, &bundle_id, &filter, &registries, &price, &currency, &licence, &buyer_roles)?;
+
+      Ok(())
+    }
+
+    /// Take a bundle off sale. Buyers keep the accesses they paid for.
+    #[pallet::weight(T::WeightInfo::remove_bundle())]
+    pub fn remove_bundle(origin: OriginFor<T>, bundle_id: BundleId) -> DispatchResult {
+      let aggregator = ensure_signed(origin)?;
+
+      Self::unbundle(&aggregator, &bundle_id)?;
+
+      Ok(())
+    }
+
+    /// Buy every registry of a bundle that is still listed and aggregatable, consented for `purpose`
+    /// and not bought yet by the signer. Each of them is a purchase of its own, escrowed with an equal
+    /// share of the price.
+    #[pallet::weight(T::WeightInfo::purchase_bundle(MAX_BUNDLE_SIZE))]
+    pub fn purchase_bundle(origin: OriginFor<T>, bundle_id: BundleId, purpose: Purpose) -> DispatchResult {
+      let buyer = ensure_signed(origin)?;
+
+      Self::purchase_bundled(&buyer, &bundle_id, &purpose)?;
+
+      Ok(())
+    }
+
+    /// Offer to pay `price_per_record` for each registry matching `criteria` and consented for
+    /// `purpose`, listed or not. The whole budget is reserved until the request is settled, at
+    /// `deadline` or once `max_records` registries fulfilled it. Only researchers can post data
+    /// requests.
+    #[pallet::weight(T::WeightInfo::post_data_request(licence.len() as u32))]
+    pub fn post_data_request(
+      origin: OriginFor<T>,
+      request_id: DataRequestId,
+      criteria: RequestCriteria,
+      price_per_record: BalanceOf<T>,
+      currency: CurrencyId,
+      min_records: u32,
+      max_records: u32,
+      licence: LicenceTerms,
+      purpose: Purpose,
+      deadline: T::BlockNumber,
+    ) -> DispatchResult {
+      let researcher = ensure_signed(origin)?;
+
+      Self::post_request(
+        &researcher,
+        &request_id,
+        &criteria,
+        &price_per_record,
+        &currency,
+        &min_records,
+        &max_records,
+        &licence,
+        &purpose,
+        &deadline,
+      )?;
+
+      Ok(())
+    }
+
+    /// Agree to fulfil a data request with a matching registry of the signer. The owner is paid, and
+    /// the researcher granted `AccessType::Buyer`, when the request is settled with enough records.
+    #[pallet::weight(T::WeightInfo::fulfil_data_request().saturating_add(T::WeightInfo::settle_data_request(MAX_REQUESTED_RECORDS)))]
+    pub fn fulfil_data_request(origin: OriginFor<T>, request_id: DataRequestId, registry_id: RegistryId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::fulfil_request(&owner, &request_id, &registry_id)?;
+
+      Ok(())
+    }
+
+    /// Withdraw a data request nobody fulfilled yet, and get its budget back.
+    #[pallet::weight(T::WeightInfo::cancel_data_request())]
+    pub fn cancel_data_request(origin: OriginFor<T>, request_id: DataRequestId) -> DispatchResult {
+      let researcher = ensure_signed(origin)?;
+
+      Self::cancel_request(&researcher, &request_id)?;
+
+      Ok(())
+    }
+
+    /// Set what buyers may do with a registry of the signer, replacing any previous consent. Buyers
+    /// keep what they were allowed when they bought the registry.
+    #[pallet::weight(T::WeightInfo::give_consent(purposes.len() as u32))]
+    pub fn give_consent(
+      origin: OriginFor<T>,
+      registry_id: RegistryId,
+      purposes: Purposes,
+      expires_at: Option<T::BlockNumber>,
+      resharing: bool,
+    ) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::consent(&owner, &registry_id, &purposes, &expires_at, &resharing)?;
+
+      Ok(())
+    }
+
+    /// Withdraw the consent given for a registry of the signer. The registry is delisted, its
+    /// auction is cancelled and every bid refunded, and it is left out of the bundles and data
+    /// requests not settled yet, but past purchases are kept.
+    #[pallet::weight(T::WeightInfo::withdraw_consent(MAX_SEALED_BIDS, MAX_FULFILLED_REQUESTS))]
+    pub fn withdraw_consent(origin: OriginFor<T>, registry_id: RegistryId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::withdraw(&owner, &registry_id)?;
+
+      Ok(())
+    }
+
+    /// Share a registry the signer bought with `recipient`, when the consent of the owner allowed
+    /// resharing at the time of the purchase and still covers its purpose. The recipient loses its
+    /// access whenever the signer does.
+    #[pallet::weight(T::WeightInfo::reshare_purchase())]
+    pub fn reshare_purchase(origin: OriginFor<T>, registry_id: RegistryId, recipient: T::AccountId) -> DispatchResult {
+      let buyer = ensure_signed(origin)?;
+
+      Self::reshare(&buyer, &registry_id, &recipient)?;
+
+      Ok(())
+    }
+
+    /// Claim the refunds in `asset_id` the signer could not receive when they were given back.
+    #[pallet::weight(T::WeightInfo::claim_refund())]
+    pub fn claim_refund(origin: OriginFor<T>, asset_id: AssetId) -> DispatchResult {
       let who = ensure_signed(origin)?;
 
-      // Update storage.
-      <Something<T>>::put(something);
+      Self::claim(&who, &asset_id)?;
 
-      // Emit an event.
-      Self::deposit_event(Event::SomethingStored(something, who));
-      // Return a successful DispatchResultWithPostInfo
       Ok(())
     }
 
-    /// An example dispatchable that may throw a custom error.
-    #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
-    pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-      let _who = ensure_signed(origin)?;
+    /// Put a registry of the signer on auction for `duration` blocks, instead of listing it. The
+    /// winning bid is escrowed like the price of a purchase, and shared the same way.
+    #[pallet::weight(T::WeightInfo::create_auction(licence.len() as u32, buyer_roles.len() as u32))]
+    pub fn create_auction(
+      origin: OriginFor<T>,
+      registry_id: RegistryId,
+      kind: AuctionKind<T::BlockNumber>,
+      reserve_price: BalanceOf<T>,
+      currency: CurrencyId,
+      licence: LicenceTerms,
+      buyer_roles: BuyerRoles,
+      revenue_split: Option<RevenueSplit>,
+      duration: T::BlockNumber,
+    ) -> DispatchResult {
+      let seller = ensure_signed(origin)?;
 
-      // Read a value from storage.
-      match <Something<T>>::get() {
-        // Return an error if the value has not been set.
-        None => Err(Error::<T>::NoneValue)?,
-        Some(old) => {
-          // Increment the value read from storage; will error in the event of overflow.
-          let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-          // Update the value in storage with the incremented result.
-          <Something<T>>::put(new);
-          Ok(())
-        },
-      }
+      Self::auction(
+        &seller,
+        &registry_id,
+        &kind,
+        &reserve_price,
+        &currency,
+        &licence,
+        &buyer_roles,
+        &revenue_split,
+        &duration,
+      )?;
+
+      Ok(())
+    }
+
+    /// Take a registry off auction, as long as nobody bid.
+    #[pallet::weight(T::WeightInfo::cancel_auction())]
+    pub fn cancel_auction(origin: OriginFor<T>, registry_id: RegistryId) -> DispatchResult {
+      let seller = ensure_signed(origin)?;
+
+      Self::cancel_auctioned(&seller, &registry_id)?;
+
+      Ok(())
+    }
+
+    /// Raise the highest bid of an English auction. The bid is reserved until the signer is outbid,
+    /// when it is refunded, or wins the auction.
+    #[pallet::weight(T::WeightInfo::place_bid())]
+    pub fn place_bid(origin: OriginFor<T>, registry_id: RegistryId, amount: BalanceOf<T>, purpose: Purpose) -> DispatchResult {
+      let bidder = ensure_signed(origin)?;
+
+      Self::bid(&bidder, &registry_id, &amount, &purpose)?;
+
+      Ok(())
+    }
+
+    /// Commit to a bid of a sealed-bid auction with the hash of the signer, the amount and a salt.
+    /// `deposit` is reserved until the auction is settled, and must cover the amount revealed.
+    #[pallet::weight(T::WeightInfo::commit_bid())]
+    pub fn commit_bid(origin: OriginFor<T>, registry_id: RegistryId, commitment: T::Hash, deposit: BalanceOf<T>, purpose: Purpose) -> DispatchResult {
+      let bidder = ensure_signed(origin)?;
+
+      Self::commit(&bidder, &registry_id, &commitment, &deposit, &purpose)?;
+
+      Ok(())
+    }
+
+    /// Reveal a bid committed to a sealed-bid auction, once the auction ended. Bids that are not
+    /// revealed cannot win, and are refunded when the auction is settled.
+    #[pallet::weight(T::WeightInfo::reveal_bid())]
+    pub fn reveal_bid(origin: OriginFor<T>, registry_id: RegistryId, amount: BalanceOf<T>, salt: [u8; 32]) -> DispatchResult {
+      let bidder = ensure_signed(origin)?;
+
+      Self::reveal(&bidder, &registry_id, &amount, &salt)?;
+
+      Ok(())
+    }
+
+    /// Dispute a purchase of the signer still in escrow, which cannot be released anymore until
+    /// custodians settle it. `evidence` is the hash of what the buyer found wrong with the records,
+    /// and the buyer reserves a deposit it loses if custodians side with the seller.
+    #[pallet::weight(T::WeightInfo::open_dispute())]
+    pub fn open_dispute(origin: OriginFor<T>, registry_id: RegistryId, evidence: T::Hash) -> DispatchResult {
+      let buyer = ensure_signed(origin)?;
+
+      Self::dispute(&buyer, &registry_id, &evidence)?;
+
+      Ok(())
+    }
+
+    /// Answer the dispute of `buyer` on a sale of the signer with the hash of the evidence of the seller.
+    #[pallet::weight(T::WeightInfo::answer_dispute())]
+    pub fn answer_dispute(origin: OriginFor<T>, buyer: T::AccountId, registry_id: RegistryId, evidence: T::Hash) -> DispatchResult {
+      let seller = ensure_signed(origin)?;
+
+      Self::answer(&seller, &buyer, &registry_id, &evidence)?;
+
+      Ok(())
+    }
+
+    /// Vote on a dispute the signer arbitrates. The dispute is settled as soon as a majority of the
+    /// panel agrees on a verdict, and otherwise at the end of the dispute period.
+    #[pallet::weight(T::WeightInfo::vote_on_dispute())]
+    pub fn vote_on_dispute(origin: OriginFor<T>, buyer: T::AccountId, registry_id: RegistryId, verdict: Verdict) -> DispatchResult {
+      let custodian = ensure_signed(origin)?;
+
+      Self::vote(&custodian, &buyer, &registry_id, &verdict)?;
+
+      Ok(())
     }
   }
 }
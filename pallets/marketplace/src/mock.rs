@@ -1,12 +1,22 @@
 use crate as pallet_template;
-use frame_support::parameter_types;
+use frame_support::{assert_ok, parameter_types, PalletId};
 use frame_system as system;
+use healthcare::{RecordFormat, Role, TherapeuticArea};
+use pallet_meta_registry::{
+  traits::RegistryProvider,
+  types::{AccessType, RegistryId, RegistryProfile},
+};
+use pallet_social_network::traits::{CustodianProvider, RoleProvider};
+use region::{Country, Region};
 use sp_core::H256;
 use sp_runtime::{
   testing::Header,
   traits::{BlakeTwo256, IdentityLookup},
+  DispatchResult, Permill,
 };
 
+use std::cell::RefCell;
+
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -18,6 +28,8 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
         TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
     }
 );
@@ -25,8 +37,28 @@ frame_support::construct_runtime!(
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const SS58Prefix: u8 = 42;
+    pub const ExistentialDeposit: u64 = 1;
+    pub const EscrowPeriod: u64 = 10;
+    pub const IssuerShare: Permill = Permill::from_percent(10);
+    pub const AggregatorCut: Permill = Permill::from_percent(20);
+    pub const TreasuryFee: Permill = Permill::from_percent(5);
+    pub const TreasuryAccount: u64 = TREASURY;
+    pub const MarketplacePalletId: PalletId = PalletId(*b"py/mrkpl");
+    pub const DisputeDeposit: u64 = 50;
+    pub const DisputePeriod: u64 = 5;
+    pub const AssetDeposit: u64 = 0;
+    pub const ApprovalDeposit: u64 = 0;
+    pub const StringLimit: u32 = 50;
+    pub const MetadataDepositBase: u64 = 0;
+    pub const MetadataDepositPerByte: u64 = 0;
 }
 
+/// The issuer of every registry.
+pub const ISSUER: u64 = 99;
+pub const TREASURY: u64 = 98;
+/// A stablecoin buyers can pay with, besides the native currency.
+pub const STABLECOIN: u32 = 1;
+
 impl system::Config for Test {
   type BaseCallFilter = frame_support::traits::Everything;
   type BlockWeights = ();
@@ -45,7 +77,7 @@ impl system::Config for Test {
   type BlockHashCount = BlockHashCount;
   type Version = ();
   type PalletInfo = PalletInfo;
-  type AccountData = ();
+  type AccountData = pallet_balances::AccountData<u64>;
   type OnNewAccount = ();
   type OnKilledAccount = ();
   type SystemWeightInfo = ();
@@ -53,11 +85,247 @@ impl system::Config for Test {
   type OnSetCode = ();
 }
 
+impl pallet_balances::Config for Test {
+  type MaxLocks = ();
+  type MaxReserves = ();
+  type ReserveIdentifier = [u8; 8];
+  type Balance = u64;
+  type Event = Event;
+  type DustRemoval = ();
+  type ExistentialDeposit = ExistentialDeposit;
+  type AccountStore = System;
+  type WeightInfo = ();
+}
+
+impl pallet_assets::Config for Test {
+  type Event = Event;
+  type Balance = u64;
+  type AssetId = u32;
+  type Currency = Balances;
+  type ForceOrigin = frame_system::EnsureRoot<u64>;
+  type AssetDeposit = AssetDeposit;
+  type MetadataDepositBase = MetadataDepositBase;
+  type MetadataDepositPerByte = MetadataDepositPerByte;
+  type ApprovalDeposit = ApprovalDeposit;
+  type StringLimit = StringLimit;
+  type Freezer = ();
+  type Extra = ();
+  type WeightInfo = ();
+}
+
+thread_local! {
+  pub static REGISTRIES: RefCell<Vec<(RegistryId, u64, bool)>> = RefCell::new(Vec::new());
+  pub static ACCESSES: RefCell<Vec<(RegistryId, u64, AccessType)>> = RefCell::new(Vec::new());
+  pub static BROKEN: RefCell<Vec<RegistryId>> = RefCell::new(Vec::new());
+  pub static PROFILES: RefCell<Vec<(RegistryId, RegistryProfile)>> = RefCell::new(Vec::new());
+  pub static ROLES: RefCell<Vec<(u64, Role)>> = RefCell::new(Vec::new());
+  pub static CUSTODIANS: RefCell<Vec<u64>> = RefCell::new(vec![40, 41, 42]);
+}
+
+/// Registries for tests, driven by `REGISTRIES`, `PROFILES`, `ACCESSES` and `BROKEN` instead of
+/// `pallet_meta_registry`.
+pub struct MockRegistries;
+
+impl RegistryProvider<u64> for MockRegistries {
+  fn owner_of(registry_id: &RegistryId) -> Option<u64> {
+    REGISTRIES.with(|registries| {
+      registries
+        .borrow()
+        .iter()
+        .find(|(id, _, _)| id == registry_id)
+        .map(|(_, owner, _)| *owner)
+    })
+  }
+
+  fn issuer_of(registry_id: &RegistryId) -> Option<u64> {
+    Self::owner_of(registry_id).map(|_| ISSUER)
+  }
+
+  fn profile_of(registry_id: &RegistryId) -> Option<RegistryProfile> {
+    PROFILES.with(|profiles| {
+      profiles
+        .borrow()
+        .iter()
+        .find(|(id, _)| id == registry_id)
+        .map(|(_, profile)| profile.clone())
+    })
+  }
+
+  fn registries_of(who: &u64) -> Vec<RegistryId> {
+    REGISTRIES.with(|registries| {
+      registries
+        .borrow()
+        .iter()
+        .filter(|(_, owner, _)| owner == who)
+        .map(|(id, _, _)| id.clone())
+        .collect()
+    })
+  }
+
+  fn set_salable(registry_id: &RegistryId, salable: bool) -> DispatchResult {
+    REGISTRIES.with(|registries| {
+      for registry in registries.borrow_mut().iter_mut().filter(|(id, _, _)| id == registry_id) {
+        registry.2 = salable;
+      }
+    });
+    Ok(())
+  }
+
+  fn granted_access(registry_id: &RegistryId, who: &u64) -> Option<AccessType> {
+    ACCESSES.with(|accesses| {
+      accesses
+        .borrow()
+        .iter()
+        .find(|(id, account, _)| id == registry_id && account == who)
+        .map(|(_, _, access_type)| *access_type)
+    })
+  }
+
+  fn grant_access(registry_id: &RegistryId, who: &u64, access_type: &AccessType) -> DispatchResult {
+    ACCESSES.with(|accesses| accesses.borrow_mut().push((registry_id.clone(), *who, *access_type)));
+    Ok(())
+  }
+
+  fn revoke_access(registry_id: &RegistryId, who: &u64, access_type: &AccessType) -> DispatchResult {
+    ACCESSES.with(|accesses| {
+      accesses
+        .borrow_mut()
+        .retain(|access| *access != (registry_id.clone(), *who, *access_type))
+    });
+    Ok(())
+  }
+
+  fn is_broken(registry_id: &RegistryId) -> bool {
+    BROKEN.with(|broken| broken.borrow().contains(registry_id))
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn register_registry(registry_id: &RegistryId, owner: &u64, _issuer: &u64, profile: &RegistryProfile) {
+    profiled_registry(registry_id, *owner, profile.clone());
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn break_registry(registry_id: &RegistryId) {
+    BROKEN.with(|broken| broken.borrow_mut().push(registry_id.clone()));
+  }
+}
+
+/// Accounts 1 to 9 are patients, 10 to 19 researchers, 20 to 29 aggregators and the others have no
+/// role, unless `ROLES` assigns them one.
+pub struct MockRoles;
+
+impl RoleProvider<u64> for MockRoles {
+  fn roles_of(who: &u64) -> Vec<Role> {
+    let assigned: Vec<Role> = ROLES.with(|roles| roles.borrow().iter().filter(|(id, _)| id == who).map(|(_, role)| *role).collect());
+    if !assigned.is_empty() {
+      return assigned;
+    }
+
+    match who {
+      1..=9 => vec![Role::Patient],
+      10..=19 => vec![Role::Researcher],
+      20..=29 => vec![Role::Aggregator],
+      _ => Vec::new(),
+    }
+  }
+
+  #[allow(unused_variables)]
+  fn enterprise_of(who: &u64) -> Option<u64> {
+    None
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn assign_role(who: &u64, role: &Role) {
+    ROLES.with(|roles| roles.borrow_mut().push((*who, *role)));
+  }
+}
+
+/// The custodians in `CUSTODIANS`, accounts 40 to 42 to begin with.
+pub struct MockCustodians;
+
+impl CustodianProvider<u64> for MockCustodians {
+  fn custodians() -> Vec<u64> {
+    CUSTODIANS.with(|custodians| custodians.borrow().clone())
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn register_custodian(who: &u64) {
+    CUSTODIANS.with(|custodians| custodians.borrow_mut().push(*who));
+  }
+}
+
 impl pallet_template::Config for Test {
   type Event = Event;
+  type Currency = Balances;
+  type Assets = Assets;
+  type PalletId = MarketplacePalletId;
+  type EscrowPeriod = EscrowPeriod;
+  type Registries = MockRegistries;
+  type Roles = MockRoles;
+  type IssuerShare = IssuerShare;
+  type AggregatorCut = AggregatorCut;
+  type TreasuryFee = TreasuryFee;
+  type TreasuryAccount = TreasuryAccount;
+  type Custodians = MockCustodians;
+  type DisputeDeposit = DisputeDeposit;
+  type DisputePeriod = DisputePeriod;
+  type WeightInfo = ();
 }
 
-// Build genesis storage according to the mock runtime.
+/// Registry `id` owned by `owner`, not on sale yet, holding cardiovascular FHIR R4 records from the
+/// Netherlands.
+pub fn registry(id: &[u8], owner: u64) -> RegistryId {
+  profiled_registry(
+    id,
+    owner,
+    RegistryProfile {
+      therapeutic_area: Some(TherapeuticArea::Cardiovascular),
+      format: RecordFormat::FhirR4,
+      country: Country::Netherlands,
+      region: Region::Europe,
+    },
+  )
+}
+
+/// Registry `id` owned by `owner`, not on sale yet, holding what `profile` tells.
+pub fn profiled_registry(id: &[u8], owner: u64, profile: RegistryProfile) -> RegistryId {
+  let registry_id: RegistryId = id.to_vec().try_into().unwrap();
+  REGISTRIES.with(|registries| registries.borrow_mut().push((registry_id.clone(), owner, false)));
+  PROFILES.with(|profiles| profiles.borrow_mut().push((registry_id.clone(), profile)));
+  registry_id
+}
+
+pub fn has_access(registry_id: &RegistryId, who: u64, access_type: AccessType) -> bool {
+  ACCESSES.with(|accesses| accesses.borrow().contains(&(registry_id.clone(), who, access_type)))
+}
+
+/// Create `STABLECOIN` and mint 1_000 of it to every account of `holders`.
+pub fn stablecoin(holders: Vec<u64>) {
+  assert_ok!(Assets::force_create(Origin::root(), STABLECOIN, 0, true, 1));
+  for holder in holders {
+    assert_ok!(Assets::mint(Origin::signed(0), STABLECOIN, holder, 1_000));
+  }
+}
+
+pub fn is_salable(registry_id: &RegistryId) -> bool {
+  REGISTRIES.with(|registries| registries.borrow().iter().any(|(id, _, salable)| id == registry_id && *salable))
+}
+
+// Build genesis storage according to the mock runtime, and reset the mocks, which benchmarks run
+// one after another on the same thread.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-  system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+  REGISTRIES.with(|registries| registries.borrow_mut().clear());
+  ACCESSES.with(|accesses| accesses.borrow_mut().clear());
+  BROKEN.with(|broken| broken.borrow_mut().clear());
+  PROFILES.with(|profiles| profiles.borrow_mut().clear());
+  ROLES.with(|roles| roles.borrow_mut().clear());
+  CUSTODIANS.with(|custodians| *custodians.borrow_mut() = vec![40, 41, 42]);
+
+  let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+  pallet_balances::GenesisConfig::<Test> {
+    balances: (1..30).map(|who| (who, 1_000)).collect(),
+  }
+  .assimilate_storage(&mut storage)
+  .unwrap();
+  storage.into()
 }
@@ -1,20 +1,1882 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+  mock::*,
+  types::{
+    AuctionKind, BundleFilter, BundleId, BundleRegistries, BuyerRoles, CurrencyId, DataRequestId, ListingFilter, Payouts, PurchaseStatus, Purpose,
+    Purposes, RequestCriteria, RevenueSplit, Verdict,
+  },
+  Error,
+};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchResult, traits::Hooks};
+use healthcare::{RecordFormat, Role, TherapeuticArea};
+use pallet_meta_registry::types::{AccessType, RegistryId, RegistryProfile};
+use pallet_social_network::traits::OnAccountRecovered;
+use region::{Country, Region};
+use sp_core::H256;
+use sp_runtime::{
+  traits::{BlakeTwo256, Hash},
+  Permill,
+};
+
+fn buyer_roles(roles: Vec<Role>) -> BuyerRoles {
+  roles.try_into().unwrap()
+}
+
+fn purposes(purposes: Vec<Purpose>) -> Purposes {
+  purposes.try_into().unwrap()
+}
+
+/// Let buyers use `registry_id` for research, without re-sharing it.
+fn consent_to_research(registry_id: &RegistryId, owner: u64) {
+  assert_ok!(TemplateModule::give_consent(
+    Origin::signed(owner),
+    registry_id.clone(),
+    purposes(vec![Purpose::AcademicResearch, Purpose::CommercialResearch]),
+    None,
+    false
+  ));
+}
+
+fn listed(owner: u64, price: u64) -> RegistryId {
+  let registry_id = registry(b"registry", owner);
+  consent_to_research(&registry_id, owner);
+
+  assert_ok!(TemplateModule::list_registry(
+    Origin::signed(owner),
+    registry_id.clone(),
+    price,
+    CurrencyId::Native,
+    Default::default(),
+    buyer_roles(vec![Role::Researcher]),
+    None
+  ));
+
+  registry_id
+}
+
+/// Registry `id` of `owner`, listed for researchers at 100 and consenting to be bundled.
+fn aggregatable(id: &[u8], owner: u64) -> RegistryId {
+  let registry_id = registry(id, owner);
+  consent_to_research(&registry_id, owner);
+
+  assert_ok!(TemplateModule::list_registry(
+    Origin::signed(owner),
+    registry_id.clone(),
+    100,
+    CurrencyId::Native,
+    Default::default(),
+    buyer_roles(vec![Role::Researcher]),
+    None
+  ));
+  assert_ok!(TemplateModule::set_aggregation_consent(Origin::signed(owner), registry_id.clone(), true));
+
+  registry_id
+}
+
+fn bundle_id(id: &[u8]) -> BundleId {
+  id.to_vec().try_into().unwrap()
+}
+
+fn bundle_registries(registries: Vec<RegistryId>) -> BundleRegistries {
+  registries.try_into().unwrap()
+}
+
+fn create_bundle(aggregator: u64, id: &[u8], registries: Vec<RegistryId>, price: u64) -> DispatchResult {
+  TemplateModule::create_bundle(
+    Origin::signed(aggregator),
+    bundle_id(id),
+    BundleFilter {
+      therapeutic_area: Some(TherapeuticArea::Cardiovascular),
+      format: None,
+      region: Some(Region::Europe),
+    },
+    bundle_registries(registries),
+    price,
+    CurrencyId::Native,
+    Default::default(),
+    buyer_roles(vec![Role::Researcher]),
+  )
+}
+
+fn request_id(id: &[u8]) -> DataRequestId {
+  id.to_vec().try_into().unwrap()
+}
+
+/// A data request of `researcher` paying 10 per cardiovascular registry from the Netherlands.
+fn post_data_request(researcher: u64, id: &[u8], min_records: u32, max_records: u32, deadline: u64) -> DispatchResult {
+  TemplateModule::post_data_request(
+    Origin::signed(researcher),
+    request_id(id),
+    RequestCriteria {
+      therapeutic_area: Some(TherapeuticArea::Cardiovascular),
+      country: Some(Country::Netherlands),
+      ..Default::default()
+    },
+    10,
+    CurrencyId::Native,
+    min_records,
+    max_records,
+    Default::default(),
+    Purpose::AcademicResearch,
+    deadline,
+  )
+}
+
+/// An auction of `registry_id` for researchers, with a reserve price of 100 and lasting 10 blocks.
+fn create_auction(owner: u64, registry_id: &RegistryId, kind: AuctionKind<u64>) -> DispatchResult {
+  TemplateModule::create_auction(
+    Origin::signed(owner),
+    registry_id.clone(),
+    kind,
+    100,
+    CurrencyId::Native,
+    Default::default(),
+    buyer_roles(vec![Role::Researcher]),
+    None,
+    10,
+  )
+}
+
+fn commitment(bidder: u64, amount: u64, salt: [u8; 32]) -> H256 {
+  BlakeTwo256::hash_of(&(bidder, amount, salt))
+}
+
+fn assert_event(event: crate::Event<Test>) {
+  System::assert_last_event(Event::TemplateModule(event));
+}
+
+#[test]
+fn owners_list_and_delist_their_registries() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry(b"registry", 1);
+
+    assert_noop!(
+      TemplateModule::list_registry(
+        Origin::signed(2),
+        registry_id.clone(),
+        100,
+        CurrencyId::Native,
+        Default::default(),
+        buyer_roles(vec![Role::Researcher]),
+        None
+      ),
+      Error::<Test>::OnlyOwnerAllowed
+    );
+    assert_noop!(
+      TemplateModule::list_registry(
+        Origin::signed(1),
+        registry_id.clone(),
+        100,
+        CurrencyId::Native,
+        Default::default(),
+        buyer_roles(vec![Role::Researcher]),
+        None
+      ),
+      Error::<Test>::ConsentNotExisted
+    );
+
+    consent_to_research(&registry_id, 1);
+    assert_noop!(
+      TemplateModule::list_registry(
+        Origin::signed(1),
+        registry_id.clone(),
+        100,
+        CurrencyId::Native,
+        Default::default(),
+        buyer_roles(vec![]),
+        None
+      ),
+      Error::<Test>::InvalidBuyerRoles
+    );
+    assert_ok!(TemplateModule::list_registry(
+      Origin::signed(1),
+      registry_id.clone(),
+      100,
+      CurrencyId::Native,
+      Default::default(),
+      buyer_roles(vec![Role::Researcher]),
+      None
+    ));
+    assert_event(crate::Event::RegistryListed(registry_id.clone(), 1, 100, CurrencyId::Native));
+    assert!(is_salable(&registry_id));
+    assert_eq!(TemplateModule::listings(&registry_id).unwrap().price, 100);
+
+    assert_noop!(
+      TemplateModule::delist_registry(Origin::signed(2), registry_id.clone()),
+      Error::<Test>::OnlyOwnerAllowed
+    );
+    assert_ok!(TemplateModule::delist_registry(Origin::signed(1), registry_id.clone()));
+    assert_event(crate::Event::RegistryDelisted(registry_id.clone()));
+    assert!(!is_salable(&registry_id));
+    assert_eq!(TemplateModule::listings(&registry_id), None);
+    assert_noop!(
+      TemplateModule::delist_registry(Origin::signed(1), registry_id),
+      Error::<Test>::ListingNotExisted
+    );
+  });
+}
+
+#[test]
+fn purchases_are_escrowed_and_grant_buyer_access() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = listed(1, 100);
+
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+    assert_event(crate::Event::RegistryPurchased(registry_id.clone(), 10, 1, 100));
+    assert_eq!(Balances::reserved_balance(10), 100);
+    assert_eq!(Balances::free_balance(1), 1_000);
+    assert!(has_access(&registry_id, 10, AccessType::Buyer));
+
+    let purchase = TemplateModule::purchases(10, &registry_id).unwrap();
+    assert_eq!((purchase.seller, purchase.status, purchase.release_at), (1, PurchaseStatus::Escrowed, 11));
+
+    assert_noop!(
+      TemplateModule::purchase_registry(Origin::signed(10), registry_id.clone(), Purpose::AcademicResearch),
+      Error::<Test>::AlreadyPurchased
+    );
+  });
+}
+
+#[test]
+fn only_allowed_roles_with_enough_funds_can_purchase() {
+  new_test_ext().execute_with(|| {
+    let registry_id = listed(1, 100);
+
+    assert_noop!(
+      TemplateModule::purchase_registry(Origin::signed(1), registry_id.clone(), Purpose::AcademicResearch),
+      Error::<Test>::OwnerCannotPurchase
+    );
+    assert_noop!(
+      TemplateModule::purchase_registry(Origin::signed(2), registry_id.clone(), Purpose::AcademicResearch),
+      Error::<Test>::BuyerRoleNotAllowed
+    );
+
+    let expensive = registry(b"expensive", 1);
+    consent_to_research(&expensive, 1);
+    assert_ok!(TemplateModule::list_registry(
+      Origin::signed(1),
+      expensive.clone(),
+      5_000,
+      CurrencyId::Native,
+      Default::default(),
+      buyer_roles(vec![Role::Researcher]),
+      None
+    ));
+    assert_noop!(
+      TemplateModule::purchase_registry(Origin::signed(10), expensive, Purpose::AcademicResearch),
+      Error::<Test>::InsufficientBalance
+    );
+  });
+}
+
+#[test]
+fn accounts_with_access_to_a_registry_cannot_purchase_it() {
+  new_test_ext().execute_with(|| {
+    let registry_id = listed(1, 100);
+    ACCESSES.with(|accesses| accesses.borrow_mut().push((registry_id.clone(), 10, AccessType::Accessor)));
+
+    assert_noop!(
+      TemplateModule::purchase_registry(Origin::signed(10), registry_id.clone(), Purpose::AcademicResearch),
+      Error::<Test>::AlreadyAccessible
+    );
+
+    let auctioned = registry(b"auctioned", 1);
+    consent_to_research(&auctioned, 1);
+    assert_ok!(create_auction(1, &auctioned, AuctionKind::English { extension: 3 }));
+    ACCESSES.with(|accesses| accesses.borrow_mut().push((auctioned.clone(), 10, AccessType::Accessor)));
+    assert_noop!(
+      TemplateModule::place_bid(Origin::signed(10), auctioned, 100, Purpose::AcademicResearch),
+      Error::<Test>::AlreadyAccessible
+    );
+  });
+}
+
+#[test]
+fn bundles_and_data_requests_leave_out_registries_already_accessible() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let first = aggregatable(b"first", 1);
+    let second = aggregatable(b"second", 2);
+    assert_ok!(create_bundle(20, b"bundle", vec![first.clone(), second.clone()], 100));
+    ACCESSES.with(|accesses| accesses.borrow_mut().push((first.clone(), 10, AccessType::Accessor)));
+
+    assert_ok!(TemplateModule::purchase_bundle(
+      Origin::signed(10),
+      bundle_id(b"bundle"),
+      Purpose::AcademicResearch
+    ));
+    assert_eq!(TemplateModule::purchases(10, &first), None);
+    assert_eq!(TemplateModule::purchases(10, &second).unwrap().price, 100);
+
+    let third = registry(b"third", 3);
+    let fourth = registry(b"fourth", 4);
+    consent_to_research(&third, 3);
+    consent_to_research(&fourth, 4);
+    assert_ok!(post_data_request(11, b"request", 1, 5, 5));
+    ACCESSES.with(|accesses| accesses.borrow_mut().push((third.clone(), 11, AccessType::Accessor)));
+    assert_noop!(
+      TemplateModule::fulfil_data_request(Origin::signed(3), request_id(b"request"), third),
+      Error::<Test>::AlreadyAccessible
+    );
+
+    // An access granted after the registry was added to the request is not paid for again.
+    assert_ok!(TemplateModule::fulfil_data_request(
+      Origin::signed(4),
+      request_id(b"request"),
+      fourth.clone()
+    ));
+    ACCESSES.with(|accesses| accesses.borrow_mut().push((fourth.clone(), 11, AccessType::Accessor)));
+    System::set_block_number(5);
+    TemplateModule::on_finalize(5);
+    assert_event(crate::Event::DataRequestExpired(request_id(b"request")));
+    assert_eq!(Balances::free_balance(4), 1_000);
+    assert_eq!(Balances::free_balance(11), 1_000);
+    assert_eq!(TemplateModule::purchases(11, &fourth), None);
+  });
+}
+
+#[test]
+fn delisted_registries_cannot_be_purchased() {
+  new_test_ext().execute_with(|| {
+    let registry_id = listed(1, 100);
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+    assert_ok!(TemplateModule::delist_registry(Origin::signed(1), registry_id.clone()));
+
+    assert_noop!(
+      TemplateModule::purchase_registry(Origin::signed(11), registry_id.clone(), Purpose::AcademicResearch),
+      Error::<Test>::ListingNotExisted
+    );
+    // Buyers keep what they paid for.
+    assert!(TemplateModule::purchases(10, &registry_id).is_some());
+  });
+}
+
+#[test]
+fn confirming_a_purchase_pays_the_seller() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = listed(1, 100);
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+
+    assert_noop!(
+      TemplateModule::confirm_purchase(Origin::signed(11), registry_id.clone()),
+      Error::<Test>::PurchaseNotExisted
+    );
+    assert_ok!(TemplateModule::confirm_purchase(Origin::signed(10), registry_id.clone()));
+    System::assert_has_event(Event::TemplateModule(crate::Event::PurchaseConfirmed(registry_id.clone(), 10)));
+    assert_event(crate::Event::EscrowReleased(registry_id.clone(), 10, 1, 100));
+    assert_eq!(Balances::reserved_balance(10), 0);
+    assert_eq!(Balances::free_balance(10), 900);
+    // The issuer gets 10% and the treasury 5% of the price.
+    assert_eq!(Balances::free_balance(1), 1_085);
+    assert_eq!(Balances::free_balance(ISSUER), 10);
+    assert_eq!(Balances::free_balance(TREASURY), 5);
+    assert_eq!(TemplateModule::purchases(10, &registry_id).unwrap().status, PurchaseStatus::Released);
+
+    assert_noop!(
+      TemplateModule::confirm_purchase(Origin::signed(10), registry_id),
+      Error::<Test>::NotEscrowed
+    );
+  });
+}
+
+#[test]
+fn unconfirmed_escrows_are_released_after_the_escrow_period() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = listed(1, 100);
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+
+    System::set_block_number(10);
+    assert_noop!(
+      TemplateModule::release_escrow(Origin::signed(1), 10, registry_id.clone()),
+      Error::<Test>::EscrowNotExpired
+    );
+
+    System::set_block_number(11);
+    assert_ok!(TemplateModule::release_escrow(Origin::signed(1), 10, registry_id.clone()));
+    assert_event(crate::Event::EscrowReleased(registry_id.clone(), 10, 1, 100));
+    assert_eq!(Balances::free_balance(1), 1_085);
+    assert!(has_access(&registry_id, 10, AccessType::Buyer));
+  });
+}
+
+#[test]
+fn broken_deliveries_are_refunded() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = listed(1, 100);
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+
+    assert_noop!(
+      TemplateModule::refund_purchase(Origin::signed(10), registry_id.clone()),
+      Error::<Test>::RegistryNotBroken
+    );
+
+    BROKEN.with(|broken| broken.borrow_mut().push(registry_id.clone()));
+    assert_ok!(TemplateModule::refund_purchase(Origin::signed(10), registry_id.clone()));
+    assert_event(crate::Event::PurchaseRefunded(registry_id.clone(), 10, 100));
+    assert_eq!(Balances::free_balance(10), 1_000);
+    assert_eq!(Balances::free_balance(1), 1_000);
+    assert!(!has_access(&registry_id, 10, AccessType::Buyer));
+
+    System::set_block_number(11);
+    assert_noop!(
+      TemplateModule::release_escrow(Origin::signed(1), 10, registry_id.clone()),
+      Error::<Test>::NotEscrowed
+    );
+
+    // A refunded buyer can buy again once the delivery is fixed.
+    BROKEN.with(|broken| broken.borrow_mut().clear());
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id,
+      Purpose::AcademicResearch
+    ));
+  });
+}
+
+#[test]
+fn released_escrows_cannot_be_refunded() {
+  new_test_ext().execute_with(|| {
+    let registry_id = listed(1, 100);
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+    assert_ok!(TemplateModule::confirm_purchase(Origin::signed(10), registry_id.clone()));
+
+    BROKEN.with(|broken| broken.borrow_mut().push(registry_id.clone()));
+    assert_noop!(
+      TemplateModule::refund_purchase(Origin::signed(10), registry_id),
+      Error::<Test>::NotEscrowed
+    );
+  });
+}
+
+#[test]
+fn owners_consent_to_have_their_registries_bundled() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry(b"registry", 1);
+
+    assert_noop!(
+      TemplateModule::set_aggregation_consent(Origin::signed(2), registry_id.clone(), true),
+      Error::<Test>::OnlyOwnerAllowed
+    );
+    assert_ok!(TemplateModule::set_aggregation_consent(Origin::signed(1), registry_id.clone(), true));
+    assert_event(crate::Event::AggregationConsentChanged(registry_id.clone(), true));
+    assert!(TemplateModule::aggregation_consents(&registry_id));
+
+    assert_ok!(TemplateModule::set_aggregation_consent(Origin::signed(1), registry_id.clone(), false));
+    assert_event(crate::Event::AggregationConsentChanged(registry_id.clone(), false));
+    assert!(!TemplateModule::aggregation_consents(&registry_id));
+  });
+}
+
+#[test]
+fn aggregators_bundle_consenting_registries_matching_their_filter() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let first = aggregatable(b"first", 1);
+    let second = aggregatable(b"second", 2);
+
+    let unlisted = registry(b"unlisted", 3);
+    let unconsented = listed(4, 100);
+    let asian = profiled_registry(
+      b"asian",
+      5,
+      RegistryProfile {
+        therapeutic_area: Some(TherapeuticArea::Cardiovascular),
+        format: RecordFormat::Dicom,
+        country: Country::Japan,
+        region: Region::Asia,
+      },
+    );
+    consent_to_research(&asian, 5);
+    assert_ok!(TemplateModule::list_registry(
+      Origin::signed(5),
+      asian.clone(),
+      100,
+      CurrencyId::Native,
+      Default::default(),
+      buyer_roles(vec![Role::Researcher]),
+      None
+    ));
+    assert_ok!(TemplateModule::set_aggregation_consent(Origin::signed(5), asian.clone(), true));
+
+    assert_noop!(
+      create_bundle(10, b"bundle", vec![first.clone()], 150),
+      Error::<Test>::OnlyAggregatorAllowed
+    );
+    assert_noop!(create_bundle(20, b"bundle", vec![], 150), Error::<Test>::InvalidBundle);
+    assert_noop!(
+      create_bundle(20, b"bundle", vec![first.clone(), first.clone()], 150),
+      Error::<Test>::InvalidBundle
+    );
+    assert_noop!(
+      create_bundle(20, b"bundle", vec![first.clone(), unlisted], 150),
+      Error::<Test>::ListingNotExisted
+    );
+    assert_noop!(
+      create_bundle(20, b"bundle", vec![first.clone(), unconsented], 150),
+      Error::<Test>::RegistryNotAggregatable
+    );
+    assert_noop!(
+      create_bundle(20, b"bundle", vec![first.clone(), asian], 150),
+      Error::<Test>::RegistryNotAggregatable
+    );
+
+    assert_ok!(create_bundle(20, b"bundle", vec![first.clone(), second.clone()], 150));
+    assert_event(crate::Event::BundleCreated(bundle_id(b"bundle"), 20, 150, CurrencyId::Native));
+    assert_eq!(
+      TemplateModule::bundles(bundle_id(b"bundle")).unwrap().registries,
+      bundle_registries(vec![first.clone(), second])
+    );
+    assert_noop!(create_bundle(21, b"bundle", vec![first], 150), Error::<Test>::BundleAlreadyExisted);
+
+    assert_noop!(
+      TemplateModule::remove_bundle(Origin::signed(21), bundle_id(b"bundle")),
+      Error::<Test>::OnlyAggregatorAllowed
+    );
+    assert_ok!(TemplateModule::remove_bundle(Origin::signed(20), bundle_id(b"bundle")));
+    assert_event(crate::Event::BundleRemoved(bundle_id(b"bundle")));
+    assert_eq!(TemplateModule::bundles(bundle_id(b"bundle")), None);
+    assert_noop!(
+      TemplateModule::remove_bundle(Origin::signed(20), bundle_id(b"bundle")),
+      Error::<Test>::BundleNotExisted
+    );
+  });
+}
+
+#[test]
+fn bundle_purchases_share_the_price_among_owners() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let first = aggregatable(b"first", 1);
+    let second = aggregatable(b"second", 2);
+    let third = aggregatable(b"third", 3);
+    let fourth = aggregatable(b"fourth", 4);
+    assert_ok!(create_bundle(
+      20,
+      b"bundle",
+      vec![first.clone(), second.clone(), third.clone(), fourth.clone()],
+      100
+    ));
+
+    // Registries already bought, or whose owner withdrew the consent, are left out.
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      fourth.clone(),
+      Purpose::AcademicResearch
+    ));
+    assert_ok!(TemplateModule::set_aggregation_consent(Origin::signed(3), third.clone(), false));
+
+    assert_noop!(
+      TemplateModule::purchase_bundle(Origin::signed(1), bundle_id(b"bundle"), Purpose::AcademicResearch),
+      Error::<Test>::BuyerRoleNotAllowed
+    );
+    assert_ok!(TemplateModule::purchase_bundle(
+      Origin::signed(10),
+      bundle_id(b"bundle"),
+      Purpose::AcademicResearch
+    ));
+    assert_event(crate::Event::BundlePurchased(bundle_id(b"bundle"), 10, 100));
+    assert_eq!(Balances::reserved_balance(10), 200);
+    assert!(has_access(&first, 10, AccessType::Buyer));
+    assert!(has_access(&second, 10, AccessType::Buyer));
+    assert!(!has_access(&third, 10, AccessType::Buyer));
+
+    let purchase = TemplateModule::purchases(10, &first).unwrap();
+    assert_eq!((purchase.seller, purchase.price, purchase.bundle), (1, 50, Some(bundle_id(b"bundle"))));
+    assert_eq!(TemplateModule::purchases(10, &fourth).unwrap().bundle, None);
+
+    assert_ok!(TemplateModule::confirm_purchase(Origin::signed(10), first.clone()));
+    assert_event(crate::Event::EscrowReleased(first.clone(), 10, 1, 50));
+    // The aggregator gets 20% of the share of the registry, and the owner the residue of rounding.
+    System::assert_has_event(Event::TemplateModule(crate::Event::RevenueShared(
+      first,
+      10,
+      Payouts {
+        owner_share: 33,
+        issuer: ISSUER,
+        issuer_share: 5,
+        treasury_fee: 2,
+        aggregator: Some(20),
+        aggregator_cut: 10,
+        residue: 1,
+      },
+    )));
+    assert_eq!(Balances::free_balance(1), 1_033);
+    assert_eq!(Balances::free_balance(20), 1_010);
+
+    assert_noop!(
+      TemplateModule::purchase_bundle(Origin::signed(10), bundle_id(b"bundle"), Purpose::AcademicResearch),
+      Error::<Test>::EmptyBundle
+    );
+  });
+}
+
+#[test]
+fn indivisible_bundle_prices_leave_the_remainder_to_the_buyer() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let first = aggregatable(b"first", 1);
+    let second = aggregatable(b"second", 2);
+    let third = aggregatable(b"third", 3);
+    assert_ok!(create_bundle(20, b"bundle", vec![first, second, third], 100));
+
+    assert_ok!(TemplateModule::purchase_bundle(
+      Origin::signed(10),
+      bundle_id(b"bundle"),
+      Purpose::AcademicResearch
+    ));
+    assert_event(crate::Event::BundlePurchased(bundle_id(b"bundle"), 10, 99));
+    assert_eq!(Balances::reserved_balance(10), 99);
+    assert_eq!(Balances::free_balance(10), 901);
+  });
+}
+
+#[test]
+fn researchers_post_data_requests_with_a_reserved_budget() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+
+    assert_noop!(post_data_request(1, b"request", 1, 5, 5), Error::<Test>::OnlyResearcherAllowed);
+    assert_noop!(post_data_request(10, b"request", 0, 5, 5), Error::<Test>::InvalidDataRequest);
+    assert_noop!(post_data_request(10, b"request", 6, 5, 5), Error::<Test>::InvalidDataRequest);
+    assert_noop!(post_data_request(10, b"request", 1, 5, 1), Error::<Test>::InvalidDataRequest);
+    assert_noop!(post_data_request(10, b"request", 1, 500, 5), Error::<Test>::InvalidDataRequest);
+    assert_noop!(
+      TemplateModule::post_data_request(
+        Origin::signed(10),
+        request_id(b"request"),
+        Default::default(),
+        u64::MAX,
+        CurrencyId::Native,
+        1,
+        2,
+        Default::default(),
+        Purpose::AcademicResearch,
+        5,
+      ),
+      Error::<Test>::InvalidDataRequest
+    );
+
+    assert_ok!(post_data_request(10, b"request", 1, 5, 5));
+    assert_event(crate::Event::DataRequestPosted(request_id(b"request"), 10, 10, 5));
+    assert_eq!(Balances::reserved_balance(10), 50);
+    assert_eq!(TemplateModule::data_request_deadlines(5).into_inner(), vec![request_id(b"request")]);
+    assert_noop!(post_data_request(11, b"request", 1, 5, 5), Error::<Test>::DataRequestAlreadyExisted);
+
+    assert_noop!(
+      TemplateModule::cancel_data_request(Origin::signed(11), request_id(b"request")),
+      Error::<Test>::OnlyResearcherAllowed
+    );
+    assert_ok!(TemplateModule::cancel_data_request(Origin::signed(10), request_id(b"request")));
+    assert_event(crate::Event::DataRequestCancelled(request_id(b"request")));
+    assert_eq!(Balances::reserved_balance(10), 0);
+    assert!(TemplateModule::data_request_deadlines(5).is_empty());
+    assert_eq!(TemplateModule::data_requests(request_id(b"request")), None);
+  });
+}
+
+#[test]
+fn patients_fulfil_data_requests_with_matching_registries() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry(b"registry", 1);
+    let aggregated = registry(b"aggregated", 20);
+    let japanese = profiled_registry(
+      b"japanese",
+      2,
+      RegistryProfile {
+        therapeutic_area: Some(TherapeuticArea::Cardiovascular),
+        format: RecordFormat::FhirR4,
+        country: Country::Japan,
+        region: Region::Asia,
+      },
+    );
+    assert_ok!(post_data_request(10, b"request", 1, 5, 5));
+
+    assert_noop!(
+      TemplateModule::fulfil_data_request(Origin::signed(1), request_id(b"unknown"), registry_id.clone()),
+      Error::<Test>::DataRequestNotExisted
+    );
+    assert_noop!(
+      TemplateModule::fulfil_data_request(Origin::signed(2), request_id(b"request"), registry_id.clone()),
+      Error::<Test>::OnlyOwnerAllowed
+    );
+    assert_noop!(
+      TemplateModule::fulfil_data_request(Origin::signed(20), request_id(b"request"), aggregated),
+      Error::<Test>::OnlyPatientAllowed
+    );
+    assert_noop!(
+      TemplateModule::fulfil_data_request(Origin::signed(2), request_id(b"request"), japanese),
+      Error::<Test>::RegistryNotMatching
+    );
+    assert_noop!(
+      TemplateModule::fulfil_data_request(Origin::signed(1), request_id(b"request"), registry_id.clone()),
+      Error::<Test>::ConsentNotExisted
+    );
+
+    consent_to_research(&registry_id, 1);
+    assert_ok!(TemplateModule::fulfil_data_request(
+      Origin::signed(1),
+      request_id(b"request"),
+      registry_id.clone()
+    ));
+    assert_event(crate::Event::DataRequestFulfilled(request_id(b"request"), registry_id.clone(), 1));
+    assert_eq!(
+      TemplateModule::data_requests(request_id(b"request")).unwrap().records.into_inner(),
+      vec![(registry_id.clone(), 1)]
+    );
+    // Owners are only paid, and researchers only get access, once the request is settled.
+    assert!(!has_access(&registry_id, 10, AccessType::Buyer));
+    assert_noop!(
+      TemplateModule::fulfil_data_request(Origin::signed(1), request_id(b"request"), registry_id),
+      Error::<Test>::AlreadyFulfilled
+    );
+    assert_noop!(
+      TemplateModule::cancel_data_request(Origin::signed(10), request_id(b"request")),
+      Error::<Test>::DataRequestHasRecords
+    );
+  });
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn data_requests_are_settled_per_record_at_their_deadline() {
   new_test_ext().execute_with(|| {
-    // Dispatch a signed extrinsic.
-    assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-    // Read pallet storage and assert an expected result.
-    assert_eq!(TemplateModule::something(), Some(42));
+    System::set_block_number(1);
+    let first = registry(b"first", 1);
+    let second = registry(b"second", 2);
+    let late = registry(b"late", 3);
+    consent_to_research(&first, 1);
+    consent_to_research(&second, 2);
+    assert_ok!(post_data_request(10, b"request", 2, 5, 5));
+    assert_ok!(TemplateModule::fulfil_data_request(
+      Origin::signed(1),
+      request_id(b"request"),
+      first.clone()
+    ));
+    assert_ok!(TemplateModule::fulfil_data_request(
+      Origin::signed(2),
+      request_id(b"request"),
+      second.clone()
+    ));
+
+    System::set_block_number(5);
+    assert_noop!(
+      TemplateModule::fulfil_data_request(Origin::signed(3), request_id(b"request"), late),
+      Error::<Test>::DataRequestClosed
+    );
+
+    TemplateModule::on_finalize(5);
+    assert_event(crate::Event::DataRequestSettled(request_id(b"request"), 2, 20));
+    assert_eq!(Balances::free_balance(1), 1_009);
+    assert_eq!(Balances::free_balance(2), 1_009);
+    assert_eq!(Balances::free_balance(10), 980);
+    assert_eq!(Balances::reserved_balance(10), 0);
+    assert!(has_access(&first, 10, AccessType::Buyer));
+    assert!(has_access(&second, 10, AccessType::Buyer));
+
+    let purchase = TemplateModule::purchases(10, &first).unwrap();
+    assert_eq!((purchase.seller, purchase.price, purchase.status), (1, 10, PurchaseStatus::Released));
+    assert_eq!(TemplateModule::data_requests(request_id(b"request")), None);
+  });
+}
+
+#[test]
+fn data_requests_without_enough_records_expire() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry(b"registry", 1);
+    consent_to_research(&registry_id, 1);
+    assert_ok!(post_data_request(10, b"request", 2, 5, 5));
+    assert_ok!(TemplateModule::fulfil_data_request(
+      Origin::signed(1),
+      request_id(b"request"),
+      registry_id.clone()
+    ));
+
+    System::set_block_number(5);
+    TemplateModule::on_finalize(5);
+    assert_event(crate::Event::DataRequestExpired(request_id(b"request")));
+    assert_eq!(Balances::free_balance(1), 1_000);
+    assert_eq!(Balances::free_balance(10), 1_000);
+    assert!(!has_access(&registry_id, 10, AccessType::Buyer));
+    assert_eq!(TemplateModule::purchases(10, &registry_id), None);
   });
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn data_requests_are_settled_as_soon_as_they_are_fulfilled() {
   new_test_ext().execute_with(|| {
-    // Ensure the expected error is thrown when no value is present.
-    assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+    System::set_block_number(1);
+    let first = registry(b"first", 1);
+    let second = registry(b"second", 2);
+    consent_to_research(&first, 1);
+    consent_to_research(&second, 2);
+    assert_ok!(post_data_request(10, b"request", 1, 2, 5));
+    assert_ok!(TemplateModule::fulfil_data_request(Origin::signed(1), request_id(b"request"), first));
+    assert_ok!(TemplateModule::fulfil_data_request(
+      Origin::signed(2),
+      request_id(b"request"),
+      second.clone()
+    ));
+
+    assert_event(crate::Event::DataRequestSettled(request_id(b"request"), 2, 20));
+    assert_eq!(Balances::free_balance(2), 1_009);
+    assert!(has_access(&second, 10, AccessType::Buyer));
+    assert!(TemplateModule::data_request_deadlines(5).is_empty());
+  });
+}
+
+#[test]
+fn owners_consent_to_the_purposes_buyers_declare() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry(b"registry", 1);
+
+    assert_noop!(
+      TemplateModule::give_consent(Origin::signed(2), registry_id.clone(), purposes(vec![Purpose::ClinicalCare]), None, false),
+      Error::<Test>::OnlyOwnerAllowed
+    );
+    assert_noop!(
+      TemplateModule::give_consent(Origin::signed(1), registry_id.clone(), purposes(vec![]), None, false),
+      Error::<Test>::InvalidConsent
+    );
+    assert_noop!(
+      TemplateModule::give_consent(
+        Origin::signed(1),
+        registry_id.clone(),
+        purposes(vec![Purpose::ClinicalCare, Purpose::ClinicalCare]),
+        None,
+        false
+      ),
+      Error::<Test>::InvalidConsent
+    );
+    assert_noop!(
+      TemplateModule::give_consent(
+        Origin::signed(1),
+        registry_id.clone(),
+        purposes(vec![Purpose::ClinicalCare]),
+        Some(1),
+        false
+      ),
+      Error::<Test>::InvalidConsent
+    );
+
+    assert_ok!(TemplateModule::give_consent(
+      Origin::signed(1),
+      registry_id.clone(),
+      purposes(vec![Purpose::AcademicResearch]),
+      Some(20),
+      true
+    ));
+    assert_event(crate::Event::ConsentGiven(registry_id.clone(), purposes(vec![Purpose::AcademicResearch])));
+    assert_ok!(TemplateModule::list_registry(
+      Origin::signed(1),
+      registry_id.clone(),
+      100,
+      CurrencyId::Native,
+      Default::default(),
+      buyer_roles(vec![Role::Researcher]),
+      None
+    ));
+
+    assert_noop!(
+      TemplateModule::purchase_registry(Origin::signed(10), registry_id.clone(), Purpose::AiTraining),
+      Error::<Test>::PurposeNotConsented
+    );
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+    let purchase = TemplateModule::purchases(10, &registry_id).unwrap();
+    assert_eq!((purchase.purpose, purchase.resharing), (Purpose::AcademicResearch, true));
+
+    System::set_block_number(20);
+    assert_noop!(
+      TemplateModule::purchase_registry(Origin::signed(11), registry_id, Purpose::AcademicResearch),
+      Error::<Test>::PurposeNotConsented
+    );
+  });
+}
+
+#[test]
+fn buyers_reshare_registries_only_when_the_consent_allowed_it() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = listed(1, 100);
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+    assert_noop!(
+      TemplateModule::reshare_purchase(Origin::signed(10), registry_id.clone(), 12),
+      Error::<Test>::ResharingNotAllowed
+    );
+    assert_noop!(
+      TemplateModule::reshare_purchase(Origin::signed(13), registry_id.clone(), 12),
+      Error::<Test>::PurchaseNotExisted
+    );
+
+    assert_ok!(TemplateModule::give_consent(
+      Origin::signed(1),
+      registry_id.clone(),
+      purposes(vec![Purpose::AcademicResearch]),
+      None,
+      true
+    ));
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(11),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+    assert_noop!(
+      TemplateModule::reshare_purchase(Origin::signed(11), registry_id.clone(), 10),
+      Error::<Test>::AlreadyAccessible
+    );
+    assert_ok!(TemplateModule::reshare_purchase(Origin::signed(11), registry_id.clone(), 12));
+    assert_event(crate::Event::PurchaseReshared(registry_id.clone(), 11, 12));
+    assert!(has_access(&registry_id, 12, AccessType::Accessor));
+    assert_eq!(TemplateModule::reshares(11, &registry_id).into_inner(), vec![12]);
+
+    // Recipients lose their access along with the buyer.
+    BROKEN.with(|broken| broken.borrow_mut().push(registry_id.clone()));
+    assert_ok!(TemplateModule::refund_purchase(Origin::signed(11), registry_id.clone()));
+    assert!(!has_access(&registry_id, 12, AccessType::Accessor));
+    assert!(TemplateModule::reshares(11, &registry_id).is_empty());
+    assert_noop!(
+      TemplateModule::reshare_purchase(Origin::signed(11), registry_id.clone(), 13),
+      Error::<Test>::ResharingNotAllowed
+    );
+  });
+}
+
+#[test]
+fn withdrawing_a_consent_delists_the_registry_and_keeps_past_purchases() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = listed(1, 100);
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+    assert_ok!(post_data_request(11, b"request", 1, 5, 5));
+    assert_ok!(TemplateModule::fulfil_data_request(
+      Origin::signed(1),
+      request_id(b"request"),
+      registry_id.clone()
+    ));
+
+    assert_noop!(
+      TemplateModule::withdraw_consent(Origin::signed(2), registry_id.clone()),
+      Error::<Test>::OnlyOwnerAllowed
+    );
+    assert_ok!(TemplateModule::withdraw_consent(Origin::signed(1), registry_id.clone()));
+    System::assert_has_event(Event::TemplateModule(crate::Event::RegistryDelisted(registry_id.clone())));
+    assert_event(crate::Event::ConsentWithdrawn(registry_id.clone()));
+    assert!(!is_salable(&registry_id));
+    assert_eq!(TemplateModule::listings(&registry_id), None);
+    assert_noop!(
+      TemplateModule::withdraw_consent(Origin::signed(1), registry_id.clone()),
+      Error::<Test>::ConsentNotExisted
+    );
+
+    // The buyer keeps its access, but the data request the registry fulfilled expires without it.
+    assert!(has_access(&registry_id, 10, AccessType::Buyer));
+    assert!(TemplateModule::data_requests(request_id(b"request")).unwrap().records.is_empty());
+    assert!(TemplateModule::fulfilments(&registry_id).is_empty());
+    System::set_block_number(5);
+    TemplateModule::on_finalize(5);
+    assert_event(crate::Event::DataRequestExpired(request_id(b"request")));
+    assert!(!has_access(&registry_id, 11, AccessType::Buyer));
+    assert_eq!(Balances::free_balance(1), 1_000);
+  });
+}
+
+#[test]
+fn listings_override_the_default_revenue_split() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry(b"registry", 1);
+    consent_to_research(&registry_id, 1);
+
+    assert_noop!(
+      TemplateModule::list_registry(
+        Origin::signed(1),
+        registry_id.clone(),
+        99,
+        CurrencyId::Native,
+        Default::default(),
+        buyer_roles(vec![Role::Researcher]),
+        Some(RevenueSplit {
+          issuer_share: Permill::from_percent(80),
+          aggregator_cut: Permill::from_percent(20),
+        })
+      ),
+      Error::<Test>::InvalidRevenueSplit
+    );
+    assert_ok!(TemplateModule::list_registry(
+      Origin::signed(1),
+      registry_id.clone(),
+      99,
+      CurrencyId::Native,
+      Default::default(),
+      buyer_roles(vec![Role::Researcher]),
+      Some(RevenueSplit {
+        issuer_share: Permill::from_percent(30),
+        aggregator_cut: Permill::zero(),
+      })
+    ));
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+    assert_ok!(TemplateModule::confirm_purchase(Origin::signed(10), registry_id.clone()));
+
+    System::assert_has_event(Event::TemplateModule(crate::Event::RevenueShared(
+      registry_id,
+      10,
+      Payouts {
+        owner_share: 66,
+        issuer: ISSUER,
+        issuer_share: 29,
+        treasury_fee: 4,
+        aggregator: None,
+        aggregator_cut: 0,
+        residue: 2,
+      },
+    )));
+    assert_eq!(Balances::free_balance(1), 1_066);
+    assert_eq!(Balances::free_balance(ISSUER), 29);
+    assert_eq!(Balances::free_balance(TREASURY), 4);
+    assert_eq!(Balances::free_balance(10), 901);
+  });
+}
+
+#[test]
+fn english_auctions_sell_to_the_highest_bidder() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry(b"registry", 1);
+    consent_to_research(&registry_id, 1);
+
+    assert_noop!(
+      create_auction(2, &registry_id, AuctionKind::English { extension: 3 }),
+      Error::<Test>::OnlyOwnerAllowed
+    );
+    assert_ok!(create_auction(1, &registry_id, AuctionKind::English { extension: 3 }));
+    assert_event(crate::Event::AuctionCreated(registry_id.clone(), 1, 100, 11));
+    assert_noop!(
+      TemplateModule::list_registry(
+        Origin::signed(1),
+        registry_id.clone(),
+        100,
+        CurrencyId::Native,
+        Default::default(),
+        buyer_roles(vec![Role::Researcher]),
+        None
+      ),
+      Error::<Test>::AuctionAlreadyExisted
+    );
+
+    assert_noop!(
+      TemplateModule::place_bid(Origin::signed(1), registry_id.clone(), 100, Purpose::AcademicResearch),
+      Error::<Test>::OwnerCannotPurchase
+    );
+    assert_noop!(
+      TemplateModule::place_bid(Origin::signed(10), registry_id.clone(), 50, Purpose::AcademicResearch),
+      Error::<Test>::BidTooLow
+    );
+    assert_noop!(
+      TemplateModule::place_bid(Origin::signed(10), registry_id.clone(), 100, Purpose::AiTraining),
+      Error::<Test>::PurposeNotConsented
+    );
+    assert_ok!(TemplateModule::place_bid(
+      Origin::signed(10),
+      registry_id.clone(),
+      100,
+      Purpose::AcademicResearch
+    ));
+    assert_noop!(
+      TemplateModule::place_bid(Origin::signed(11), registry_id.clone(), 100, Purpose::AcademicResearch),
+      Error::<Test>::BidTooLow
+    );
+    assert_ok!(TemplateModule::place_bid(
+      Origin::signed(11),
+      registry_id.clone(),
+      150,
+      Purpose::AcademicResearch
+    ));
+    assert_event(crate::Event::BidPlaced(registry_id.clone(), 11, 150));
+    assert_eq!(Balances::reserved_balance(10), 0);
+    assert_eq!(Balances::reserved_balance(11), 150);
+
+    System::set_block_number(9);
+    assert_ok!(TemplateModule::place_bid(
+      Origin::signed(10),
+      registry_id.clone(),
+      200,
+      Purpose::AcademicResearch
+    ));
+    assert_event(crate::Event::AuctionExtended(registry_id.clone(), 12));
+    assert_eq!(Balances::reserved_balance(10), 200);
+    assert_eq!(Balances::reserved_balance(11), 0);
+    assert!(TemplateModule::auction_ends(11).is_empty());
+    assert_eq!(TemplateModule::auction_ends(12).into_inner(), vec![registry_id.clone()]);
+
+    System::set_block_number(12);
+    assert_noop!(
+      TemplateModule::place_bid(Origin::signed(11), registry_id.clone(), 250, Purpose::AcademicResearch),
+      Error::<Test>::AuctionClosed
+    );
+
+    TemplateModule::on_finalize(12);
+    assert_event(crate::Event::AuctionWon(registry_id.clone(), 10, 200));
+    assert_eq!(Balances::reserved_balance(10), 200);
+    assert_eq!(Balances::free_balance(11), 1_000);
+    assert!(has_access(&registry_id, 10, AccessType::Buyer));
+    assert_eq!(TemplateModule::auctions(&registry_id), None);
+
+    let purchase = TemplateModule::purchases(10, &registry_id).unwrap();
+    assert_eq!((purchase.seller, purchase.price, purchase.status), (1, 200, PurchaseStatus::Escrowed));
+  });
+}
+
+#[test]
+fn withdrawing_a_consent_cancels_the_auction_and_refunds_every_bid() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let english_id = registry(b"english", 1);
+    let sealed_id = registry(b"sealed", 2);
+    consent_to_research(&english_id, 1);
+    consent_to_research(&sealed_id, 2);
+    assert_ok!(create_auction(1, &english_id, AuctionKind::English { extension: 3 }));
+    assert_ok!(create_auction(2, &sealed_id, AuctionKind::SealedBid { reveal_period: 5 }));
+    assert_ok!(TemplateModule::place_bid(
+      Origin::signed(10),
+      english_id.clone(),
+      150,
+      Purpose::AcademicResearch
+    ));
+    assert_ok!(TemplateModule::commit_bid(
+      Origin::signed(11),
+      sealed_id.clone(),
+      commitment(11, 200, [7; 32]),
+      250,
+      Purpose::AcademicResearch
+    ));
+
+    assert_ok!(TemplateModule::withdraw_consent(Origin::signed(1), english_id.clone()));
+    System::assert_has_event(Event::TemplateModule(crate::Event::AuctionCancelled(english_id.clone())));
+    assert_ok!(TemplateModule::withdraw_consent(Origin::signed(2), sealed_id.clone()));
+    System::assert_has_event(Event::TemplateModule(crate::Event::AuctionCancelled(sealed_id.clone())));
+
+    assert_eq!(Balances::reserved_balance(10), 0);
+    assert_eq!(Balances::reserved_balance(11), 0);
+    assert_eq!(TemplateModule::auctions(&english_id), None);
+    assert_eq!(TemplateModule::auctions(&sealed_id), None);
+    assert_eq!(TemplateModule::sealed_bids(&sealed_id, 11), None);
+    assert!(TemplateModule::auction_ends(11).is_empty());
+    assert!(TemplateModule::auction_ends(16).is_empty());
+    assert!(!is_salable(&english_id) && !is_salable(&sealed_id));
+  });
+}
+
+#[test]
+fn blocks_reserve_the_weight_of_what_they_settle() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry(b"registry", 1);
+    consent_to_research(&registry_id, 1);
+    let idle = TemplateModule::on_initialize(11);
+
+    assert_ok!(create_auction(1, &registry_id, AuctionKind::SealedBid { reveal_period: 5 }));
+    assert_eq!(TemplateModule::on_initialize(11), idle);
+    let unbid = TemplateModule::on_initialize(16);
+    assert!(unbid > idle);
+
+    assert_ok!(TemplateModule::commit_bid(
+      Origin::signed(10),
+      registry_id.clone(),
+      commitment(10, 200, [7; 32]),
+      250,
+      Purpose::AcademicResearch
+    ));
+    assert!(TemplateModule::on_initialize(16) > unbid);
+  });
+}
+
+#[test]
+fn sealed_bid_auctions_sell_to_the_highest_revealed_bid() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry(b"registry", 1);
+    let salt = [7; 32];
+    consent_to_research(&registry_id, 1);
+
+    assert_noop!(
+      create_auction(1, &registry_id, AuctionKind::SealedBid { reveal_period: 0 }),
+      Error::<Test>::InvalidAuction
+    );
+    assert_ok!(create_auction(1, &registry_id, AuctionKind::SealedBid { reveal_period: 5 }));
+    assert_noop!(
+      TemplateModule::place_bid(Origin::signed(10), registry_id.clone(), 100, Purpose::AcademicResearch),
+      Error::<Test>::NotEnglishAuction
+    );
+
+    assert_ok!(TemplateModule::commit_bid(
+      Origin::signed(10),
+      registry_id.clone(),
+      commitment(10, 300, salt),
+      400,
+      Purpose::AcademicResearch
+    ));
+    assert_event(crate::Event::BidCommitted(registry_id.clone(), 10));
+    assert_noop!(
+      TemplateModule::commit_bid(
+        Origin::signed(10),
+        registry_id.clone(),
+        commitment(10, 350, salt),
+        400,
+        Purpose::AcademicResearch
+      ),
+      Error::<Test>::BidAlreadyExisted
+    );
+    assert_ok!(TemplateModule::commit_bid(
+      Origin::signed(11),
+      registry_id.clone(),
+      commitment(11, 250, salt),
+      250,
+      Purpose::AcademicResearch
+    ));
+    assert_ok!(TemplateModule::commit_bid(
+      Origin::signed(12),
+      registry_id.clone(),
+      commitment(12, 500, salt),
+      500,
+      Purpose::AcademicResearch
+    ));
+    assert_eq!(Balances::reserved_balance(10), 400);
+    assert_noop!(
+      TemplateModule::cancel_auction(Origin::signed(1), registry_id.clone()),
+      Error::<Test>::AuctionHasBids
+    );
+    assert_noop!(
+      TemplateModule::reveal_bid(Origin::signed(10), registry_id.clone(), 300, salt),
+      Error::<Test>::AuctionNotRevealing
+    );
+
+    System::set_block_number(11);
+    assert_noop!(
+      TemplateModule::reveal_bid(Origin::signed(10), registry_id.clone(), 350, salt),
+      Error::<Test>::InvalidReveal
+    );
+    assert_ok!(TemplateModule::reveal_bid(Origin::signed(10), registry_id.clone(), 300, salt));
+    assert_event(crate::Event::BidRevealed(registry_id.clone(), 10, 300));
+    assert_ok!(TemplateModule::reveal_bid(Origin::signed(11), registry_id.clone(), 250, salt));
+    assert_eq!(TemplateModule::auctions(&registry_id).unwrap().highest_bid.unwrap().bidder, 10);
+
+    TemplateModule::on_finalize(16);
+    assert_event(crate::Event::AuctionWon(registry_id.clone(), 10, 300));
+    assert_eq!(Balances::reserved_balance(10), 300);
+    assert_eq!(Balances::free_balance(10), 700);
+    assert_eq!(Balances::free_balance(11), 1_000);
+    assert_eq!(Balances::free_balance(12), 1_000);
+    assert_eq!(TemplateModule::sealed_bids(&registry_id, 10), None);
+    assert_eq!(TemplateModule::purchases(10, &registry_id).unwrap().price, 300);
+  });
+}
+
+#[test]
+fn auctions_fail_without_a_bid_the_owner_still_consents_to() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry(b"registry", 1);
+    consent_to_research(&registry_id, 1);
+
+    assert_ok!(create_auction(1, &registry_id, AuctionKind::English { extension: 0 }));
+    assert_noop!(
+      TemplateModule::cancel_auction(Origin::signed(2), registry_id.clone()),
+      Error::<Test>::OnlyOwnerAllowed
+    );
+    assert_ok!(TemplateModule::cancel_auction(Origin::signed(1), registry_id.clone()));
+    assert_event(crate::Event::AuctionCancelled(registry_id.clone()));
+    assert!(TemplateModule::auction_ends(11).is_empty());
+
+    assert_ok!(create_auction(1, &registry_id, AuctionKind::English { extension: 0 }));
+    TemplateModule::on_finalize(11);
+    assert_event(crate::Event::AuctionFailed(registry_id.clone()));
+
+    System::set_block_number(11);
+    assert_ok!(create_auction(1, &registry_id, AuctionKind::English { extension: 0 }));
+    assert_ok!(TemplateModule::place_bid(
+      Origin::signed(10),
+      registry_id.clone(),
+      100,
+      Purpose::AcademicResearch
+    ));
+    assert_ok!(TemplateModule::withdraw_consent(Origin::signed(1), registry_id.clone()));
+
+    TemplateModule::on_finalize(21);
+    assert_event(crate::Event::AuctionFailed(registry_id.clone()));
+    assert_eq!(Balances::free_balance(10), 1_000);
+    assert_eq!(TemplateModule::purchases(10, &registry_id), None);
+    assert!(!has_access(&registry_id, 10, AccessType::Buyer));
+  });
+}
+
+#[test]
+fn listings_are_paid_and_refunded_in_assets() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    stablecoin(vec![10, 11]);
+    let registry_id = registry(b"registry", 1);
+    let broken_id = registry(b"broken", 2);
+    consent_to_research(&registry_id, 1);
+    consent_to_research(&broken_id, 2);
+
+    assert_noop!(
+      TemplateModule::list_registry(
+        Origin::signed(1),
+        registry_id.clone(),
+        100,
+        CurrencyId::Asset(STABLECOIN + 1),
+        Default::default(),
+        buyer_roles(vec![Role::Researcher]),
+        None
+      ),
+      Error::<Test>::CurrencyNotSupported
+    );
+    for (id, owner) in [(&registry_id, 1), (&broken_id, 2)] {
+      assert_ok!(TemplateModule::list_registry(
+        Origin::signed(owner),
+        id.clone(),
+        100,
+        CurrencyId::Asset(STABLECOIN),
+        Default::default(),
+        buyer_roles(vec![Role::Researcher]),
+        None
+      ));
+    }
+
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(11),
+      broken_id.clone(),
+      Purpose::AcademicResearch
+    ));
+    assert_eq!(Assets::balance(STABLECOIN, 10), 900);
+    assert_eq!(Assets::balance(STABLECOIN, TemplateModule::account_id()), 200);
+    assert_eq!(Balances::free_balance(10), 1_000);
+
+    assert_ok!(TemplateModule::confirm_purchase(Origin::signed(10), registry_id.clone()));
+    assert_eq!(Assets::balance(STABLECOIN, 1), 85);
+    assert_eq!(Assets::balance(STABLECOIN, ISSUER), 10);
+    assert_eq!(Assets::balance(STABLECOIN, TREASURY), 5);
+    assert_eq!(Balances::free_balance(1), 1_000);
+
+    BROKEN.with(|broken| broken.borrow_mut().push(broken_id.clone()));
+    assert_ok!(TemplateModule::refund_purchase(Origin::signed(11), broken_id));
+    assert_eq!(Assets::balance(STABLECOIN, 11), 1_000);
+    assert_eq!(Assets::balance(STABLECOIN, TemplateModule::account_id()), 0);
+  });
+}
+
+#[test]
+fn asset_refunds_that_cannot_be_paid_are_kept_to_claim() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    stablecoin(vec![10]);
+    let registry_id = registry(b"registry", 1);
+    consent_to_research(&registry_id, 1);
+    assert_ok!(TemplateModule::create_auction(
+      Origin::signed(1),
+      registry_id.clone(),
+      AuctionKind::English { extension: 3 },
+      100,
+      CurrencyId::Asset(STABLECOIN),
+      Default::default(),
+      buyer_roles(vec![Role::Researcher]),
+      None,
+      10,
+    ));
+    assert_ok!(TemplateModule::place_bid(
+      Origin::signed(10),
+      registry_id.clone(),
+      150,
+      Purpose::AcademicResearch
+    ));
+
+    assert_ok!(Assets::freeze_asset(Origin::signed(0), STABLECOIN));
+    assert_ok!(TemplateModule::withdraw_consent(Origin::signed(1), registry_id));
+    System::assert_has_event(Event::TemplateModule(crate::Event::RefundDeferred(10, STABLECOIN, 150)));
+    assert_eq!(TemplateModule::unclaimed_refunds(10, STABLECOIN), 150);
+    assert_eq!(Assets::balance(STABLECOIN, 10), 850);
+
+    assert_noop!(TemplateModule::claim_refund(Origin::signed(10), STABLECOIN), Error::<Test>::RefundFailed);
+    assert_ok!(Assets::thaw_asset(Origin::signed(0), STABLECOIN));
+    assert_ok!(TemplateModule::claim_refund(Origin::signed(10), STABLECOIN));
+    assert_event(crate::Event::RefundClaimed(10, STABLECOIN, 150));
+    assert_eq!(Assets::balance(STABLECOIN, 10), 1_000);
+    assert_eq!(Assets::balance(STABLECOIN, TemplateModule::account_id()), 0);
+    assert_noop!(
+      TemplateModule::claim_refund(Origin::signed(10), STABLECOIN),
+      Error::<Test>::RefundNotExisted
+    );
+  });
+}
+
+/// A purchase of a registry of 1 by 10 at 100, disputed by 10.
+fn disputed() -> RegistryId {
+  let registry_id = listed(1, 100);
+  assert_ok!(TemplateModule::purchase_registry(
+    Origin::signed(10),
+    registry_id.clone(),
+    Purpose::AcademicResearch
+  ));
+  assert_ok!(TemplateModule::open_dispute(
+    Origin::signed(10),
+    registry_id.clone(),
+    H256::repeat_byte(1)
+  ));
+
+  registry_id
+}
+
+#[test]
+fn buyers_dispute_escrowed_purchases_custodians_refund() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = disputed();
+    assert_event(crate::Event::DisputeOpened(registry_id.clone(), 10, H256::repeat_byte(1)));
+    assert_eq!(Balances::reserved_balance(10), 150);
+    assert_eq!(TemplateModule::purchases(10, &registry_id).unwrap().status, PurchaseStatus::Disputed);
+    assert_eq!(TemplateModule::disputes(10, &registry_id).unwrap().panel.into_inner(), vec![40, 41, 42]);
+    assert_noop!(
+      TemplateModule::open_dispute(Origin::signed(11), registry_id.clone(), H256::repeat_byte(1)),
+      Error::<Test>::PurchaseNotExisted
+    );
+    assert_noop!(
+      TemplateModule::release_escrow(Origin::signed(1), 10, registry_id.clone()),
+      Error::<Test>::NotEscrowed
+    );
+
+    assert_noop!(
+      TemplateModule::answer_dispute(Origin::signed(2), 10, registry_id.clone(), H256::repeat_byte(2)),
+      Error::<Test>::OnlySellerAllowed
+    );
+    assert_ok!(TemplateModule::answer_dispute(
+      Origin::signed(1),
+      10,
+      registry_id.clone(),
+      H256::repeat_byte(2)
+    ));
+    assert_noop!(
+      TemplateModule::answer_dispute(Origin::signed(1), 10, registry_id.clone(), H256::repeat_byte(3)),
+      Error::<Test>::ResponseAlreadyExisted
+    );
+
+    assert_noop!(
+      TemplateModule::vote_on_dispute(Origin::signed(10), 10, registry_id.clone(), Verdict::Refund),
+      Error::<Test>::OnlyPanelAllowed
+    );
+    assert_ok!(TemplateModule::vote_on_dispute(
+      Origin::signed(40),
+      10,
+      registry_id.clone(),
+      Verdict::Refund
+    ));
+    assert_noop!(
+      TemplateModule::vote_on_dispute(Origin::signed(40), 10, registry_id.clone(), Verdict::Release),
+      Error::<Test>::AlreadyVoted
+    );
+    assert_ok!(TemplateModule::vote_on_dispute(
+      Origin::signed(41),
+      10,
+      registry_id.clone(),
+      Verdict::Refund
+    ));
+    assert_event(crate::Event::DisputeResolved(registry_id.clone(), 10, Verdict::Refund));
+
+    assert_eq!(Balances::free_balance(10), 1_000);
+    assert_eq!(Balances::free_balance(1), 1_000);
+    assert!(!has_access(&registry_id, 10, AccessType::Buyer));
+    assert_eq!(TemplateModule::purchases(10, &registry_id).unwrap().status, PurchaseStatus::Refunded);
+    assert_eq!(TemplateModule::disputes(10, &registry_id), None);
+    assert!(TemplateModule::dispute_deadlines(6).is_empty());
+  });
+}
+
+#[test]
+fn buyers_lose_their_deposit_when_custodians_release_the_price() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = disputed();
+
+    assert_ok!(TemplateModule::vote_on_dispute(
+      Origin::signed(40),
+      10,
+      registry_id.clone(),
+      Verdict::Release
+    ));
+    assert_ok!(TemplateModule::vote_on_dispute(
+      Origin::signed(42),
+      10,
+      registry_id.clone(),
+      Verdict::Release
+    ));
+    assert_event(crate::Event::DisputeResolved(registry_id.clone(), 10, Verdict::Release));
+
+    assert_eq!(Balances::free_balance(10), 850);
+    assert_eq!(Balances::reserved_balance(10), 0);
+    assert_eq!(Balances::free_balance(1), 1_085);
+    assert_eq!(Balances::free_balance(ISSUER), 10);
+    assert_eq!(Balances::free_balance(TREASURY), 55);
+    assert!(has_access(&registry_id, 10, AccessType::Buyer));
+    assert_eq!(TemplateModule::purchases(10, &registry_id).unwrap().status, PurchaseStatus::Released);
+  });
+}
+
+#[test]
+fn recovered_buyers_and_sellers_keep_their_escrowed_purchases() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = disputed();
+
+    <TemplateModule as OnAccountRecovered<u64>>::on_account_recovered(&10, &20);
+    <TemplateModule as OnAccountRecovered<u64>>::on_account_recovered(&1, &21);
+
+    assert_eq!(TemplateModule::purchases(10, &registry_id), None);
+    assert_eq!(TemplateModule::disputes(10, &registry_id), None);
+    let purchase = TemplateModule::purchases(20, &registry_id).unwrap();
+    assert_eq!((purchase.seller, purchase.status), (21, PurchaseStatus::Disputed));
+    assert!(TemplateModule::disputes(20, &registry_id).is_some());
+    assert_eq!(TemplateModule::dispute_deadlines(6).into_inner(), vec![(20, registry_id.clone())]);
+    assert_eq!(TemplateModule::escrowed_payouts(1, (20, registry_id.clone())), None);
+    assert!(TemplateModule::escrowed_payouts(21, (20, registry_id.clone())).is_some());
+    assert_eq!(Balances::reserved_balance(10), 0);
+    assert_eq!(Balances::reserved_balance(20), 150);
+
+    assert_ok!(TemplateModule::vote_on_dispute(
+      Origin::signed(40),
+      20,
+      registry_id.clone(),
+      Verdict::Release
+    ));
+    assert_ok!(TemplateModule::vote_on_dispute(
+      Origin::signed(42),
+      20,
+      registry_id.clone(),
+      Verdict::Release
+    ));
+
+    assert_eq!(Balances::reserved_balance(20), 0);
+    assert_eq!(Balances::free_balance(1), 1_000);
+    assert_eq!(Balances::free_balance(21), 1_085);
+    assert_eq!(TemplateModule::escrowed_payouts(21, (20, registry_id)), None);
+  });
+}
+
+#[test]
+fn undecided_disputes_are_split_at_the_end_of_the_dispute_period() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = disputed();
+
+    assert_ok!(TemplateModule::vote_on_dispute(
+      Origin::signed(40),
+      10,
+      registry_id.clone(),
+      Verdict::Refund
+    ));
+    assert_ok!(TemplateModule::vote_on_dispute(
+      Origin::signed(41),
+      10,
+      registry_id.clone(),
+      Verdict::Release
+    ));
+
+    TemplateModule::on_finalize(6);
+    assert_event(crate::Event::DisputeResolved(registry_id.clone(), 10, Verdict::Split));
+    System::assert_has_event(Event::TemplateModule(crate::Event::RevenueShared(
+      registry_id.clone(),
+      10,
+      Payouts {
+        owner_share: 43,
+        issuer: ISSUER,
+        issuer_share: 5,
+        treasury_fee: 2,
+        aggregator: None,
+        aggregator_cut: 0,
+        residue: 1,
+      },
+    )));
+
+    assert_eq!(Balances::free_balance(10), 950);
+    assert_eq!(Balances::reserved_balance(10), 0);
+    assert_eq!(Balances::free_balance(1), 1_043);
+    assert!(has_access(&registry_id, 10, AccessType::Buyer));
+
+    let purchase = TemplateModule::purchases(10, &registry_id).unwrap();
+    assert_eq!((purchase.price, purchase.status), (50, PurchaseStatus::Released));
+  });
+}
+
+fn listed_ids(filter: ListingFilter<u64>, cursor: Option<RegistryId>, limit: u32) -> Vec<RegistryId> {
+  TemplateModule::listings_matching(&filter, &cursor, limit)
+    .into_iter()
+    .map(|(registry_id, _)| registry_id)
+    .collect()
+}
+
+#[test]
+fn clients_browse_listings_by_profile_and_price() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let european = registry(b"european", 1);
+    let asian = profiled_registry(
+      b"asian",
+      2,
+      RegistryProfile {
+        therapeutic_area: Some(TherapeuticArea::Cardiovascular),
+        format: RecordFormat::Dicom,
+        country: Country::Japan,
+        region: Region::Asia,
+      },
+    );
+    let expiring = registry(b"expiring", 3);
+    consent_to_research(&european, 1);
+    consent_to_research(&asian, 2);
+    assert_ok!(TemplateModule::give_consent(
+      Origin::signed(3),
+      expiring.clone(),
+      purposes(vec![Purpose::AcademicResearch]),
+      Some(5),
+      false
+    ));
+    for (registry_id, owner, price) in [(&european, 1, 100), (&asian, 2, 300), (&expiring, 3, 200)] {
+      assert_ok!(TemplateModule::list_registry(
+        Origin::signed(owner),
+        registry_id.clone(),
+        price,
+        CurrencyId::Native,
+        Default::default(),
+        buyer_roles(vec![Role::Researcher]),
+        None
+      ));
+    }
+
+    let all = listed_ids(Default::default(), None, 10);
+    assert_eq!(all.len(), 3);
+    assert_eq!(listed_ids(Default::default(), Some(all[0].clone()), 10), all[1..].to_vec());
+    assert_eq!(listed_ids(Default::default(), None, 1), all[..1].to_vec());
+
+    let in_europe = listed_ids(
+      ListingFilter {
+        region: Some(Region::Europe),
+        ..Default::default()
+      },
+      None,
+      10,
+    );
+    assert_eq!(in_europe.len(), 2);
+    assert!(in_europe.contains(&european) && in_europe.contains(&expiring));
+    assert_eq!(
+      listed_ids(
+        ListingFilter {
+          region: Some(Region::Europe),
+          ..Default::default()
+        },
+        Some(in_europe[0].clone()),
+        10,
+      ),
+      in_europe[1..].to_vec()
+    );
+
+    let above = listed_ids(
+      ListingFilter {
+        min_price: Some(150),
+        ..Default::default()
+      },
+      None,
+      10,
+    );
+    assert_eq!(above.len(), 2);
+    assert!(above.contains(&asian) && above.contains(&expiring));
+
+    assert_eq!(
+      listed_ids(
+        ListingFilter {
+          region: Some(Region::Europe),
+          max_price: Some(150),
+          ..Default::default()
+        },
+        None,
+        10
+      ),
+      vec![european.clone()]
+    );
+    assert_eq!(
+      listed_ids(
+        ListingFilter {
+          format: Some(RecordFormat::Dicom),
+          ..Default::default()
+        },
+        None,
+        10
+      ),
+      vec![asian.clone()]
+    );
+    assert!(listed_ids(
+      ListingFilter {
+        currency: Some(CurrencyId::Asset(STABLECOIN)),
+        ..Default::default()
+      },
+      None,
+      10
+    )
+    .is_empty());
+
+    // Listings whose consent expired cannot be bought anymore.
+    System::set_block_number(5);
+    assert!(!listed_ids(Default::default(), None, 10).contains(&expiring));
+
+    assert_ok!(TemplateModule::delist_registry(Origin::signed(1), european.clone()));
+    assert_eq!(TemplateModule::listings_by_region(Region::Europe, &european), None);
+    assert_eq!(TemplateModule::listings_by_format(RecordFormat::FhirR4, &european), None);
+    assert_eq!(
+      TemplateModule::listings_by_therapeutic_area(TherapeuticArea::Cardiovascular, &european),
+      None
+    );
+    assert_eq!(TemplateModule::listings_by_region(Region::Asia, &asian), Some(()));
+  });
+}
+
+#[test]
+fn buyers_get_their_purchase_history() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = listed(1, 100);
+    assert_ok!(TemplateModule::purchase_registry(
+      Origin::signed(10),
+      registry_id.clone(),
+      Purpose::AcademicResearch
+    ));
+
+    let history = TemplateModule::purchases_of(&10, &None, 10);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].0, registry_id);
+    assert_eq!((history[0].1.price, history[0].1.status), (100, PurchaseStatus::Escrowed));
+    assert!(TemplateModule::purchases_of(&10, &Some(registry_id), 10).is_empty());
+    assert!(TemplateModule::purchases_of(&11, &None, 10).is_empty());
+  });
+}
+
+#[test]
+fn patients_find_the_data_requests_their_registries_could_fulfil() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let first = registry(b"first", 1);
+    let unconsented = registry(b"unconsented", 1);
+    let japanese = profiled_registry(
+      b"japanese",
+      1,
+      RegistryProfile {
+        therapeutic_area: Some(TherapeuticArea::Cardiovascular),
+        format: RecordFormat::FhirR4,
+        country: Country::Japan,
+        region: Region::Asia,
+      },
+    );
+    consent_to_research(&first, 1);
+    consent_to_research(&japanese, 1);
+    assert_ok!(post_data_request(10, b"request", 1, 5, 5));
+
+    let requests = TemplateModule::data_requests_for(&1, &None, 10);
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].0, request_id(b"request"));
+    assert_eq!(requests[0].2, vec![first.clone()]);
+    assert!(!requests[0].2.contains(&unconsented));
+    assert!(TemplateModule::data_requests_for(&2, &None, 10).is_empty());
+    assert!(TemplateModule::data_requests_for(&10, &None, 10).is_empty());
+
+    assert_ok!(TemplateModule::fulfil_data_request(Origin::signed(1), request_id(b"request"), first));
+    assert!(TemplateModule::data_requests_for(&1, &None, 10).is_empty());
+  });
+}
+
+#[test]
+fn patients_page_through_the_data_requests_of_their_therapeutic_areas() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry(b"registry", 1);
+    consent_to_research(&registry_id, 1);
+    assert_ok!(post_data_request(10, b"cardiovascular", 1, 5, 5));
+    assert_ok!(TemplateModule::post_data_request(
+      Origin::signed(11),
+      request_id(b"any"),
+      Default::default(),
+      10,
+      CurrencyId::Native,
+      1,
+      5,
+      Default::default(),
+      Purpose::AcademicResearch,
+      5,
+    ));
+    assert_eq!(
+      TemplateModule::data_requests_by_therapeutic_area(None::<TherapeuticArea>, request_id(b"any")),
+      Some(())
+    );
+
+    let first_page = TemplateModule::data_requests_for(&1, &None, 1);
+    assert_eq!(first_page.len(), 1);
+    assert_eq!(first_page[0].0, request_id(b"cardiovascular"));
+
+    let cursor = Some((first_page[0].1.criteria.therapeutic_area, first_page[0].0.clone()));
+    let second_page = TemplateModule::data_requests_for(&1, &cursor, 1);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page[0].0, request_id(b"any"));
+    assert!(TemplateModule::data_requests_for(&1, &Some((None, request_id(b"any"))), 1).is_empty());
+
+    assert_ok!(TemplateModule::cancel_data_request(Origin::signed(11), request_id(b"any")));
+    assert_eq!(
+      TemplateModule::data_requests_by_therapeutic_area(None::<TherapeuticArea>, request_id(b"any")),
+      None
+    );
   });
 }
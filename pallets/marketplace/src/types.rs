@@ -0,0 +1,413 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use crate::constants::{
+  BUNDLE_ID_MAX_LEN, DATA_REQUEST_ID_MAX_LEN, LICENCE_TERMS_MAX_LEN, MAX_AUCTIONS_PER_BLOCK, MAX_BUNDLE_SIZE, MAX_BUYER_ROLES,
+  MAX_DEADLINES_PER_BLOCK, MAX_DISPUTES_PER_BLOCK, MAX_FULFILLED_REQUESTS, MAX_PANEL_SIZE, MAX_PURPOSES, MAX_REQUESTED_RECORDS, MAX_RESHARES,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::*, sp_runtime::Permill};
+use healthcare::{RecordFormat, Role, TherapeuticArea};
+use pallet_meta_registry::types::{RegistryId, RegistryProfile};
+use region::{Country, Region};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::{
+  cmp::{Eq, PartialEq},
+  ops::Add,
+};
+
+/// The id of an asset of the assets pallet
+pub type AssetId = u32;
+pub type LicenceTerms = BoundedVec<u8, ConstU32<LICENCE_TERMS_MAX_LEN>>;
+pub type BuyerRoles = BoundedVec<Role, ConstU32<MAX_BUYER_ROLES>>;
+pub type BundleId = BoundedVec<u8, ConstU32<BUNDLE_ID_MAX_LEN>>;
+pub type BundleRegistries = BoundedVec<RegistryId, ConstU32<MAX_BUNDLE_SIZE>>;
+pub type DataRequestId = BoundedVec<u8, ConstU32<DATA_REQUEST_ID_MAX_LEN>>;
+pub type DataRequestIds = BoundedVec<DataRequestId, ConstU32<MAX_DEADLINES_PER_BLOCK>>;
+pub type Purposes = BoundedVec<Purpose, ConstU32<MAX_PURPOSES>>;
+pub type AuctionedRegistries = BoundedVec<RegistryId, ConstU32<MAX_AUCTIONS_PER_BLOCK>>;
+/// The custodians arbitrating a dispute
+pub type Panel<AccountId> = BoundedVec<AccountId, ConstU32<MAX_PANEL_SIZE>>;
+pub type Votes<AccountId> = BoundedVec<(AccountId, Verdict), ConstU32<MAX_PANEL_SIZE>>;
+/// Disputed purchases, by buyer and registry
+pub type DisputedPurchases<AccountId> = BoundedVec<(AccountId, RegistryId), ConstU32<MAX_DISPUTES_PER_BLOCK>>;
+/// The registries fulfilling a data request, along with their owner
+pub type RequestedRecords<AccountId> = BoundedVec<(RegistryId, AccountId), ConstU32<MAX_REQUESTED_RECORDS>>;
+/// Where to resume browsing data requests: the therapeutic area the last request of the previous
+/// page asks for, and its id
+pub type DataRequestCursor = (Option<TherapeuticArea>, DataRequestId);
+/// The data requests a registry fulfils, which are not settled yet
+pub type FulfilledRequests = BoundedVec<DataRequestId, ConstU32<MAX_FULFILLED_REQUESTS>>;
+/// The accounts a buyer shared a registry with
+pub type Recipients<AccountId> = BoundedVec<AccountId, ConstU32<MAX_RESHARES>>;
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CurrencyId {
+  /// The native token of the chain
+  Native,
+  /// An asset of the assets pallet, such as a stablecoin
+  Asset(AssetId),
+}
+
+/// What a buyer intends to do with the records of a registry.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Purpose {
+  ClinicalCare,
+  AcademicResearch,
+  CommercialResearch,
+  AiTraining,
+}
+
+/// What the owner of a registry allows buyers to do with it.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Consent<BlockNumber> {
+  /// The purposes buyers may declare
+  pub purposes: Purposes,
+
+  /// The block from which the registry cannot be sold anymore, if any
+  pub expires_at: Option<BlockNumber>,
+
+  /// Whether buyers may share the records further
+  pub resharing: bool,
+
+  pub given_at: BlockNumber,
+}
+
+impl<BlockNumber: PartialOrd> Consent<BlockNumber> {
+  /// Whether a buyer may declare `purpose` at block `now`.
+  pub fn covers(&self, purpose: &Purpose, now: &BlockNumber) -> bool {
+    self.purposes.contains(purpose) && self.expires_at.as_ref().map_or(true, |expires_at| now < expires_at)
+  }
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Listing<Balance, BlockNumber> {
+  /// The price of an access, paid to the owner of the registry
+  pub price: Balance,
+
+  /// What the price is paid in
+  pub currency: CurrencyId,
+
+  /// The externally stored licence terms buyers agree to
+  pub licence: LicenceTerms,
+
+  /// The roles allowed to buy, a buyer needs at least one of them
+  pub buyer_roles: BuyerRoles,
+
+  /// How the price is shared, instead of the defaults of the runtime
+  pub revenue_split: Option<RevenueSplit>,
+
+  pub listed_at: BlockNumber,
+}
+
+/// The shares of the price of a registry that do not go to its owner, besides the treasury fee.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RevenueSplit {
+  /// The share of the issuer of the registry
+  pub issuer_share: Permill,
+
+  /// The share of the aggregator, when the registry is bought with a bundle
+  pub aggregator_cut: Permill,
+}
+
+/// What everyone is paid out of the price of a purchase, settled when the purchase is created.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Payouts<AccountId, Balance> {
+  /// The rest of the price once the other shares are paid
+  pub owner_share: Balance,
+
+  pub issuer: AccountId,
+
+  pub issuer_share: Balance,
+
+  pub treasury_fee: Balance,
+
+  /// The aggregator of the bundle the registry was bought with, if any
+  pub aggregator: Option<AccountId>,
+
+  pub aggregator_cut: Balance,
+
+  /// The part of `owner_share` coming from rounding the other shares down
+  pub residue: Balance,
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PurchaseStatus {
+  /// The price is reserved from the buyer until the purchase is released or refunded
+  Escrowed,
+  /// The price was paid to the seller
+  Released,
+  /// The price went back to the buyer, who lost its access
+  Refunded,
+  /// The buyer disputed the delivery, the price stays reserved until custodians settle it
+  Disputed,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Purchase<AccountId, Balance, BlockNumber> {
+  /// The owner of the registry the price was paid to
+  pub seller: AccountId,
+
+  pub price: Balance,
+
+  pub currency: CurrencyId,
+
+  /// The licence terms of the listing at the time of the purchase
+  pub licence: LicenceTerms,
+
+  pub purchased_at: BlockNumber,
+
+  /// The block from which the escrow can be released without the confirmation of the buyer
+  pub release_at: BlockNumber,
+
+  pub status: PurchaseStatus,
+
+  /// The bundle the registry was bought with, if it was not bought on its own
+  pub bundle: Option<BundleId>,
+
+  /// What the buyer declared to buy the registry for
+  pub purpose: Purpose,
+
+  /// Whether the consent of the owner allowed the buyer to share the records further
+  pub resharing: bool,
+
+  /// How the price is shared when the escrow is released
+  pub payouts: Payouts<AccountId, Balance>,
+}
+
+/// The registries an aggregator may bundle, `None` matching any value.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+pub struct BundleFilter {
+  pub therapeutic_area: Option<TherapeuticArea>,
+  pub format: Option<RecordFormat>,
+  pub region: Option<Region>,
+}
+
+impl BundleFilter {
+  pub fn matches(&self, profile: &RegistryProfile) -> bool {
+    self.therapeutic_area.map_or(true, |area| profile.therapeutic_area == Some(area))
+      && self.format.map_or(true, |format| profile.format == format)
+      && self.region.as_ref().map_or(true, |region| profile.region == *region)
+  }
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Bundle<AccountId, Balance, BlockNumber> {
+  /// The aggregator who put the bundle together
+  pub aggregator: AccountId,
+
+  /// What every registry of the bundle holds
+  pub filter: BundleFilter,
+
+  pub registries: BundleRegistries,
+
+  /// The price of the whole bundle, shared pro rata among the owners of the registries
+  pub price: Balance,
+
+  pub currency: CurrencyId,
+
+  /// The externally stored licence terms buyers agree to
+  pub licence: LicenceTerms,
+
+  /// The roles allowed to buy, a buyer needs at least one of them
+  pub buyer_roles: BuyerRoles,
+
+  pub created_at: BlockNumber,
+}
+
+/// The registries a researcher asks for, `None` matching any value.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RequestCriteria {
+  pub therapeutic_area: Option<TherapeuticArea>,
+  pub format: Option<RecordFormat>,
+  pub country: Option<Country>,
+  pub region: Option<Region>,
+}
+
+impl RequestCriteria {
+  pub fn matches(&self, profile: &RegistryProfile) -> bool {
+    self.therapeutic_area.map_or(true, |area| profile.therapeutic_area == Some(area))
+      && self.format.map_or(true, |format| profile.format == format)
+      && self.country.as_ref().map_or(true, |country| profile.country == *country)
+      && self.region.as_ref().map_or(true, |region| profile.region == *region)
+  }
+}
+
+/// The listings a client browses, `None` matching any value. Prices are compared regardless of
+/// the currency unless `currency` is set.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ListingFilter<Balance> {
+  pub therapeutic_area: Option<TherapeuticArea>,
+  pub format: Option<RecordFormat>,
+  pub region: Option<Region>,
+  pub currency: Option<CurrencyId>,
+  pub min_price: Option<Balance>,
+  pub max_price: Option<Balance>,
+}
+
+impl<Balance: PartialOrd> ListingFilter<Balance> {
+  pub fn matches<BlockNumber>(&self, profile: &RegistryProfile, listing: &Listing<Balance, BlockNumber>) -> bool {
+    self.therapeutic_area.map_or(true, |area| profile.therapeutic_area == Some(area))
+      && self.format.map_or(true, |format| profile.format == format)
+      && self.region.as_ref().map_or(true, |region| profile.region == *region)
+      && self.currency.map_or(true, |currency| listing.currency == currency)
+      && self.min_price.as_ref().map_or(true, |min_price| listing.price >= *min_price)
+      && self.max_price.as_ref().map_or(true, |max_price| listing.price <= *max_price)
+  }
+}
+
+/// A researcher offering to pay for registries that are not necessarily listed. Every registry
+/// counts as one record.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct DataRequest<AccountId, Balance, BlockNumber> {
+  pub researcher: AccountId,
+
+  pub criteria: RequestCriteria,
+
+  /// What the owner of each registry fulfilling the request is paid
+  pub price_per_record: Balance,
+
+  pub currency: CurrencyId,
+
+  /// Below this count at the deadline, the request expires and nobody is paid
+  pub min_records: u32,
+
+  /// The request is settled as soon as this count is reached, and the budget reserved is
+  /// `price_per_record * max_records`
+  pub max_records: u32,
+
+  /// The externally stored licence terms owners agree to
+  pub licence: LicenceTerms,
+
+  /// What the researcher intends to do with the records, which their owner must consent to
+  pub purpose: Purpose,
+
+  pub posted_at: BlockNumber,
+
+  /// The block the request is settled at, unless it is fulfilled before
+  pub deadline: BlockNumber,
+
+  pub records: RequestedRecords<AccountId>,
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
+pub enum AuctionKind<BlockNumber> {
+  /// Public bids, each raising the highest one. A bid placed less than `extension` blocks before the
+  /// end pushes the end back to `extension` blocks after the bid.
+  English { extension: BlockNumber },
+  /// Bids committed as hashes until the end of the auction, then revealed for `reveal_period`
+  /// blocks. The highest revealed bid wins and pays what it bid.
+  SealedBid { reveal_period: BlockNumber },
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Bid<AccountId, Balance> {
+  pub bidder: AccountId,
+
+  pub amount: Balance,
+
+  /// What the bidder intends to do with the registry, which its owner must consent to
+  pub purpose: Purpose,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct SealedBid<Hash, Balance> {
+  /// The hash of the bidder, the amount and a salt
+  pub commitment: Hash,
+
+  /// What is reserved from the bidder until the auction is settled, at least the amount bid
+  pub deposit: Balance,
+
+  pub purpose: Purpose,
+
+  /// The amount bid, once revealed
+  pub revealed: Option<Balance>,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Auction<AccountId, Balance, BlockNumber> {
+  pub kind: AuctionKind<BlockNumber>,
+
+  /// The lowest bid that can win
+  pub reserve_price: Balance,
+
+  pub currency: CurrencyId,
+
+  /// The externally stored licence terms the winner agrees to
+  pub licence: LicenceTerms,
+
+  /// The roles allowed to bid, a bidder needs at least one of them
+  pub buyer_roles: BuyerRoles,
+
+  /// How the winning bid is shared, instead of the defaults of the runtime
+  pub revenue_split: Option<RevenueSplit>,
+
+  pub started_at: BlockNumber,
+
+  /// The block bids cannot be placed or committed from
+  pub ends_at: BlockNumber,
+
+  /// The highest bid placed, or revealed, so far
+  pub highest_bid: Option<Bid<AccountId, Balance>>,
+
+  /// How many sealed bids were committed
+  pub sealed_bids: u32,
+}
+
+impl<AccountId, Balance, BlockNumber: Copy + Add<Output = BlockNumber>> Auction<AccountId, Balance, BlockNumber> {
+  /// The block the auction is settled at, once bids cannot be placed or revealed anymore.
+  pub fn settles_at(&self) -> BlockNumber {
+    match self.kind {
+      AuctionKind::English { .. } => self.ends_at,
+      AuctionKind::SealedBid { reveal_period } => self.ends_at + reveal_period,
+    }
+  }
+}
+
+/// What custodians decide to do with the price of a disputed purchase.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
+pub enum Verdict {
+  /// The price goes back to the buyer, who loses its access
+  Refund,
+  /// The price is paid to the seller, and the buyer loses its deposit
+  Release,
+  /// Half of the price is paid to the seller and the buyer keeps its access
+  Split,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Dispute<AccountId, Balance, BlockNumber, Hash> {
+  /// The hash of the evidence of the buyer, kept off-chain
+  pub evidence: Hash,
+
+  /// The hash of the evidence the seller answered with, if any
+  pub response: Option<Hash>,
+
+  /// Reserved from the buyer until the dispute is settled
+  pub deposit: Balance,
+
+  /// The custodians voting on the dispute, other than the buyer and the seller
+  pub panel: Panel<AccountId>,
+
+  pub votes: Votes<AccountId>,
+
+  pub opened_at: BlockNumber,
+
+  /// When the verdict with the most votes is applied, unless a majority of the panel agreed before
+  pub decide_by: BlockNumber,
+}
+
+impl<AccountId, Balance, BlockNumber, Hash> Dispute<AccountId, Balance, BlockNumber, Hash> {
+  /// The number of votes for `verdict`.
+  pub fn votes_for(&self, verdict: &Verdict) -> usize {
+    self.votes.iter().filter(|(_, vote)| vote == verdict).count()
+  }
+}
@@ -0,0 +1,397 @@
+//! Weights for pallet_marketplace.
+//!
+//! The `SubstrateWeight` values below are placeholders, estimated by hand from the storage accesses
+//! of each call rather than measured. Calls whose cost depends on the state rather than on their
+//! arguments are weighted for the worst case their bounds allow. They are to be replaced by running
+//! the benchmarks in `benchmarking.rs` against the reference hardware:
+//!
+//! ./target/release/meta benchmark --chain=dev --steps=50 --repeat=20 --pallet=pallet_marketplace
+//!   --extrinsic='*' --execution=wasm --wasm-execution=compiled --heap-pages=4096
+//!   --output=./pallets/marketplace/src/weights.rs --template=./.maintain/frame-weight-template.hbs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+  traits::Get,
+  weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_marketplace.
+pub trait WeightInfo {
+  fn list_registry(l: u32, r: u32) -> Weight;
+  fn delist_registry() -> Weight;
+  fn purchase_registry() -> Weight;
+  fn confirm_purchase() -> Weight;
+  fn release_escrow() -> Weight;
+  fn refund_purchase(s: u32) -> Weight;
+  fn set_aggregation_consent() -> Weight;
+  fn create_bundle(b: u32, l: u32, r: u32) -> Weight;
+  fn remove_bundle() -> Weight;
+  fn purchase_bundle(b: u32) -> Weight;
+  fn post_data_request(l: u32) -> Weight;
+  fn fulfil_data_request() -> Weight;
+  fn cancel_data_request() -> Weight;
+  fn give_consent(p: u32) -> Weight;
+  fn withdraw_consent(s: u32, f: u32) -> Weight;
+  fn reshare_purchase() -> Weight;
+  fn claim_refund() -> Weight;
+  fn create_auction(l: u32, r: u32) -> Weight;
+  fn cancel_auction() -> Weight;
+  fn place_bid() -> Weight;
+  fn commit_bid() -> Weight;
+  fn reveal_bid() -> Weight;
+  fn open_dispute() -> Weight;
+  fn answer_dispute() -> Weight;
+  fn vote_on_dispute() -> Weight;
+  fn settle_auction(s: u32) -> Weight;
+  fn settle_data_request(r: u32) -> Weight;
+  fn settle_dispute() -> Weight;
+}
+
+/// Weights for pallet_marketplace using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+  fn list_registry(l: u32, r: u32) -> Weight {
+    (36_000_000 as Weight)
+      .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+      .saturating_add((150_000 as Weight).saturating_mul(r as Weight))
+      .saturating_add(T::DbWeight::get().reads(5 as Weight))
+      .saturating_add(T::DbWeight::get().writes(2 as Weight))
+  }
+  fn delist_registry() -> Weight {
+    (24_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes(2 as Weight))
+  }
+  fn purchase_registry() -> Weight {
+    (62_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(9 as Weight))
+      .saturating_add(T::DbWeight::get().writes(5 as Weight))
+  }
+  fn confirm_purchase() -> Weight {
+    (58_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(6 as Weight))
+      .saturating_add(T::DbWeight::get().writes(6 as Weight))
+  }
+  fn release_escrow() -> Weight {
+    (58_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(6 as Weight))
+      .saturating_add(T::DbWeight::get().writes(6 as Weight))
+  }
+  fn refund_purchase(s: u32) -> Weight {
+    (41_000_000 as Weight)
+      .saturating_add((9_000_000 as Weight).saturating_mul(s as Weight))
+      .saturating_add(T::DbWeight::get().reads(4 as Weight))
+      .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+      .saturating_add(T::DbWeight::get().writes(4 as Weight))
+      .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+  }
+  fn set_aggregation_consent() -> Weight {
+    (20_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(1 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn create_bundle(b: u32, l: u32, r: u32) -> Weight {
+    (35_000_000 as Weight)
+      .saturating_add((8_000_000 as Weight).saturating_mul(b as Weight))
+      .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+      .saturating_add((150_000 as Weight).saturating_mul(r as Weight))
+      .saturating_add(T::DbWeight::get().reads(3 as Weight))
+      .saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(b as Weight)))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn remove_bundle() -> Weight {
+    (22_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(1 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn purchase_bundle(b: u32) -> Weight {
+    (48_000_000 as Weight)
+      .saturating_add((40_000_000 as Weight).saturating_mul(b as Weight))
+      .saturating_add(T::DbWeight::get().reads(3 as Weight))
+      .saturating_add(T::DbWeight::get().reads((8 as Weight).saturating_mul(b as Weight)))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+      .saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(b as Weight)))
+  }
+  fn post_data_request(l: u32) -> Weight {
+    (40_000_000 as Weight)
+      .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+      .saturating_add(T::DbWeight::get().reads(5 as Weight))
+      .saturating_add(T::DbWeight::get().writes(3 as Weight))
+  }
+  fn fulfil_data_request() -> Weight {
+    (45_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(8 as Weight))
+      .saturating_add(T::DbWeight::get().writes(3 as Weight))
+  }
+  fn cancel_data_request() -> Weight {
+    (30_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes(3 as Weight))
+  }
+  fn give_consent(p: u32) -> Weight {
+    (21_000_000 as Weight)
+      .saturating_add((300_000 as Weight).saturating_mul(p as Weight))
+      .saturating_add(T::DbWeight::get().reads(1 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn withdraw_consent(s: u32, f: u32) -> Weight {
+    (40_000_000 as Weight)
+      .saturating_add((20_000_000 as Weight).saturating_mul(s as Weight))
+      .saturating_add((10_000_000 as Weight).saturating_mul(f as Weight))
+      .saturating_add(T::DbWeight::get().reads(5 as Weight))
+      .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+      .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(f as Weight)))
+      .saturating_add(T::DbWeight::get().writes(5 as Weight))
+      .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+      .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(f as Weight)))
+  }
+  fn reshare_purchase() -> Weight {
+    (35_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(5 as Weight))
+      .saturating_add(T::DbWeight::get().writes(3 as Weight))
+  }
+  fn claim_refund() -> Weight {
+    (32_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(3 as Weight))
+      .saturating_add(T::DbWeight::get().writes(3 as Weight))
+  }
+  fn create_auction(l: u32, r: u32) -> Weight {
+    (40_000_000 as Weight)
+      .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+      .saturating_add((150_000 as Weight).saturating_mul(r as Weight))
+      .saturating_add(T::DbWeight::get().reads(6 as Weight))
+      .saturating_add(T::DbWeight::get().writes(3 as Weight))
+  }
+  fn cancel_auction() -> Weight {
+    (30_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(3 as Weight))
+      .saturating_add(T::DbWeight::get().writes(4 as Weight))
+  }
+  fn place_bid() -> Weight {
+    (50_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(6 as Weight))
+      .saturating_add(T::DbWeight::get().writes(4 as Weight))
+  }
+  fn commit_bid() -> Weight {
+    (45_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(6 as Weight))
+      .saturating_add(T::DbWeight::get().writes(3 as Weight))
+  }
+  fn reveal_bid() -> Weight {
+    (35_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes(2 as Weight))
+  }
+  fn open_dispute() -> Weight {
+    (45_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(5 as Weight))
+      .saturating_add(T::DbWeight::get().writes(4 as Weight))
+  }
+  fn answer_dispute() -> Weight {
+    (25_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn vote_on_dispute() -> Weight {
+    (70_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(7 as Weight))
+      .saturating_add(T::DbWeight::get().writes(8 as Weight))
+  }
+  fn settle_auction(s: u32) -> Weight {
+    (60_000_000 as Weight)
+      .saturating_add((12_000_000 as Weight).saturating_mul(s as Weight))
+      .saturating_add(T::DbWeight::get().reads(6 as Weight))
+      .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+      .saturating_add(T::DbWeight::get().writes(7 as Weight))
+      .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+  }
+  fn settle_data_request(r: u32) -> Weight {
+    (30_000_000 as Weight)
+      .saturating_add((45_000_000 as Weight).saturating_mul(r as Weight))
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(r as Weight)))
+      .saturating_add(T::DbWeight::get().writes(3 as Weight))
+      .saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(r as Weight)))
+  }
+  fn settle_dispute() -> Weight {
+    (70_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(6 as Weight))
+      .saturating_add(T::DbWeight::get().writes(8 as Weight))
+  }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+  fn list_registry(l: u32, r: u32) -> Weight {
+    (36_000_000 as Weight)
+      .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+      .saturating_add((150_000 as Weight).saturating_mul(r as Weight))
+      .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+  }
+  fn delist_registry() -> Weight {
+    (24_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+  }
+  fn purchase_registry() -> Weight {
+    (62_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+  }
+  fn confirm_purchase() -> Weight {
+    (58_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+  }
+  fn release_escrow() -> Weight {
+    (58_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+  }
+  fn refund_purchase(s: u32) -> Weight {
+    (41_000_000 as Weight)
+      .saturating_add((9_000_000 as Weight).saturating_mul(s as Weight))
+      .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+      .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+      .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+      .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+  }
+  fn set_aggregation_consent() -> Weight {
+    (20_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn create_bundle(b: u32, l: u32, r: u32) -> Weight {
+    (35_000_000 as Weight)
+      .saturating_add((8_000_000 as Weight).saturating_mul(b as Weight))
+      .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+      .saturating_add((150_000 as Weight).saturating_mul(r as Weight))
+      .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+      .saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(b as Weight)))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn remove_bundle() -> Weight {
+    (22_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn purchase_bundle(b: u32) -> Weight {
+    (48_000_000 as Weight)
+      .saturating_add((40_000_000 as Weight).saturating_mul(b as Weight))
+      .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+      .saturating_add(RocksDbWeight::get().reads((8 as Weight).saturating_mul(b as Weight)))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+      .saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(b as Weight)))
+  }
+  fn post_data_request(l: u32) -> Weight {
+    (40_000_000 as Weight)
+      .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+      .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+  }
+  fn fulfil_data_request() -> Weight {
+    (45_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+  }
+  fn cancel_data_request() -> Weight {
+    (30_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+  }
+  fn give_consent(p: u32) -> Weight {
+    (21_000_000 as Weight)
+      .saturating_add((300_000 as Weight).saturating_mul(p as Weight))
+      .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn withdraw_consent(s: u32, f: u32) -> Weight {
+    (40_000_000 as Weight)
+      .saturating_add((20_000_000 as Weight).saturating_mul(s as Weight))
+      .saturating_add((10_000_000 as Weight).saturating_mul(f as Weight))
+      .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+      .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+      .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(f as Weight)))
+      .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+      .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+      .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(f as Weight)))
+  }
+  fn reshare_purchase() -> Weight {
+    (35_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+  }
+  fn claim_refund() -> Weight {
+    (32_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+  }
+  fn create_auction(l: u32, r: u32) -> Weight {
+    (40_000_000 as Weight)
+      .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+      .saturating_add((150_000 as Weight).saturating_mul(r as Weight))
+      .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+  }
+  fn cancel_auction() -> Weight {
+    (30_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+  }
+  fn place_bid() -> Weight {
+    (50_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+  }
+  fn commit_bid() -> Weight {
+    (45_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+  }
+  fn reveal_bid() -> Weight {
+    (35_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+  }
+  fn open_dispute() -> Weight {
+    (45_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+  }
+  fn answer_dispute() -> Weight {
+    (25_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn vote_on_dispute() -> Weight {
+    (70_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+  }
+  fn settle_auction(s: u32) -> Weight {
+    (60_000_000 as Weight)
+      .saturating_add((12_000_000 as Weight).saturating_mul(s as Weight))
+      .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+      .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+      .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+      .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
+  }
+  fn settle_data_request(r: u32) -> Weight {
+    (30_000_000 as Weight)
+      .saturating_add((45_000_000 as Weight).saturating_mul(r as Weight))
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(r as Weight)))
+      .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+      .saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(r as Weight)))
+  }
+  fn settle_dispute() -> Weight {
+    (70_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+  }
+}
@@ -1,2 +1,3 @@
 pub const REGISTRY_INFO_MAX_LEN: u32 = 512;
 pub const DELIVERY_NETWORK_MAX_URI_LEN: u32 = 512;
+pub const MAX_SHARED_GROUPS: usize = 16;
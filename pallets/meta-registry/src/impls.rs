@@ -1,136 +1,362 @@
 use super::pallet::*;
+use crate::constants::MAX_SHARED_GROUPS;
+use crate::traits::RegistryProvider;
+use crate::types::{
+  AccessType, Accessibility, Chunk, ChunkHash, DeliveryNetwork, DeliveryNetworkId, DeliveryNetworkURI, Registry, RegistryHash, RegistryId,
+  RegistryInfo, RegistryProfile,
+};
+use frame_support::{dispatch::DispatchResult, ensure};
+use healthcare::{RecordFormat, TherapeuticArea};
+use pallet_social_network::{
+  traits::{GroupMembership, OnAccountRecovered, OrganizationProvider},
+  types::GroupId,
+};
+use region::{region_of, sub_region_of, Country, Region, SubRegion};
+use sp_std::vec::Vec;
 
-use frame_support::ensure;
-use frame_support::pallet_prelude::*;
-use frame_system::pallet_prelude::*;
+impl<T: Config> Pallet<T> {
+  pub fn create_delivery_network(
+    delivery_network_id: &DeliveryNetworkId,
+    uri: &DeliveryNetworkURI,
+    country: &Option<Country>,
+    region: &Option<Region>,
+    sub_region: &Option<SubRegion>,
+  ) -> Result<(), Error<T>> {
+    ensure!(
+      !DeliveryNetworks::<T>::contains_key(delivery_network_id),
+      Error::<T>::DeliveryNetworkAlreadyExisted
+    );
 
-use region::{region_of, sub_region_of};
+    DeliveryNetworks::<T>::insert(
+      delivery_network_id,
+      DeliveryNetwork {
+        uri: uri.clone(),
+        country: country.clone(),
+        region: region.clone(),
+        sub_region: sub_region.clone(),
+      },
+    );
 
-use crate::types::{Accessibility, ChunkHash};
+    Ok(())
+  }
 
-impl<T: Config> Pallet<T> {
-    pub fn create_delivery_network(
-        delivery_network_id: &DeliveryNetworkId,
-        uri: &DeliveryNetworkURI,
-        country: &Option<Country>,
-        region: &Option<Region>,
-        sub_region: &Option<SubRegion>,
-    ) -> Result<(), Error<T>> {
-        ensure!(
-            !DeliveryNetworks::contains_key(delivery_network_id),
-            Error::<T>::DeliveryNetworkAlreadyExisted
-        );
-
-        DeliveryNetworks::<T>::insert(
-            delivery_network_id,
-            DeliveryNetwork {
-                uri: uri.clone(),
-                country: country.clone(),
-                region: region.clone(),
-                sub_region: sub_region.clone(),
-            },
-        );
-
-        Ok(())
+  pub fn create_registry(
+    registry_id: &RegistryId,
+    owner_id: &T::AccountId,
+    issuer_id: &T::AccountId,
+    signer_id: &T::AccountId,
+    hash: &RegistryHash,
+    info: &RegistryInfo,
+    therapeutic_area: &Option<TherapeuticArea>,
+    format: &RecordFormat,
+    salable: &bool,
+    country: &Country,
+    delivery_network_id: &DeliveryNetworkId,
+    chunk_hashes: &Vec<ChunkHash>,
+  ) -> Result<(), Error<T>> {
+    ensure!(T::Organizations::can_act_for(issuer_id, signer_id), Error::<T>::NonAuthorized);
+    ensure!(
+      DeliveryNetworks::<T>::contains_key(delivery_network_id),
+      Error::<T>::DeliveryNetworkNotExisted
+    );
+    ensure!(!Registries::<T>::contains_key(registry_id), Error::<T>::RegistryAlreadyExisted);
+    for chunk_hash in chunk_hashes.iter() {
+      ensure!(!Chunks::<T>::contains_key(chunk_hash), Error::<T>::ChunkAlreadyExisted);
     }
 
-    pub fn create_registry(
-        registry_id: &RegistryId,
-        owner_id: &AccountId,
-        issuer_id: &AccountId,
-        hash: &RegistryHash,
-        info: &RegistryInfo,
-        salable: &bool,
-        country: &Country,
-        delivery_network_id: &DeliveryNetworkId,
-        chunk_hashes: &Vec<ChunkHash>,
-    ) -> Result<(), Error<T>> {
-        ensure!(
-            DeliveryNetworks::contains_key(delivery_network_id),
-            Error::<T>::DeliveryNetworkNotExisted
-        );
-        ensure!(
-            !Registries::<T>::contains_key(registry_id),
-            Error::<T>::RegistryAlreadyExisted
-        );
-        for chunk_hash in chunk_hashes.clone().iter() {
-            ensure!(
-                !maybe_chunk.contains_key(chunk_hash),
-                Error::<T>::ChunkAlreadyExisted
-            );
+    let now = <frame_system::Pallet<T>>::block_number();
+
+    for chunk_hash in chunk_hashes.iter() {
+      Chunks::<T>::insert(
+        chunk_hash,
+        Chunk {
+          registry_id: registry_id.clone(),
+          last_block: now,
+          status: Accessibility::New,
+        },
+      )
+    }
+
+    Accesses::<T>::insert(registry_id.clone(), issuer_id.clone(), AccessType::Issuer);
+    Accesses::<T>::insert(registry_id.clone(), owner_id.clone(), AccessType::Owner);
+    AccessibleRegistries::<T>::insert(issuer_id, registry_id, ());
+    AccessibleRegistries::<T>::insert(owner_id, registry_id, ());
+    OwnedRegistries::<T>::insert(owner_id, registry_id, ());
+
+    Registries::<T>::insert(
+      registry_id,
+      Registry {
+        delivery_network_id: delivery_network_id.clone(),
+        owner_id: owner_id.clone(),
+        issuer_id: issuer_id.clone(),
+        signer_id: signer_id.clone(),
+        hash: hash.clone(),
+        info: info.clone(),
+        therapeutic_area: *therapeutic_area,
+        format: *format,
+        salable: false,
+        country: country.clone(),
+        region: region_of(country.clone()),
+        sub_region: sub_region_of(country.clone()),
+        accessors: 2,
+        chunk_hashes: chunk_hashes.clone(),
+      },
+    );
+
+    Ok(())
+  }
+
+  pub fn delete_registry(registry_id: &RegistryId, actor_id: &T::AccountId) -> Result<(), Error<T>> {
+    let maybe_registry = Registries::<T>::get(registry_id);
+    ensure!(maybe_registry.is_some(), Error::<T>::RegistryNotExisted);
+
+    let registry = maybe_registry.unwrap();
+    ensure!(
+      registry.owner_id == *actor_id || T::Organizations::can_act_for(&registry.issuer_id, actor_id),
+      Error::<T>::NonAuthorized
+    );
+    ensure!(!registry.salable, Error::<T>::RegistrySalable);
+
+    for chunk_hash in registry.chunk_hashes.iter() {
+      Chunks::<T>::remove(chunk_hash);
+    }
+
+    for (who_id, _) in Accesses::<T>::drain_prefix(registry_id) {
+      AccessibleRegistries::<T>::remove(who_id, registry_id);
+    }
+    GroupShares::<T>::remove_prefix(registry_id, None);
+    OwnedRegistries::<T>::remove(&registry.owner_id, registry_id);
+    Registries::<T>::remove(registry_id);
+
+    Ok(())
+  }
+
+  pub fn update_chunk(chunk_hash: &ChunkHash, new_block: &T::BlockNumber, accessibility: &Accessibility) -> Result<(), Error<T>> {
+    Chunks::<T>::try_mutate(chunk_hash, |maybe_chunk| {
+      let mut chunk = maybe_chunk.take().ok_or(Error::<T>::ChunkNotExisted)?;
+
+      chunk.last_block = *new_block;
+      chunk.status = accessibility.clone();
+
+      *maybe_chunk = Some(chunk);
+
+      Ok(())
+    })
+  }
+
+  pub fn share_with_group(registry_id: &RegistryId, actor_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    let maybe_registry = Registries::<T>::get(registry_id);
+
+    ensure!(maybe_registry.is_some(), Error::<T>::RegistryNotExisted);
+    ensure!(maybe_registry.unwrap().owner_id == *actor_id, Error::<T>::NonAuthorized);
+    ensure!(!GroupShares::<T>::contains_key(registry_id, group_id), Error::<T>::GroupAlreadyShared);
+    ensure!(
+      GroupShares::<T>::iter_prefix(registry_id).count() < MAX_SHARED_GROUPS,
+      Error::<T>::TooManySharedGroups
+    );
+
+    GroupShares::<T>::insert(registry_id, group_id, ());
+
+    Self::deposit_event(Event::RegistryShared(registry_id.clone(), group_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn unshare_with_group(registry_id: &RegistryId, actor_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    let maybe_registry = Registries::<T>::get(registry_id);
+
+    ensure!(maybe_registry.is_some(), Error::<T>::RegistryNotExisted);
+    ensure!(maybe_registry.unwrap().owner_id == *actor_id, Error::<T>::NonAuthorized);
+    ensure!(GroupShares::<T>::contains_key(registry_id, group_id), Error::<T>::GroupNotShared);
+
+    GroupShares::<T>::remove(registry_id, group_id);
+
+    Self::deposit_event(Event::RegistryUnshared(registry_id.clone(), group_id.clone()));
+
+    Ok(())
+  }
+
+  /// The access `who_id` has on a registry, either granted directly in `Accesses` or derived from
+  /// the membership of a group the registry is shared with.
+  pub fn access_of(registry_id: &RegistryId, who_id: &T::AccountId) -> Option<AccessType> {
+    Accesses::<T>::get(registry_id, who_id).or_else(|| {
+      GroupShares::<T>::iter_prefix(registry_id)
+        .any(|(group_id, _)| T::GroupMembership::can_read(&group_id, who_id))
+        .then(|| AccessType::Accessor)
+    })
+  }
+}
+
+impl<T: Config> OnAccountRecovered<T::AccountId> for Pallet<T> {
+  fn on_account_recovered(lost_id: &T::AccountId, new_id: &T::AccountId) {
+    for (registry_id, _) in OwnedRegistries::<T>::drain_prefix(lost_id) {
+      Registries::<T>::mutate(&registry_id, |maybe_registry| {
+        if let Some(registry) = maybe_registry {
+          registry.owner_id = new_id.clone();
         }
+      });
+      OwnedRegistries::<T>::insert(new_id, &registry_id, ());
 
-        let now = <frame_system::Pallet<T>>::block_number();
-
-        for chunk_hash in chunk_hashes.clone().iter() {
-            Chunks::<T>::insert(
-                chunk_hash,
-                Chunk {
-                    registry_id: registry_id.clone(),
-                    last_block: now,
-                    status: Accessibility::New,
-                },
-            )
+      Self::deposit_event(Event::RegistryTransferred(registry_id, lost_id.clone(), new_id.clone()));
+    }
+
+    // Whatever `lost_id` could access as issuer, buyer or accessor, `new_id` can. When `new_id`
+    // already had an access of its own, it is kept and the registry counts one accessor less.
+    for (registry_id, _) in AccessibleRegistries::<T>::drain_prefix(lost_id) {
+      let access_type = match Accesses::<T>::take(&registry_id, lost_id) {
+        Some(access_type) => access_type,
+        None => continue,
+      };
+      let merged = Accesses::<T>::contains_key(&registry_id, new_id);
+      Registries::<T>::mutate(&registry_id, |maybe_registry| {
+        if let Some(registry) = maybe_registry {
+          if registry.issuer_id == *lost_id {
+            registry.issuer_id = new_id.clone();
+          }
+          if merged {
+            registry.accessors = registry.accessors.saturating_sub(1);
+          }
         }
+      });
+      if !merged {
+        Accesses::<T>::insert(&registry_id, new_id, access_type);
+        AccessibleRegistries::<T>::insert(new_id, &registry_id, ());
+      }
+    }
+  }
+}
+
+impl<T: Config> RegistryProvider<T::AccountId> for Pallet<T> {
+  fn owner_of(registry_id: &RegistryId) -> Option<T::AccountId> {
+    Registries::<T>::get(registry_id).map(|registry| registry.owner_id)
+  }
+
+  fn issuer_of(registry_id: &RegistryId) -> Option<T::AccountId> {
+    Registries::<T>::get(registry_id).map(|registry| registry.issuer_id)
+  }
 
-        Accesses::<T>::insert(registry_id.clone(), issuer_id.clone(), AccessType::Issuer);
-        Accesses::<T>::insert(registry_id.clone(), owner_id.clone(), AccessType::Owner);
-
-        Registries::<T>::insert(
-            registry_id,
-            Registry {
-                delivery_network_id: delivery_network_id.clone(),
-                owner_id: owner_id.clone(),
-                issuer_id: issuer_id.clone(),
-                hash: hash.clone(),
-                info: info.clone(),
-                salable: false,
-                country: country.clone(),
-                region: region_of(country.clone()),
-                sub_region: sub_region_of(country.clone()),
-                accessors: 2,
-                chunk_hashes: chunk_hashes.clone(),
-            },
-        );
-
-        Ok(())
+  fn profile_of(registry_id: &RegistryId) -> Option<RegistryProfile> {
+    Registries::<T>::get(registry_id).map(|registry| RegistryProfile {
+      therapeutic_area: registry.therapeutic_area,
+      format: registry.format,
+      country: registry.country,
+      region: registry.region,
+    })
+  }
+
+  fn registries_of(who_id: &T::AccountId) -> Vec<RegistryId> {
+    OwnedRegistries::<T>::iter_prefix(who_id).map(|(registry_id, _)| registry_id).collect()
+  }
+
+  fn granted_access(registry_id: &RegistryId, who_id: &T::AccountId) -> Option<AccessType> {
+    Accesses::<T>::get(registry_id, who_id)
+  }
+
+  fn set_salable(registry_id: &RegistryId, salable: bool) -> DispatchResult {
+    Registries::<T>::try_mutate(registry_id, |maybe_registry| {
+      let registry = maybe_registry.as_mut().ok_or(Error::<T>::RegistryNotExisted)?;
+
+      registry.salable = salable;
+
+      Ok(())
+    })
+  }
+
+  fn grant_access(registry_id: &RegistryId, who_id: &T::AccountId, access_type: &AccessType) -> DispatchResult {
+    ensure!(Registries::<T>::contains_key(registry_id), Error::<T>::RegistryNotExisted);
+
+    if Accesses::<T>::contains_key(registry_id, who_id) {
+      return Ok(());
     }
 
-    pub fn delete_registry(registry_id: &RegistryId, actor_id: &AccountId) {
-        let maybe_registry = Registries::<T>::get(registry_id);
-        ensure!(maybe_registry.is_some(), Error::<T>::RegistryNotExisted);
+    Accesses::<T>::insert(registry_id, who_id, access_type);
+    AccessibleRegistries::<T>::insert(who_id, registry_id, ());
+    Registries::<T>::mutate(registry_id, |maybe_registry| {
+      if let Some(registry) = maybe_registry {
+        registry.accessors = registry.accessors.saturating_add(1);
+      }
+    });
 
-        let registry = maybe_registry.unwrap();
-        ensure!(
-            registry.owner_id == actor_id || registry.issuer_id == actor_id,
-            Error::<T>::NonAuthorized
-        );
-        ensure!(!registry.salable, Error::<T>::RegistrySalable);
+    Self::deposit_event(Event::AccessGranted(registry_id.clone(), who_id.clone(), *access_type));
 
-        for chunk_hash in registry.chunk_hashes.iter() {
-            Chunks::<T>::remove(chunk_hash);
-        }
+    Ok(())
+  }
 
-        Accesses::<T>::clear_prefix(registry_id);
-        Registries::<T>::remove(registry_id);
+  fn revoke_access(registry_id: &RegistryId, who_id: &T::AccountId, access_type: &AccessType) -> DispatchResult {
+    ensure!(Registries::<T>::contains_key(registry_id), Error::<T>::RegistryNotExisted);
 
-        Ok(())
+    if Accesses::<T>::get(registry_id, who_id) != Some(*access_type) {
+      return Ok(());
     }
 
-    pub fn update_chunk(
-        chunk_hash: &ChunkHash,
-        new_block: &BlockNumber,
-        accessibility: &Accessibility,
-    ) -> Result<(), Error<T>> {
-        Chunks::<T>::try_mutate(chunk_hash, |maybe_chunk| {
-            let mut chunk = maybe_chunk.take().ok_or(Error::<T>::ChunkNotExisted)?;
+    Accesses::<T>::remove(registry_id, who_id);
+    AccessibleRegistries::<T>::remove(who_id, registry_id);
+    Registries::<T>::mutate(registry_id, |maybe_registry| {
+      if let Some(registry) = maybe_registry {
+        registry.accessors = registry.accessors.saturating_sub(1);
+      }
+    });
+
+    Self::deposit_event(Event::AccessRevoked(registry_id.clone(), who_id.clone(), *access_type));
 
-            chunk.last_block = new_block;
-            chunk.accessibility = accessibility;
+    Ok(())
+  }
 
-            *maybe_chunk = Some(chunk);
+  fn is_broken(registry_id: &RegistryId) -> bool {
+    Registries::<T>::get(registry_id).map_or(false, |registry| {
+      registry
+        .chunk_hashes
+        .iter()
+        .any(|chunk_hash| Chunks::<T>::get(chunk_hash).map_or(false, |chunk| chunk.status == Accessibility::Broken))
+    })
+  }
 
-            Ok(())
-        })
+  #[cfg(feature = "runtime-benchmarks")]
+  fn register_registry(registry_id: &RegistryId, owner_id: &T::AccountId, issuer_id: &T::AccountId, profile: &RegistryProfile) {
+    let chunk_hash: ChunkHash = sp_io::hashing::blake2_256(registry_id);
+
+    Chunks::<T>::insert(
+      chunk_hash,
+      Chunk {
+        registry_id: registry_id.clone(),
+        last_block: <frame_system::Pallet<T>>::block_number(),
+        status: Accessibility::New,
+      },
+    );
+    Accesses::<T>::insert(registry_id, issuer_id, AccessType::Issuer);
+    Accesses::<T>::insert(registry_id, owner_id, AccessType::Owner);
+    AccessibleRegistries::<T>::insert(issuer_id, registry_id, ());
+    AccessibleRegistries::<T>::insert(owner_id, registry_id, ());
+    OwnedRegistries::<T>::insert(owner_id, registry_id, ());
+    Registries::<T>::insert(
+      registry_id,
+      Registry {
+        delivery_network_id: Default::default(),
+        owner_id: owner_id.clone(),
+        issuer_id: issuer_id.clone(),
+        signer_id: issuer_id.clone(),
+        hash: Default::default(),
+        info: Default::default(),
+        therapeutic_area: profile.therapeutic_area,
+        format: profile.format,
+        salable: false,
+        country: profile.country.clone(),
+        region: profile.region.clone(),
+        sub_region: sub_region_of(profile.country.clone()),
+        accessors: 2,
+        chunk_hashes: sp_std::vec![chunk_hash],
+      },
+    );
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn break_registry(registry_id: &RegistryId) {
+    for chunk_hash in Registries::<T>::get(registry_id).map_or(Vec::new(), |registry| registry.chunk_hashes) {
+      Chunks::<T>::mutate(chunk_hash, |maybe_chunk| {
+        if let Some(chunk) = maybe_chunk {
+          chunk.status = Accessibility::Broken;
+        }
+      });
     }
+  }
 }
@@ -1,8 +1,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-mod constants;
-mod traits;
-mod types;
+pub mod constants;
+pub mod traits;
+pub mod types;
+
+mod impls;
 
 pub use pallet::*;
 
@@ -18,9 +20,15 @@ mod benchmarking;
 #[frame_support::pallet]
 pub mod pallet {
   use crate::traits::IssuerRules;
-  use crate::types::{AccessType, Chunk, ChunkHash, DeliveryNetwork, DeliveryNetworkId, Registry, RegistryId};
+  use crate::types::{AccessType, Chunk, ChunkHash, DeliveryNetwork, DeliveryNetworkId, Registry, RegistryHash, RegistryId, RegistryInfo};
   use frame_support::pallet_prelude::*;
   use frame_system::pallet_prelude::*;
+  use healthcare::{RecordFormat, TherapeuticArea};
+  use pallet_social_network::{
+    traits::{GroupMembership, OrganizationProvider},
+    types::GroupId,
+  };
+  use region::Country;
   use sp_std::vec::Vec;
 
   /// Configure the pallet by specifying the parameters and types on which it depends.
@@ -30,6 +38,12 @@ pub mod pallet {
     type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
     type IssuerRules: IssuerRules<Self::AccountId>;
+
+    /// Tells whether a member of a group a registry is shared with may read it.
+    type GroupMembership: GroupMembership<Self::AccountId>;
+
+    /// Tells whether a signer may issue registries on behalf of an organization.
+    type Organizations: OrganizationProvider<Self::AccountId>;
   }
 
   #[pallet::pallet]
@@ -56,6 +70,22 @@ pub mod pallet {
   #[pallet::getter(fn accesses)]
   pub type Accesses<T: Config> = StorageDoubleMap<_, Blake2_128Concat, RegistryId, Blake2_128Concat, T::AccountId, AccessType>;
 
+  /// The reverse of `Accesses`, keyed by account then registry.
+  #[pallet::storage]
+  #[pallet::getter(fn accessible_registries)]
+  pub type AccessibleRegistries<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, RegistryId, ()>;
+
+  /// Groups a registry is shared with. Readers of those groups get `AccessType::Accessor` on the
+  /// registry for as long as they stay in the group.
+  #[pallet::storage]
+  #[pallet::getter(fn group_shares)]
+  pub type GroupShares<T: Config> = StorageDoubleMap<_, Blake2_128Concat, RegistryId, Blake2_128Concat, GroupId, ()>;
+
+  /// The registries each account owns, keyed by owner then registry.
+  #[pallet::storage]
+  #[pallet::getter(fn owned_registries)]
+  pub type OwnedRegistries<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, RegistryId, ()>;
+
   // Pallets use events to inform users when important changes are made.
   // https://substrate.dev/docs/en/knowledgebase/runtime/events
   #[pallet::event]
@@ -64,6 +94,18 @@ pub mod pallet {
     /// Event documentation should end with an array that provides descriptive names for event
     /// parameters. [something, who]
     SomethingStored(u32, T::AccountId),
+    /// A registry was issued. \[registry_id, owner, issuer, signer\]
+    RegistryIssued(RegistryId, T::AccountId, T::AccountId, T::AccountId),
+    /// A registry was shared with a group. \[registry_id, group_id\]
+    RegistryShared(RegistryId, GroupId),
+    /// A registry is no longer shared with a group. \[registry_id, group_id\]
+    RegistryUnshared(RegistryId, GroupId),
+    /// A registry moved to the new key of its recovered owner. \[registry_id, lost, new\]
+    RegistryTransferred(RegistryId, T::AccountId, T::AccountId),
+    /// An access to a registry was granted by another pallet. \[registry_id, who, access_type\]
+    AccessGranted(RegistryId, T::AccountId, AccessType),
+    /// An access granted by another pallet was taken back. \[registry_id, who, access_type\]
+    AccessRevoked(RegistryId, T::AccountId, AccessType),
   }
 
   // Errors inform users that something went wrong.
@@ -73,6 +115,8 @@ pub mod pallet {
     ChunkNotExisted,
     DeliveryNetworkAlreadyExisted,
     DeliveryNetworkNotExisted,
+    GroupAlreadyShared,
+    GroupNotShared,
     NoneValue,
     Overflow,
     RegistryAlreadyExisted,
@@ -80,6 +124,7 @@ pub mod pallet {
     RegistrySalable,
     StorageOverflow,
     NonAuthorized,
+    TooManySharedGroups,
   }
 
   #[pallet::call]
@@ -90,5 +135,67 @@ pub mod pallet {
 
       Ok(())
     }
+
+    /// Issue a registry for `owner`. The signer is either the issuer itself or, when the issuer is an
+    /// organization, one of its members; the signer is recorded on the registry either way.
+    #[pallet::weight(10_000)]
+    pub fn issue_registry(
+      origin: OriginFor<T>,
+      registry_id: RegistryId,
+      owner: T::AccountId,
+      issuer: T::AccountId,
+      hash: RegistryHash,
+      info: RegistryInfo,
+      therapeutic_area: Option<TherapeuticArea>,
+      format: RecordFormat,
+      country: Country,
+      delivery_network_id: DeliveryNetworkId,
+      chunk_hashes: Vec<ChunkHash>,
+    ) -> DispatchResult {
+      let signer = ensure_signed(origin)?;
+
+      ensure!(
+        T::IssuerRules::can_create(&registry_id, &owner, &issuer, &hash, &info, &false, &country, &chunk_hashes),
+        Error::<T>::NonAuthorized
+      );
+      Self::create_registry(
+        &registry_id,
+        &owner,
+        &issuer,
+        &signer,
+        &hash,
+        &info,
+        &therapeutic_area,
+        &format,
+        &false,
+        &country,
+        &delivery_network_id,
+        &chunk_hashes,
+      )?;
+      T::IssuerRules::on_create(&registry_id, &owner, &issuer, &hash, &info, &false, &country, &chunk_hashes).map_err(|e| e.error)?;
+
+      Self::deposit_event(Event::RegistryIssued(registry_id, owner, issuer, signer));
+
+      Ok(())
+    }
+
+    /// Share a registry with a group. Only the owner of the registry can share it.
+    #[pallet::weight(10_000)]
+    pub fn share_registry(origin: OriginFor<T>, registry_id: RegistryId, group_id: GroupId) -> DispatchResult {
+      let owner_id = ensure_signed(origin)?;
+
+      Self::share_with_group(&registry_id, &owner_id, &group_id)?;
+
+      Ok(())
+    }
+
+    #[pallet::weight(10_000)]
+    pub fn unshare_registry(origin: OriginFor<T>, registry_id: RegistryId, group_id: GroupId) -> DispatchResult {
+      let owner_id = ensure_signed(origin)?;
+
+      Self::unshare_with_group(&registry_id, &owner_id, &group_id)?;
+
+      Ok(())
+    }
   }
 }
@@ -1,12 +1,15 @@
 use crate as pallet_template;
 use frame_support::parameter_types;
 use frame_system as system;
+use pallet_social_network::{traits::GroupMembership, types::GroupId};
 use sp_core::H256;
 use sp_runtime::{
   testing::Header,
   traits::{BlakeTwo256, IdentityLookup},
 };
 
+use std::cell::RefCell;
+
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -53,8 +56,24 @@ impl system::Config for Test {
   type OnSetCode = ();
 }
 
+thread_local! {
+  pub static GROUP_READERS: RefCell<Vec<(GroupId, u64)>> = RefCell::new(Vec::new());
+}
+
+/// Group memberships for tests, driven by `GROUP_READERS` instead of `pallet_social_network`.
+pub struct MockGroups;
+
+impl GroupMembership<u64> for MockGroups {
+  fn can_read(group_id: &GroupId, who: &u64) -> bool {
+    GROUP_READERS.with(|readers| readers.borrow().contains(&(group_id.clone(), *who)))
+  }
+}
+
 impl pallet_template::Config for Test {
   type Event = Event;
+  type IssuerRules = ();
+  type GroupMembership = MockGroups;
+  type Organizations = ();
 }
 
 // Build genesis storage according to the mock runtime.
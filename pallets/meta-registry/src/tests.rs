@@ -1,5 +1,13 @@
-use crate::{mock::*, Error};
+use crate::{
+  mock::*,
+  traits::RegistryProvider,
+  types::{AccessType, Accessibility, RegistryProfile},
+  Error,
+};
 use frame_support::{assert_noop, assert_ok};
+use healthcare::{RecordFormat, TherapeuticArea};
+use pallet_social_network::{traits::OnAccountRecovered, types::GroupId};
+use region::{Country, Region};
 
 #[test]
 fn it_works_for_default_value() {
@@ -18,3 +26,237 @@ fn correct_error_for_none_value() {
     assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
   });
 }
+
+fn registry_of(owner: u64, issuer: u64) -> crate::types::RegistryId {
+  let registry_id: crate::types::RegistryId = b"registry".to_vec().try_into().unwrap();
+  let delivery_network_id: crate::types::DeliveryNetworkId = b"network".to_vec().try_into().unwrap();
+
+  assert_ok!(TemplateModule::create_delivery_network(
+    &delivery_network_id,
+    &Default::default(),
+    &None,
+    &None,
+    &None
+  ));
+  assert_ok!(TemplateModule::create_registry(
+    &registry_id,
+    &owner,
+    &issuer,
+    &issuer,
+    &[0u8; 32],
+    &Default::default(),
+    &Some(TherapeuticArea::Cardiovascular),
+    &RecordFormat::FhirR4,
+    &false,
+    &Country::Netherlands,
+    &delivery_network_id,
+    &vec![[1u8; 32]],
+  ));
+
+  registry_id
+}
+
+#[test]
+fn group_readers_get_derived_access_while_they_are_members() {
+  new_test_ext().execute_with(|| {
+    let registry_id = registry_of(1, 2);
+    let group_id: GroupId = b"care-team".to_vec().try_into().unwrap();
+
+    assert_noop!(
+      TemplateModule::share_registry(Origin::signed(2), registry_id.clone(), group_id.clone()),
+      Error::<Test>::NonAuthorized
+    );
+    assert_ok!(TemplateModule::share_registry(Origin::signed(1), registry_id.clone(), group_id.clone()));
+
+    assert_eq!(TemplateModule::access_of(&registry_id, &3), None);
+    GROUP_READERS.with(|readers| readers.borrow_mut().push((group_id.clone(), 3)));
+    assert_eq!(TemplateModule::access_of(&registry_id, &3), Some(AccessType::Accessor));
+    assert_eq!(TemplateModule::accesses(&registry_id, 3), None);
+    assert_eq!(<TemplateModule as RegistryProvider<u64>>::granted_access(&registry_id, &3), None);
+
+    // Leaving the group ends the derived access without touching `Accesses`.
+    GROUP_READERS.with(|readers| readers.borrow_mut().clear());
+    assert_eq!(TemplateModule::access_of(&registry_id, &3), None);
+  });
+}
+
+#[test]
+fn unsharing_a_registry_ends_derived_access() {
+  new_test_ext().execute_with(|| {
+    let registry_id = registry_of(1, 2);
+    let group_id: GroupId = b"care-team".to_vec().try_into().unwrap();
+    GROUP_READERS.with(|readers| readers.borrow_mut().push((group_id.clone(), 3)));
+
+    assert_ok!(TemplateModule::share_registry(Origin::signed(1), registry_id.clone(), group_id.clone()));
+    assert_noop!(
+      TemplateModule::share_registry(Origin::signed(1), registry_id.clone(), group_id.clone()),
+      Error::<Test>::GroupAlreadyShared
+    );
+    assert_ok!(TemplateModule::unshare_registry(Origin::signed(1), registry_id.clone(), group_id.clone()));
+
+    assert_eq!(TemplateModule::access_of(&registry_id, &3), None);
+    assert_eq!(TemplateModule::access_of(&registry_id, &1), Some(AccessType::Owner));
+  });
+}
+
+#[test]
+fn registries_are_issued_only_by_the_issuer_or_on_its_behalf() {
+  new_test_ext().execute_with(|| {
+    let delivery_network_id: crate::types::DeliveryNetworkId = b"network".to_vec().try_into().unwrap();
+    assert_ok!(TemplateModule::create_delivery_network(
+      &delivery_network_id,
+      &Default::default(),
+      &None,
+      &None,
+      &None
+    ));
+
+    assert_noop!(
+      TemplateModule::issue_registry(
+        Origin::signed(3),
+        b"registry".to_vec().try_into().unwrap(),
+        1,
+        2,
+        [0u8; 32],
+        Default::default(),
+        None,
+        RecordFormat::Dicom,
+        Country::Netherlands,
+        delivery_network_id.clone(),
+        vec![[1u8; 32]],
+      ),
+      Error::<Test>::NonAuthorized
+    );
+    assert_ok!(TemplateModule::issue_registry(
+      Origin::signed(2),
+      b"registry".to_vec().try_into().unwrap(),
+      1,
+      2,
+      [0u8; 32],
+      Default::default(),
+      None,
+      RecordFormat::Dicom,
+      Country::Netherlands,
+      delivery_network_id,
+      vec![[1u8; 32]],
+    ));
+
+    let registry = TemplateModule::registries(crate::types::RegistryId::try_from(b"registry".to_vec()).unwrap()).unwrap();
+    assert_eq!((registry.issuer_id, registry.signer_id), (2, 2));
+  });
+}
+
+#[test]
+fn sold_registries_grant_buyer_access_and_cannot_be_deleted() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry_of(1, 2);
+
+    assert_eq!(<TemplateModule as RegistryProvider<u64>>::owner_of(&registry_id), Some(1));
+    assert_eq!(<TemplateModule as RegistryProvider<u64>>::issuer_of(&registry_id), Some(2));
+    assert_eq!(<TemplateModule as RegistryProvider<u64>>::registries_of(&1), vec![registry_id.clone()]);
+    assert_eq!(
+      <TemplateModule as RegistryProvider<u64>>::profile_of(&registry_id),
+      Some(RegistryProfile {
+        therapeutic_area: Some(TherapeuticArea::Cardiovascular),
+        format: RecordFormat::FhirR4,
+        country: Country::Netherlands,
+        region: Region::Europe,
+      })
+    );
+    assert_ok!(<TemplateModule as RegistryProvider<u64>>::set_salable(&registry_id, true));
+    assert_noop!(TemplateModule::delete_registry(&registry_id, &1), Error::<Test>::RegistrySalable);
+
+    assert_ok!(<TemplateModule as RegistryProvider<u64>>::grant_access(
+      &registry_id,
+      &3,
+      &AccessType::Buyer
+    ));
+    System::assert_last_event(Event::TemplateModule(crate::Event::AccessGranted(
+      registry_id.clone(),
+      3,
+      AccessType::Buyer,
+    )));
+    assert_eq!(TemplateModule::access_of(&registry_id, &3), Some(AccessType::Buyer));
+    assert_eq!(TemplateModule::registries(&registry_id).unwrap().accessors, 3);
+
+    // The owner keeps its own access when it is granted another one.
+    assert_ok!(<TemplateModule as RegistryProvider<u64>>::grant_access(
+      &registry_id,
+      &1,
+      &AccessType::Buyer
+    ));
+    assert_eq!(TemplateModule::access_of(&registry_id, &1), Some(AccessType::Owner));
+
+    assert_ok!(<TemplateModule as RegistryProvider<u64>>::set_salable(&registry_id, false));
+    assert_ok!(TemplateModule::delete_registry(&registry_id, &1));
+  });
+}
+
+#[test]
+fn granted_accesses_are_revoked_and_broken_chunks_reported() {
+  new_test_ext().execute_with(|| {
+    let registry_id = registry_of(1, 2);
+
+    assert_ok!(<TemplateModule as RegistryProvider<u64>>::grant_access(
+      &registry_id,
+      &3,
+      &AccessType::Buyer
+    ));
+    assert_ok!(<TemplateModule as RegistryProvider<u64>>::revoke_access(
+      &registry_id,
+      &1,
+      &AccessType::Buyer
+    ));
+    assert_eq!(TemplateModule::access_of(&registry_id, &1), Some(AccessType::Owner));
+    assert_ok!(<TemplateModule as RegistryProvider<u64>>::revoke_access(
+      &registry_id,
+      &3,
+      &AccessType::Buyer
+    ));
+    assert_eq!(TemplateModule::access_of(&registry_id, &3), None);
+    assert_eq!(TemplateModule::registries(&registry_id).unwrap().accessors, 2);
+
+    assert!(!<TemplateModule as RegistryProvider<u64>>::is_broken(&registry_id));
+    assert_ok!(TemplateModule::update_chunk(&[1u8; 32], &1, &Accessibility::Broken));
+    assert!(<TemplateModule as RegistryProvider<u64>>::is_broken(&registry_id));
+  });
+}
+
+#[test]
+fn recovered_accounts_keep_their_accesses() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let registry_id = registry_of(1, 2);
+    assert_ok!(<TemplateModule as RegistryProvider<u64>>::grant_access(
+      &registry_id,
+      &3,
+      &AccessType::Buyer
+    ));
+
+    <TemplateModule as OnAccountRecovered<u64>>::on_account_recovered(&1, &11);
+    <TemplateModule as OnAccountRecovered<u64>>::on_account_recovered(&2, &12);
+    <TemplateModule as OnAccountRecovered<u64>>::on_account_recovered(&3, &13);
+
+    assert_eq!(<TemplateModule as RegistryProvider<u64>>::owner_of(&registry_id), Some(11));
+    assert_eq!(<TemplateModule as RegistryProvider<u64>>::issuer_of(&registry_id), Some(12));
+    assert_eq!(TemplateModule::access_of(&registry_id, &11), Some(AccessType::Owner));
+    assert_eq!(TemplateModule::access_of(&registry_id, &12), Some(AccessType::Issuer));
+    assert_eq!(TemplateModule::access_of(&registry_id, &13), Some(AccessType::Buyer));
+    for lost in [1, 2, 3] {
+      assert_eq!(TemplateModule::access_of(&registry_id, &lost), None);
+      assert_eq!(TemplateModule::accessible_registries(lost, &registry_id), None);
+    }
+    assert_eq!(TemplateModule::registries(&registry_id).unwrap().accessors, 3);
+
+    // An access the new key already had is kept, and counted once.
+    assert_ok!(<TemplateModule as RegistryProvider<u64>>::grant_access(
+      &registry_id,
+      &14,
+      &AccessType::Accessor
+    ));
+    <TemplateModule as OnAccountRecovered<u64>>::on_account_recovered(&13, &14);
+    assert_eq!(TemplateModule::access_of(&registry_id, &14), Some(AccessType::Accessor));
+    assert_eq!(TemplateModule::registries(&registry_id).unwrap().accessors, 3);
+  });
+}
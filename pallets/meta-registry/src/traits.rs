@@ -1,6 +1,5 @@
-use crate::types::{ChunkHash, RegistryHash, RegistryId, RegistryInfo};
-use crate::Config;
-use frame_support::dispatch::DispatchResultWithPostInfo;
+use crate::types::{AccessType, ChunkHash, RegistryHash, RegistryId, RegistryInfo, RegistryProfile};
+use frame_support::dispatch::{DispatchResult, DispatchResultWithPostInfo};
 use region::Country;
 use sp_std::vec::Vec;
 
@@ -107,3 +106,97 @@ impl<AccountId> IssuerRules<AccountId> for () {
     Ok(().into())
   }
 }
+
+pub trait RegistryProvider<AccountId> {
+  /// The owner of `registry_id`, if the registry exists.
+  fn owner_of(registry_id: &RegistryId) -> Option<AccountId>;
+
+  /// The issuer of `registry_id`, if the registry exists.
+  fn issuer_of(registry_id: &RegistryId) -> Option<AccountId>;
+
+  /// What `registry_id` holds, if the registry exists.
+  fn profile_of(registry_id: &RegistryId) -> Option<RegistryProfile>;
+
+  /// The registries `who` owns.
+  fn registries_of(who: &AccountId) -> Vec<RegistryId>;
+
+  /// The access granted to `who` on `registry_id` in `Accesses`, leaving out the access it only
+  /// has for as long as it reads a group the registry is shared with.
+  fn granted_access(registry_id: &RegistryId, who: &AccountId) -> Option<AccessType>;
+
+  /// Flag a registry as offered for sale, or not anymore. A salable registry cannot be deleted.
+  fn set_salable(registry_id: &RegistryId, salable: bool) -> DispatchResult;
+
+  /// Grant `who` an access to `registry_id`. An access `who` already holds is kept as it is.
+  fn grant_access(registry_id: &RegistryId, who: &AccountId, access_type: &AccessType) -> DispatchResult;
+
+  /// Take back an access granted with `grant_access`. Any other access `who` holds is left untouched.
+  fn revoke_access(registry_id: &RegistryId, who: &AccountId, access_type: &AccessType) -> DispatchResult;
+
+  /// Check if the delivery network reported any chunk of `registry_id` as `Accessibility::Broken`.
+  fn is_broken(registry_id: &RegistryId) -> bool;
+
+  /// Create `registry_id` with a single chunk, owned by `owner` and issued by `issuer`, holding what
+  /// `profile` tells, so that benchmarks can trade it.
+  #[cfg(feature = "runtime-benchmarks")]
+  fn register_registry(registry_id: &RegistryId, owner: &AccountId, issuer: &AccountId, profile: &RegistryProfile);
+
+  /// Report the chunks of `registry_id` as broken, so that benchmarks can refund its purchases.
+  #[cfg(feature = "runtime-benchmarks")]
+  fn break_registry(registry_id: &RegistryId);
+}
+
+impl<AccountId> RegistryProvider<AccountId> for () {
+  #[allow(unused_variables)]
+  fn owner_of(registry_id: &RegistryId) -> Option<AccountId> {
+    None
+  }
+
+  #[allow(unused_variables)]
+  fn issuer_of(registry_id: &RegistryId) -> Option<AccountId> {
+    None
+  }
+
+  #[allow(unused_variables)]
+  fn profile_of(registry_id: &RegistryId) -> Option<RegistryProfile> {
+    None
+  }
+
+  #[allow(unused_variables)]
+  fn registries_of(who: &AccountId) -> Vec<RegistryId> {
+    Vec::new()
+  }
+
+  #[allow(unused_variables)]
+  fn granted_access(registry_id: &RegistryId, who: &AccountId) -> Option<AccessType> {
+    None
+  }
+
+  #[allow(unused_variables)]
+  fn set_salable(registry_id: &RegistryId, salable: bool) -> DispatchResult {
+    Ok(())
+  }
+
+  #[allow(unused_variables)]
+  fn grant_access(registry_id: &RegistryId, who: &AccountId, access_type: &AccessType) -> DispatchResult {
+    Ok(())
+  }
+
+  #[allow(unused_variables)]
+  fn revoke_access(registry_id: &RegistryId, who: &AccountId, access_type: &AccessType) -> DispatchResult {
+    Ok(())
+  }
+
+  #[allow(unused_variables)]
+  fn is_broken(registry_id: &RegistryId) -> bool {
+    false
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  #[allow(unused_variables)]
+  fn register_registry(registry_id: &RegistryId, owner: &AccountId, issuer: &AccountId, profile: &RegistryProfile) {}
+
+  #[cfg(feature = "runtime-benchmarks")]
+  #[allow(unused_variables)]
+  fn break_registry(registry_id: &RegistryId) {}
+}
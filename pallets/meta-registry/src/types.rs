@@ -3,6 +3,7 @@
 use crate::constants::{DELIVERY_NETWORK_MAX_URI_LEN, REGISTRY_INFO_MAX_LEN};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::pallet_prelude::*;
+use healthcare::{RecordFormat, TherapeuticArea};
 use region::{Country, Region, SubRegion};
 use scale_info::TypeInfo;
 use sp_std::cmp::{Eq, PartialEq};
@@ -23,7 +24,7 @@ pub enum Accessibility {
   Broken,
 }
 
-#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Copy)]
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
 pub enum AccessType {
   Issuer,
   Owner,
@@ -45,8 +46,14 @@ pub struct Registry<AccountId> {
   pub delivery_network_id: DeliveryNetworkId,
   pub owner_id: AccountId,
   pub issuer_id: AccountId,
+  /// The individual who signed on behalf of the issuer, the issuer itself unless it is an organization
+  pub signer_id: AccountId,
   pub hash: RegistryHash,
   pub info: RegistryInfo,
+  /// The specialty the records are about, if any
+  pub therapeutic_area: Option<TherapeuticArea>,
+  /// The format the records are kept in
+  pub format: RecordFormat,
   pub salable: bool,
   pub country: Country,
   pub region: Region,
@@ -61,3 +68,12 @@ pub struct Chunk<BlockNumber> {
   pub last_block: BlockNumber,
   pub status: Accessibility,
 }
+
+/// What a registry holds, as far as buyers can tell without accessing it.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RegistryProfile {
+  pub therapeutic_area: Option<TherapeuticArea>,
+  pub format: RecordFormat,
+  pub country: Country,
+  pub region: Region,
+}
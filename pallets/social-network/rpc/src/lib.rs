@@ -0,0 +1,202 @@
+//! JSON-RPC for querying the social graph through `SocialNetworkApi`.
+
+use codec::Codec;
+use healthcare::Role;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_social_network::types::{AccessControl, AccountDetail, AccountStatus, ConnectionInfo, Group, GroupId, JoinPolicy, Relation, Visibility};
+pub use pallet_social_network_runtime_api::SocialNetworkApi as SocialNetworkRuntimeApi;
+use region::{Country, Region, SubRegion};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+/// The runtime API call failed.
+const RUNTIME_ERROR: i64 = 1;
+/// The group id is longer than a `GroupId` can hold.
+const INVALID_GROUP_ID: i64 = 2;
+
+/// An account as returned over RPC, with its bounded fields unwrapped.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountView<AccountId> {
+  pub issuer: AccountId,
+  pub freezer: Option<AccountId>,
+  pub status: AccountStatus,
+  pub roles: Vec<Role>,
+  pub enterprise: Option<AccountId>,
+  pub info: Bytes,
+}
+
+impl<AccountId> From<AccountDetail<AccountId>> for AccountView<AccountId> {
+  fn from(detail: AccountDetail<AccountId>) -> Self {
+    AccountView {
+      issuer: detail.issuer,
+      freezer: detail.freezer,
+      status: detail.status,
+      roles: detail.roles.into_inner(),
+      enterprise: detail.enterprise,
+      info: detail.info.into_inner().into(),
+    }
+  }
+}
+
+/// A group as returned over RPC, with its bounded fields unwrapped.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupView<AccountId> {
+  pub id: Bytes,
+  pub owner: AccountId,
+  pub parent: Option<Bytes>,
+  pub info: Bytes,
+  pub country: Country,
+  pub region: Region,
+  pub sub_region: SubRegion,
+  pub visibility: Visibility,
+  pub join_policy: JoinPolicy,
+  pub admins: u32,
+  pub members: u32,
+}
+
+impl<AccountId> From<(GroupId, Group<AccountId>)> for GroupView<AccountId> {
+  fn from((id, group): (GroupId, Group<AccountId>)) -> Self {
+    GroupView {
+      id: id.into_inner().into(),
+      owner: group.owner,
+      parent: group.parent.map(|parent| parent.into_inner().into()),
+      info: group.info.into_inner().into(),
+      country: group.country,
+      region: group.region,
+      sub_region: group.sub_region,
+      visibility: group.visibility,
+      join_policy: group.join_policy,
+      admins: group.admins,
+      members: group.members,
+    }
+  }
+}
+
+#[rpc]
+pub trait SocialNetworkApi<BlockHash, AccountId> {
+  #[rpc(name = "socialNetwork_connectionsOf")]
+  fn connections_of(
+    &self,
+    account: AccountId,
+    status: Option<Relation>,
+    cursor: u32,
+    limit: u32,
+    at: Option<BlockHash>,
+  ) -> Result<Vec<ConnectionInfo<AccountId>>>;
+
+  #[rpc(name = "socialNetwork_groupsOf")]
+  fn groups_of(&self, account: AccountId, at: Option<BlockHash>) -> Result<Vec<(Bytes, AccessControl)>>;
+
+  #[rpc(name = "socialNetwork_membersOf")]
+  fn members_of(&self, group: Bytes, cursor: u32, limit: u32, at: Option<BlockHash>) -> Result<Vec<(AccountId, AccessControl)>>;
+
+  #[rpc(name = "socialNetwork_account")]
+  fn account(&self, account: AccountId, at: Option<BlockHash>) -> Result<Option<AccountView<AccountId>>>;
+
+  #[rpc(name = "socialNetwork_mutualConnections")]
+  fn mutual_connections(&self, a: AccountId, b: AccountId, at: Option<BlockHash>) -> Result<Vec<AccountId>>;
+
+  #[rpc(name = "socialNetwork_nearbyGroups")]
+  fn nearby_groups(&self, country: Country, cursor: u32, limit: u32, at: Option<BlockHash>) -> Result<Vec<GroupView<AccountId>>>;
+}
+
+pub struct SocialNetwork<C, Block> {
+  client: Arc<C>,
+  _marker: PhantomData<Block>,
+}
+
+impl<C, Block> SocialNetwork<C, Block> {
+  pub fn new(client: Arc<C>) -> Self {
+    SocialNetwork {
+      client,
+      _marker: Default::default(),
+    }
+  }
+}
+
+fn runtime_error(err: impl std::fmt::Debug) -> RpcError {
+  RpcError {
+    code: ErrorCode::ServerError(RUNTIME_ERROR),
+    message: "Unable to query the social network.".into(),
+    data: Some(format!("{:?}", err).into()),
+  }
+}
+
+impl<C, Block, AccountId> SocialNetworkApi<<Block as BlockT>::Hash, AccountId> for SocialNetwork<C, Block>
+where
+  Block: BlockT,
+  C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+  C::Api: SocialNetworkRuntimeApi<Block, AccountId>,
+  AccountId: Codec,
+{
+  fn connections_of(
+    &self,
+    account: AccountId,
+    status: Option<Relation>,
+    cursor: u32,
+    limit: u32,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> Result<Vec<ConnectionInfo<AccountId>>> {
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    self
+      .client
+      .runtime_api()
+      .connections_of(&at, account, status, cursor, limit)
+      .map_err(runtime_error)
+  }
+
+  fn groups_of(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(Bytes, AccessControl)>> {
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+    let groups = self.client.runtime_api().groups_of(&at, account).map_err(runtime_error)?;
+
+    Ok(
+      groups
+        .into_iter()
+        .map(|(group_id, access_control)| (group_id.into_inner().into(), access_control))
+        .collect(),
+    )
+  }
+
+  fn members_of(&self, group: Bytes, cursor: u32, limit: u32, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(AccountId, AccessControl)>> {
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+    let group_id: GroupId = group.0.try_into().map_err(|_| RpcError {
+      code: ErrorCode::ServerError(INVALID_GROUP_ID),
+      message: "Group id is too long.".into(),
+      data: None,
+    })?;
+
+    self.client.runtime_api().members_of(&at, group_id, cursor, limit).map_err(runtime_error)
+  }
+
+  fn account(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Option<AccountView<AccountId>>> {
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+    let detail = self.client.runtime_api().account(&at, account).map_err(runtime_error)?;
+
+    Ok(detail.map(Into::into))
+  }
+
+  fn mutual_connections(&self, a: AccountId, b: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<AccountId>> {
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    self.client.runtime_api().mutual_connections(&at, a, b).map_err(runtime_error)
+  }
+
+  fn nearby_groups(&self, country: Country, cursor: u32, limit: u32, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<GroupView<AccountId>>> {
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+    let groups = self
+      .client
+      .runtime_api()
+      .nearby_groups(&at, country, cursor, limit)
+      .map_err(runtime_error)?;
+
+    Ok(groups.into_iter().map(Into::into).collect())
+  }
+}
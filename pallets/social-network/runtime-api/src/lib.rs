@@ -0,0 +1,31 @@
+//! Runtime API to query the social graph without walking raw storage keys.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_social_network::types::{AccessControl, AccountDetail, ConnectionInfo, Group, GroupId, Relation};
+use region::Country;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+  pub trait SocialNetworkApi<AccountId> where AccountId: Codec {
+    /// The connections of `account` in either direction, optionally only those in `status`. The
+    /// first `cursor` entries are skipped and at most `limit` are returned.
+    fn connections_of(account: AccountId, status: Option<Relation>, cursor: u32, limit: u32) -> Vec<ConnectionInfo<AccountId>>;
+
+    /// The groups `account` is a direct member of, with its access control in each.
+    fn groups_of(account: AccountId) -> Vec<(GroupId, AccessControl)>;
+
+    /// The direct members of `group`, paginated like `connections_of`.
+    fn members_of(group: GroupId, cursor: u32, limit: u32) -> Vec<(AccountId, AccessControl)>;
+
+    fn account(account: AccountId) -> Option<AccountDetail<AccountId>>;
+
+    /// The accounts both `a` and `b` are connected to.
+    fn mutual_connections(a: AccountId, b: AccountId) -> Vec<AccountId>;
+
+    /// Groups in `country`, then elsewhere in its sub-region, then elsewhere in its region,
+    /// paginated like `connections_of`.
+    fn nearby_groups(country: Country, cursor: u32, limit: u32) -> Vec<(GroupId, Group<AccountId>)>;
+  }
+}
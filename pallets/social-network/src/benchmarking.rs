@@ -1,19 +1,618 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-social-network
 
 use super::*;
-#[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use crate::{
+  constants::{MAX_CHANNEL_MESSAGES, MAX_EXPIRIES_PER_BLOCK, MAX_LAPSE_DELAY, MAX_MESSAGE_LEN, MAX_RECOVERY_CONTACTS, MAX_SUB_GROUPS},
+  types::{
+    AccessControl, AccountDetail, AccountStatus, CredentialKind, EncryptedMessage, GroupId, JoinPolicy, OrganizationRole, PendingMemberships,
+    Relation, Roles, Visibility,
+  },
+  Pallet as SocialNetwork,
+};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
 use frame_system::RawOrigin;
+use healthcare::Role;
+use pallet_key_registry::traits::KeyProvider;
+use region::Country;
+use sp_runtime::traits::{Bounded, One};
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+const SEED: u32 = 0;
+
+/// A live account able to afford every deposit.
+fn live<T: Config>(who: &T::AccountId, roles: Vec<Role>) {
+  T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+  Accounts::<T>::insert(
+    who,
+    AccountDetail {
+      issuer: who.clone(),
+      freezer: None,
+      status: AccountStatus::Live,
+      roles: roles.try_into().unwrap(),
+      enterprise: None,
+      info: Default::default(),
+    },
+  );
+}
+
+fn member<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+  let who = account(name, index, SEED);
+  live::<T>(&who, vec![Role::Patient]);
+  who
+}
+
+fn custodian<T: Config>() -> T::AccountId {
+  let custodian = account("custodian", 0, SEED);
+  T::Currency::make_free_balance_be(&custodian, BalanceOf::<T>::max_value() / 2u32.into());
+  SocialNetwork::<T>::add_custodian(&custodian).unwrap();
+  custodian
+}
+
+fn group_id(id: &[u8]) -> GroupId {
+  id.to_vec().try_into().unwrap()
+}
+
+fn group<T: Config>(owner: &T::AccountId, id: &[u8], parent: Option<GroupId>, join_policy: JoinPolicy) -> GroupId {
+  let id = group_id(id);
+  SocialNetwork::<T>::do_create_group(
+    owner,
+    &id,
+    &Default::default(),
+    &Country::Netherlands,
+    &parent,
+    &Visibility::Public,
+    &join_policy,
+  )
+  .unwrap();
+  id
+}
+
+/// Let `who` in a group through an invitation of `admin`.
+fn joined<T: Config>(admin: &T::AccountId, who: &T::AccountId, id: &GroupId, access_control: AccessControl) -> Result<(), Error<T>> {
+  SocialNetwork::<T>::do_invite(admin, who, id, &access_control)?;
+  SocialNetwork::<T>::do_accept_invitation(who, id)
+}
+
+fn connected<T: Config>(a: &T::AccountId, b: &T::AccountId) {
+  SocialNetwork::<T>::connect(a, b).unwrap();
+  SocialNetwork::<T>::do_connect(a, b).unwrap();
+}
+
+/// Fill every block a membership made now could lapse at but the last, so that scheduling its lapse
+/// walks all of them.
+fn full_lapses<T: Config>() {
+  let first = frame_system::Pallet::<T>::block_number() + T::MembershipExpiry::get() + One::one();
+  let pending: PendingMemberships<T::AccountId> = (0..MAX_EXPIRIES_PER_BLOCK)
+    .map(|index| (group_id(b"other"), account("pending", index, SEED)))
+    .collect::<Vec<_>>()
+    .try_into()
+    .unwrap();
+  for delay in 0..MAX_LAPSE_DELAY {
+    MembershipExpiries::<T>::insert(first + delay.into(), pending.clone());
+  }
+}
+
+fn recovering<T: Config>(lost: &T::AccountId, new: &T::AccountId) -> T::AccountId {
+  let contact = member::<T>("contact", 0);
+  connected::<T>(lost, &contact);
+  SocialNetwork::<T>::configure_recovery(lost, &vec![contact.clone()], 1).unwrap();
+  SocialNetwork::<T>::vouch(&contact, lost, new).unwrap();
+  contact
+}
 
 benchmarks! {
-    do_something {
-        let s in 0 .. 100;
-        let caller: T::AccountId = whitelisted_caller();
-    }: _(RawOrigin::Signed(caller), s)
-    verify {
-        assert_eq!(Something::<T>::get(), Some(s));
+  onboard_account {
+    let custodian = custodian::<T>();
+    let who: T::AccountId = account("who", 0, SEED);
+    let roles: Roles = vec![Role::Patient].try_into().unwrap();
+  }: _(RawOrigin::Signed(custodian), who.clone(), roles, None, Default::default())
+  verify {
+    assert!(Accounts::<T>::contains_key(&who));
+  }
+
+  close_account {
+    let custodian = custodian::<T>();
+    let who: T::AccountId = whitelisted_caller();
+    let roles: Roles = vec![Role::Patient].try_into().unwrap();
+    SocialNetwork::<T>::onboard(&custodian, &who, &roles, &None, &Default::default())?;
+  }: _(RawOrigin::Signed(who.clone()))
+  verify {
+    assert!(!Accounts::<T>::contains_key(&who));
+  }
+
+  freeze_account {
+    let custodian = custodian::<T>();
+    let who = member::<T>("who", 0);
+  }: _(RawOrigin::Signed(custodian), who.clone())
+  verify {
+    assert_eq!(Accounts::<T>::get(&who).unwrap().status, AccountStatus::Frozen);
+  }
+
+  unfreeze_account {
+    let custodian = custodian::<T>();
+    let who = member::<T>("who", 0);
+    SocialNetwork::<T>::freeze(&custodian, &who)?;
+  }: _(RawOrigin::Signed(custodian), who.clone())
+  verify {
+    assert_eq!(Accounts::<T>::get(&who).unwrap().status, AccountStatus::Live);
+  }
+
+  request_role_change {
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    let roles: Roles = vec![Role::Patient, Role::Researcher].try_into().unwrap();
+  }: _(RawOrigin::Signed(who.clone()), roles, None)
+  verify {
+    assert!(RoleChangeRequests::<T>::contains_key(&who));
+  }
+
+  approve_role_change {
+    let custodian = custodian::<T>();
+    let who = member::<T>("who", 0);
+    let roles: Roles = vec![Role::Patient, Role::Researcher].try_into().unwrap();
+    SocialNetwork::<T>::do_request_role_change(&who, &roles, &None)?;
+  }: _(RawOrigin::Signed(custodian), who.clone())
+  verify {
+    assert_eq!(Accounts::<T>::get(&who).unwrap().roles, roles);
+  }
+
+  reject_role_change {
+    let custodian = custodian::<T>();
+    let who = member::<T>("who", 0);
+    let roles: Roles = vec![Role::Patient, Role::Researcher].try_into().unwrap();
+    SocialNetwork::<T>::do_request_role_change(&who, &roles, &None)?;
+  }: _(RawOrigin::Signed(custodian), who.clone())
+  verify {
+    assert!(!RoleChangeRequests::<T>::contains_key(&who));
+  }
+
+  request_connection {
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    let to = member::<T>("to", 0);
+  }: _(RawOrigin::Signed(who.clone()), to.clone())
+  verify {
+    assert_eq!(Connections::<T>::get(&who, &to), Some(Relation::Pending));
+  }
+
+  accept_connection {
+    let from = member::<T>("from", 0);
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    SocialNetwork::<T>::connect(&from, &who)?;
+  }: _(RawOrigin::Signed(who.clone()), from.clone())
+  verify {
+    assert!(SocialNetwork::<T>::is_connected(&from, &who));
+  }
+
+  remove_connection {
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    let other = member::<T>("other", 0);
+    connected::<T>(&other, &who);
+  }: _(RawOrigin::Signed(who.clone()), other.clone())
+  verify {
+    assert!(!SocialNetwork::<T>::is_connected(&other, &who));
+  }
+
+  create_group {
+    let owner: T::AccountId = whitelisted_caller();
+    live::<T>(&owner, vec![Role::Patient]);
+    let parent = group::<T>(&owner, b"parent", None, JoinPolicy::RequestApproval);
+    let id = group_id(b"group");
+  }: _(
+    RawOrigin::Signed(owner),
+    id.clone(),
+    Default::default(),
+    Country::Netherlands,
+    Some(parent),
+    Visibility::Public,
+    JoinPolicy::RequestApproval
+  )
+  verify {
+    assert!(Groups::<T>::contains_key(&id));
+  }
+
+  set_group_policy {
+    let owner: T::AccountId = whitelisted_caller();
+    live::<T>(&owner, vec![Role::Patient]);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::RequestApproval);
+  }: _(RawOrigin::Signed(owner), id.clone(), Visibility::Unlisted, JoinPolicy::Open)
+  verify {
+    assert_eq!(Groups::<T>::get(&id).unwrap().join_policy, JoinPolicy::Open);
+  }
+
+  join_group {
+    let owner = member::<T>("owner", 0);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::Open);
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+  }: _(RawOrigin::Signed(who.clone()), id.clone())
+  verify {
+    assert_eq!(SocialNetwork::<T>::access_control_of(&id, &who), Some(AccessControl::Customer));
+  }
+
+  request_join {
+    let owner = member::<T>("owner", 0);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::RequestApproval);
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    full_lapses::<T>();
+  }: _(RawOrigin::Signed(who.clone()), id.clone())
+  verify {
+    assert!(JoinRequests::<T>::contains_key(&id, &who));
+  }
+
+  cancel_join_request {
+    let owner = member::<T>("owner", 0);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::RequestApproval);
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    SocialNetwork::<T>::do_request_join(&who, &id)?;
+  }: _(RawOrigin::Signed(who.clone()), id.clone())
+  verify {
+    assert!(!JoinRequests::<T>::contains_key(&id, &who));
+  }
+
+  approve_join {
+    let owner: T::AccountId = whitelisted_caller();
+    live::<T>(&owner, vec![Role::Patient]);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::RequestApproval);
+    let who = member::<T>("who", 0);
+    SocialNetwork::<T>::do_request_join(&who, &id)?;
+  }: _(RawOrigin::Signed(owner), id.clone(), who.clone(), AccessControl::ReadWrite)
+  verify {
+    assert_eq!(SocialNetwork::<T>::access_control_of(&id, &who), Some(AccessControl::ReadWrite));
+  }
+
+  reject_join {
+    let owner: T::AccountId = whitelisted_caller();
+    live::<T>(&owner, vec![Role::Patient]);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::RequestApproval);
+    let who = member::<T>("who", 0);
+    SocialNetwork::<T>::do_request_join(&who, &id)?;
+  }: _(RawOrigin::Signed(owner), id.clone(), who.clone())
+  verify {
+    assert!(!JoinRequests::<T>::contains_key(&id, &who));
+  }
+
+  invite {
+    let owner: T::AccountId = whitelisted_caller();
+    live::<T>(&owner, vec![Role::Patient]);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::InviteOnly);
+    let who = member::<T>("who", 0);
+    full_lapses::<T>();
+  }: _(RawOrigin::Signed(owner), id.clone(), who.clone(), AccessControl::ReadOnly)
+  verify {
+    assert!(Invitations::<T>::contains_key(&id, &who));
+  }
+
+  revoke_invitation {
+    let owner: T::AccountId = whitelisted_caller();
+    live::<T>(&owner, vec![Role::Patient]);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::InviteOnly);
+    let who = member::<T>("who", 0);
+    SocialNetwork::<T>::do_invite(&owner, &who, &id, &AccessControl::ReadOnly)?;
+  }: _(RawOrigin::Signed(owner), id.clone(), who.clone())
+  verify {
+    assert!(!Invitations::<T>::contains_key(&id, &who));
+  }
+
+  accept_invitation {
+    let owner = member::<T>("owner", 0);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::InviteOnly);
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    SocialNetwork::<T>::do_invite(&owner, &who, &id, &AccessControl::ReadOnly)?;
+  }: _(RawOrigin::Signed(who.clone()), id.clone())
+  verify {
+    assert_eq!(SocialNetwork::<T>::access_control_of(&id, &who), Some(AccessControl::ReadOnly));
+  }
+
+  decline_invitation {
+    let owner = member::<T>("owner", 0);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::InviteOnly);
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    SocialNetwork::<T>::do_invite(&owner, &who, &id, &AccessControl::ReadOnly)?;
+  }: _(RawOrigin::Signed(who.clone()), id.clone())
+  verify {
+    assert!(!Invitations::<T>::contains_key(&id, &who));
+  }
+
+  remove_member {
+    let owner: T::AccountId = whitelisted_caller();
+    live::<T>(&owner, vec![Role::Patient]);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::RequestApproval);
+    let who = member::<T>("who", 0);
+    joined::<T>(&owner, &who, &id, AccessControl::ReadWrite)?;
+  }: _(RawOrigin::Signed(owner), id.clone(), who.clone())
+  verify {
+    assert_eq!(SocialNetwork::<T>::access_control_of(&id, &who), None);
+  }
+
+  leave_group {
+    let owner = member::<T>("owner", 0);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::RequestApproval);
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    joined::<T>(&owner, &who, &id, AccessControl::ReadWrite)?;
+  }: _(RawOrigin::Signed(who.clone()), id.clone())
+  verify {
+    assert_eq!(SocialNetwork::<T>::access_control_of(&id, &who), None);
+  }
+
+  change_access_control {
+    let owner: T::AccountId = whitelisted_caller();
+    live::<T>(&owner, vec![Role::Patient]);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::RequestApproval);
+    let who = member::<T>("who", 0);
+    joined::<T>(&owner, &who, &id, AccessControl::ReadOnly)?;
+  }: _(RawOrigin::Signed(owner), id.clone(), who.clone(), AccessControl::Admin)
+  verify {
+    assert_eq!(SocialNetwork::<T>::access_control_of(&id, &who), Some(AccessControl::Admin));
+  }
+
+  transfer_group_ownership {
+    let owner: T::AccountId = whitelisted_caller();
+    live::<T>(&owner, vec![Role::Patient]);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::RequestApproval);
+    let new_owner = member::<T>("new_owner", 0);
+    joined::<T>(&owner, &new_owner, &id, AccessControl::ReadWrite)?;
+  }: _(RawOrigin::Signed(owner), id.clone(), new_owner.clone())
+  verify {
+    assert_eq!(Groups::<T>::get(&id).unwrap().owner, new_owner);
+  }
+
+  dissolve_group {
+    let g in 1 .. MAX_SUB_GROUPS as u32;
+
+    let owner: T::AccountId = whitelisted_caller();
+    live::<T>(&owner, vec![Role::Patient]);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::RequestApproval);
+    for i in 0 .. g {
+      let sub_group = group::<T>(&owner, &[b's', i as u8], Some(id.clone()), JoinPolicy::RequestApproval);
+      joined::<T>(&owner, &member::<T>("member", i), &sub_group, AccessControl::ReadWrite)?;
     }
+  }: _(RawOrigin::Signed(owner), id.clone())
+  verify {
+    assert!(!Groups::<T>::contains_key(&id));
+  }
+
+  add_guardian {
+    let ward: T::AccountId = whitelisted_caller();
+    live::<T>(&ward, vec![Role::Patient]);
+    let guardian = member::<T>("guardian", 0);
+    let expiry = frame_system::Pallet::<T>::block_number() + T::MaxGuardianshipPeriod::get();
+  }: _(RawOrigin::Signed(ward.clone()), guardian.clone(), expiry)
+  verify {
+    assert!(Guardians::<T>::contains_key(&ward, &guardian));
+  }
+
+  appoint_guardian {
+    let custodian = custodian::<T>();
+    let ward = member::<T>("ward", 0);
+    let guardian = member::<T>("guardian", 0);
+    let expiry = frame_system::Pallet::<T>::block_number() + T::MaxGuardianshipPeriod::get();
+  }: _(RawOrigin::Signed(custodian), ward.clone(), guardian.clone(), expiry)
+  verify {
+    assert!(Guardians::<T>::contains_key(&ward, &guardian));
+  }
+
+  remove_guardian {
+    let ward = member::<T>("ward", 0);
+    let guardian: T::AccountId = whitelisted_caller();
+    live::<T>(&guardian, vec![Role::Patient]);
+    let expiry = frame_system::Pallet::<T>::block_number() + T::MaxGuardianshipPeriod::get();
+    SocialNetwork::<T>::establish_guardianship(&ward, &ward, &guardian, &expiry)?;
+  }: _(RawOrigin::Signed(guardian.clone()), ward.clone(), guardian.clone())
+  verify {
+    assert!(!Guardians::<T>::contains_key(&ward, &guardian));
+  }
+
+  act_for_ward {
+    let owner = member::<T>("owner", 0);
+    let id = group::<T>(&owner, b"group", None, JoinPolicy::RequestApproval);
+    let ward = member::<T>("ward", 0);
+    let guardian: T::AccountId = whitelisted_caller();
+    live::<T>(&guardian, vec![Role::Patient]);
+    let expiry = frame_system::Pallet::<T>::block_number() + T::MaxGuardianshipPeriod::get();
+    SocialNetwork::<T>::establish_guardianship(&ward, &ward, &guardian, &expiry)?;
+    SocialNetwork::<T>::do_request_join(&ward, &id)?;
+    let call: <T as Config>::Call = Call::<T>::cancel_join_request { group_id: id.clone() }.into();
+  }: _(RawOrigin::Signed(guardian), ward.clone(), Box::new(call))
+  verify {
+    assert!(!JoinRequests::<T>::contains_key(&id, &ward));
+  }
+
+  create_organization {
+    let organization: T::AccountId = whitelisted_caller();
+    live::<T>(&organization, vec![Role::HealthFacility]);
+  }: _(RawOrigin::Signed(organization.clone()), Default::default())
+  verify {
+    assert!(Organizations::<T>::contains_key(&organization));
+  }
+
+  add_organization_member {
+    let organization: T::AccountId = whitelisted_caller();
+    live::<T>(&organization, vec![Role::HealthFacility]);
+    SocialNetwork::<T>::do_create_organization(&organization, &Default::default())?;
+    let who = member::<T>("who", 0);
+  }: _(RawOrigin::Signed(organization.clone()), organization.clone(), who.clone(), OrganizationRole::Admin)
+  verify {
+    assert_eq!(OrganizationMembers::<T>::get(&organization, &who), Some(OrganizationRole::Admin));
+  }
+
+  remove_organization_member {
+    let organization: T::AccountId = whitelisted_caller();
+    live::<T>(&organization, vec![Role::HealthFacility]);
+    SocialNetwork::<T>::do_create_organization(&organization, &Default::default())?;
+    let who = member::<T>("who", 0);
+    SocialNetwork::<T>::do_add_organization_member(&organization, &organization, &who, &OrganizationRole::Admin)?;
+  }: _(RawOrigin::Signed(organization.clone()), organization.clone(), who.clone())
+  verify {
+    assert_eq!(OrganizationMembers::<T>::get(&organization, &who), None);
+  }
+
+  register_issuer {
+    let custodian = custodian::<T>();
+    let issuer: T::AccountId = account("issuer", 0, SEED);
+  }: _(RawOrigin::Signed(custodian), issuer.clone(), Country::Netherlands, Default::default())
+  verify {
+    assert!(Issuers::<T>::contains_key(&issuer));
+  }
+
+  deregister_issuer {
+    let custodian = custodian::<T>();
+    let issuer: T::AccountId = account("issuer", 0, SEED);
+    SocialNetwork::<T>::add_issuer(&custodian, &issuer, &Country::Netherlands, &Default::default())?;
+  }: _(RawOrigin::Signed(custodian), issuer.clone())
+  verify {
+    assert!(!Issuers::<T>::contains_key(&issuer));
+  }
+
+  issue_credential {
+    let custodian = custodian::<T>();
+    let issuer: T::AccountId = whitelisted_caller();
+    SocialNetwork::<T>::add_issuer(&custodian, &issuer, &Country::Netherlands, &Default::default())?;
+    let holder = member::<T>("holder", 0);
+    let expiry = frame_system::Pallet::<T>::block_number() + 100u32.into();
+  }: _(RawOrigin::Signed(issuer), holder.clone(), CredentialKind::MedicalLicense, None, expiry)
+  verify {
+    assert!(Credentials::<T>::contains_key(&holder, CredentialKind::MedicalLicense));
+  }
+
+  revoke_credential {
+    let custodian = custodian::<T>();
+    let issuer: T::AccountId = whitelisted_caller();
+    SocialNetwork::<T>::add_issuer(&custodian, &issuer, &Country::Netherlands, &Default::default())?;
+    let holder = member::<T>("holder", 0);
+    let expiry = frame_system::Pallet::<T>::block_number() + 100u32.into();
+    SocialNetwork::<T>::issue(&issuer, &holder, &CredentialKind::MedicalLicense, &None, &expiry)?;
+  }: _(RawOrigin::Signed(issuer), holder.clone(), CredentialKind::MedicalLicense)
+  verify {
+    assert!(!Credentials::<T>::contains_key(&holder, CredentialKind::MedicalLicense));
+  }
+
+  open_channel {
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    let other = member::<T>("other", 0);
+    connected::<T>(&who, &other);
+  }: _(RawOrigin::Signed(who.clone()), other.clone(), None)
+  verify {
+    let (first, second) = SocialNetwork::<T>::channel_of(&who, &other);
+    assert!(Channels::<T>::contains_key(&first, &second));
+  }
+
+  close_channel {
+    let m in 0 .. MAX_CHANNEL_MESSAGES;
+
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    let other = member::<T>("other", 0);
+    T::Keys::register_encryption_key(&other, [1u8; 32]);
+    connected::<T>(&who, &other);
+    SocialNetwork::<T>::do_open_channel(&who, &other, &None)?;
+    for _ in 0 .. m {
+      SocialNetwork::<T>::do_send_message(&who, &other, &vec![0u8; 32].try_into().unwrap())?;
+    }
+  }: _(RawOrigin::Signed(who.clone()), other.clone())
+  verify {
+    let (first, second) = SocialNetwork::<T>::channel_of(&who, &other);
+    assert!(!Channels::<T>::contains_key(&first, &second));
+  }
+
+  send_message {
+    let l in 1 .. MAX_MESSAGE_LEN;
+
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    let to = member::<T>("to", 0);
+    T::Keys::register_encryption_key(&to, [1u8; 32]);
+    connected::<T>(&who, &to);
+    SocialNetwork::<T>::do_open_channel(&who, &to, &None)?;
+    let payload: EncryptedMessage = vec![0u8; l as usize].try_into().unwrap();
+  }: _(RawOrigin::Signed(who.clone()), to.clone(), payload)
+  verify {
+    let (first, second) = SocialNetwork::<T>::channel_of(&who, &to);
+    assert_eq!(Channels::<T>::get(&first, &second).unwrap().messages, 1);
+  }
+
+  mark_read {
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    let from = member::<T>("from", 0);
+    T::Keys::register_encryption_key(&who, [1u8; 32]);
+    connected::<T>(&from, &who);
+    SocialNetwork::<T>::do_open_channel(&from, &who, &None)?;
+    SocialNetwork::<T>::do_send_message(&from, &who, &vec![0u8; 32].try_into().unwrap())?;
+  }: _(RawOrigin::Signed(who.clone()), from.clone(), 1)
+  verify {
+    let channel_id = SocialNetwork::<T>::channel_of(&who, &from);
+    assert_eq!(ReadReceipts::<T>::get(&channel_id, &who), 1);
+  }
+
+  set_recovery_contacts {
+    let c in 1 .. MAX_RECOVERY_CONTACTS;
+
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    let contacts: Vec<T::AccountId> = (0 .. c).map(|i| member::<T>("contact", i)).collect();
+    for contact in contacts.iter() {
+      connected::<T>(&who, contact);
+    }
+  }: _(RawOrigin::Signed(who.clone()), contacts, c)
+  verify {
+    assert!(RecoveryConfigs::<T>::contains_key(&who));
+  }
+
+  remove_recovery_contacts {
+    let who: T::AccountId = whitelisted_caller();
+    live::<T>(&who, vec![Role::Patient]);
+    let contact = member::<T>("contact", 0);
+    connected::<T>(&who, &contact);
+    SocialNetwork::<T>::configure_recovery(&who, &vec![contact], 1)?;
+  }: _(RawOrigin::Signed(who.clone()))
+  verify {
+    assert!(!RecoveryConfigs::<T>::contains_key(&who));
+  }
+
+  vouch_recovery {
+    let lost = member::<T>("lost", 0);
+    let new: T::AccountId = account("new", 0, SEED);
+    let contact: T::AccountId = whitelisted_caller();
+    live::<T>(&contact, vec![Role::Patient]);
+    connected::<T>(&lost, &contact);
+    SocialNetwork::<T>::configure_recovery(&lost, &vec![contact.clone()], 1)?;
+  }: _(RawOrigin::Signed(contact), lost.clone(), new.clone())
+  verify {
+    assert!(Recoveries::<T>::get(&lost, &new).unwrap().claimable_at.is_some());
+  }
+
+  cancel_recovery {
+    let lost: T::AccountId = whitelisted_caller();
+    live::<T>(&lost, vec![Role::Patient]);
+    let new: T::AccountId = account("new", 0, SEED);
+    recovering::<T>(&lost, &new);
+  }: _(RawOrigin::Signed(lost.clone()))
+  verify {
+    assert!(!Recoveries::<T>::contains_key(&lost, &new));
+  }
+
+  claim_recovery {
+    let lost = member::<T>("lost", 0);
+    let new: T::AccountId = whitelisted_caller();
+    T::Currency::make_free_balance_be(&new, BalanceOf::<T>::max_value() / 2u32.into());
+    recovering::<T>(&lost, &new);
+    let owned = group::<T>(&lost, b"group", None, JoinPolicy::RequestApproval);
+    frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + T::RecoveryDelay::get());
+  }: _(RawOrigin::Signed(new.clone()), lost.clone())
+  verify {
+    assert!(Accounts::<T>::contains_key(&new));
+    assert_eq!(Groups::<T>::get(&owned).unwrap().owner, new);
+  }
 }
 
-impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+impl_benchmark_test_suite!(SocialNetwork, crate::mock::new_test_ext(), crate::mock::Test);
@@ -1,5 +1,26 @@
 pub const GROUP_INFO_MAX_LEN: u32 = 128;
 pub const USER_INFO_MAX_LEN: u32 = 128;
+pub const ISSUER_INFO_MAX_LEN: u32 = 128;
 pub const GROUP_OID_MAX_LEN: u32 = 64;
 pub const MAX_CUSTODIANS: usize = 8;
+pub const MAX_GUARDIANS: usize = 4;
+/// The number of `CredentialKind`s, and so the most credentials an account holds.
+pub const MAX_CREDENTIALS: u32 = 5;
+pub const MAX_ROLES: u32 = 4;
+pub const MAX_RECOVERY_CONTACTS: u32 = 8;
+pub const MAX_GROUP_DEPTH: usize = 4;
+pub const MAX_SUB_GROUPS: usize = 16;
+pub const MAX_CASCADE_DELETE: usize = 32;
+/// The most join requests and invitations that can lapse at the same block.
+pub const MAX_EXPIRIES_PER_BLOCK: u32 = 50;
+/// The most blocks the removal of a lapsed join request or invitation is pushed back by when the
+/// block it lapses at is full. Those that cannot be scheduled are only ignored until touched again.
+pub const MAX_LAPSE_DELAY: u32 = 10;
+/// The most entries a paginated query returns at once.
+pub const MAX_PAGE_SIZE: u32 = 100;
 pub const EDN_MAX_LEN: u32 = 512;
+pub const MAX_MESSAGE_LEN: u32 = 4096;
+/// The most messages a channel holds, so that closing it removes a bounded number of entries.
+pub const MAX_CHANNEL_MESSAGES: u32 = 500;
+/// The offchain indexing key prefix encrypted messages are stored under.
+pub const MESSAGE_INDEX_PREFIX: &[u8] = b"social-network::message";
@@ -1,10 +1,26 @@
 use super::pallet::*;
 use crate::{
-  constants::MAX_CUSTODIANS,
-  types::{AccessControl, AccountStatus, Group, GroupId, GroupInfo, Relation},
+  constants::{
+    MAX_CASCADE_DELETE, MAX_CHANNEL_MESSAGES, MAX_CREDENTIALS, MAX_CUSTODIANS, MAX_GROUP_DEPTH, MAX_GUARDIANS, MAX_LAPSE_DELAY, MAX_PAGE_SIZE,
+    MAX_RECOVERY_CONTACTS, MAX_SUB_GROUPS, MESSAGE_INDEX_PREFIX,
+  },
+  traits::{CredentialProvider, CustodianProvider, GroupMembership, OnAccountRecovered, OrganizationProvider, RoleProvider},
+  types::{
+    AccessControl, AccountDetail, AccountInfo, AccountStatus, Channel, ConnectionInfo, Credential, CredentialKind, Deposit, EncryptedMessage, Group,
+    GroupId, GroupInfo, Guardianship, Invitation, Issuer, IssuerInfo, JoinPolicy, Message, Organization, OrganizationRole, Recovery, RecoveryConfig,
+    Relation, RoleChange, Roles, Visibility,
+  },
 };
-use frame_support::ensure;
-use region::{Country, Region, SubRegion};
+use codec::Encode;
+use frame_support::{
+  ensure,
+  traits::{BalanceStatus, Currency, Get, ReservableCurrency},
+};
+use healthcare::{Role, TherapeuticArea};
+use pallet_key_registry::traits::KeyProvider;
+use region::{region_of, sub_region_of, Country};
+use sp_runtime::traits::{Hash, One, Saturating, Zero};
+use sp_std::vec::Vec;
 
 impl<T: Config> Pallet<T> {
   pub fn add_custodian(custodian_id: &T::AccountId) -> Result<(), Error<T>> {
@@ -18,6 +34,8 @@ impl<T: Config> Pallet<T> {
     custodians.push(custodian_id.clone());
     Custodians::<T>::put(custodians);
 
+    Self::deposit_event(Event::CustodianAdded(custodian_id.clone()));
+
     Ok(())
   }
 
@@ -32,6 +50,173 @@ impl<T: Config> Pallet<T> {
     custodians.remove(index.unwrap());
     Custodians::<T>::put(custodians);
 
+    Self::deposit_event(Event::CustodianRemoved(custodian_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn onboard(
+    custodian_id: &T::AccountId,
+    who_id: &T::AccountId,
+    roles: &Roles,
+    enterprise_id: &Option<T::AccountId>,
+    info: &AccountInfo,
+  ) -> Result<(), Error<T>> {
+    Self::ensure_custodian(custodian_id)?;
+    ensure!(!Accounts::<T>::contains_key(who_id), Error::<T>::AccountAlreadyExisted);
+    Self::ensure_valid_roles(roles, enterprise_id)?;
+    Self::hold_deposit(custodian_id, Deposit::Account(who_id.clone()), T::AccountDeposit::get())?;
+
+    Accounts::<T>::insert(
+      who_id,
+      AccountDetail {
+        issuer: custodian_id.clone(),
+        freezer: None,
+        status: AccountStatus::Live,
+        roles: roles.clone(),
+        enterprise: enterprise_id.clone(),
+        info: info.clone(),
+      },
+    );
+    if let Some(enterprise_id) = enterprise_id {
+      Employees::<T>::insert(enterprise_id, who_id, ());
+    }
+
+    Self::deposit_event(Event::AccountOnboarded(who_id.clone(), custodian_id.clone()));
+
+    Ok(())
+  }
+
+  /// Remove a live account that is no longer part of any group, connection, organization or
+  /// channel, nor the guardian of anyone, and refund the deposit of the custodian that onboarded
+  /// it. Its guardians and credentials go with it.
+  pub fn offboard(who_id: &T::AccountId) -> Result<(), Error<T>> {
+    let maybe_who = Accounts::<T>::get(who_id);
+
+    Self::ensure_live(who_id)?;
+    ensure!(MemberGroups::<T>::iter_prefix(who_id).next().is_none(), Error::<T>::AccountInUse);
+    ensure!(Connections::<T>::iter_prefix(who_id).next().is_none(), Error::<T>::AccountInUse);
+    ensure!(IncomingConnections::<T>::iter_prefix(who_id).next().is_none(), Error::<T>::AccountInUse);
+    ensure!(Wards::<T>::iter_prefix(who_id).next().is_none(), Error::<T>::AccountInUse);
+    ensure!(MemberOrganizations::<T>::iter_prefix(who_id).next().is_none(), Error::<T>::AccountInUse);
+    ensure!(
+      Organizations::<T>::get(who_id).map_or(true, |organization| organization.members == 0),
+      Error::<T>::AccountInUse
+    );
+    ensure!(Channels::<T>::iter_prefix(who_id).next().is_none(), Error::<T>::AccountInUse);
+    ensure!(ReverseChannels::<T>::iter_prefix(who_id).next().is_none(), Error::<T>::AccountInUse);
+    ensure!(Recoveries::<T>::iter_prefix(who_id).next().is_none(), Error::<T>::RecoveryAlreadyStarted);
+
+    let who = maybe_who.unwrap();
+    if let Some(enterprise_id) = &who.enterprise {
+      Employees::<T>::remove(enterprise_id, who_id);
+    }
+    Accounts::<T>::remove(who_id);
+    RoleChangeRequests::<T>::remove(who_id);
+    RecoveryConfigs::<T>::remove(who_id);
+    Organizations::<T>::remove(who_id);
+    for (guardian_id, _) in Guardians::<T>::drain_prefix(who_id) {
+      Wards::<T>::remove(&guardian_id, who_id);
+    }
+    Credentials::<T>::remove_prefix(who_id, Some(MAX_CREDENTIALS));
+    Self::release_deposit(&who.issuer, Deposit::Account(who_id.clone()));
+
+    Self::deposit_event(Event::AccountClosed(who_id.clone()));
+
+    Ok(())
+  }
+
+  /// Freeze a live account, which stops it from doing any activity in the network.
+  pub fn freeze(custodian_id: &T::AccountId, who_id: &T::AccountId) -> Result<(), Error<T>> {
+    Self::ensure_custodian(custodian_id)?;
+    Self::ensure_live(who_id)?;
+
+    Accounts::<T>::mutate(who_id, |maybe_who| {
+      if let Some(who) = maybe_who {
+        who.status = AccountStatus::Frozen;
+        who.freezer = Some(custodian_id.clone());
+      }
+    });
+
+    Self::deposit_event(Event::AccountFrozen(who_id.clone(), custodian_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn unfreeze(custodian_id: &T::AccountId, who_id: &T::AccountId) -> Result<(), Error<T>> {
+    let maybe_who = Accounts::<T>::get(who_id);
+
+    Self::ensure_custodian(custodian_id)?;
+    ensure!(maybe_who.is_some(), Error::<T>::AccountNotExisted);
+    ensure!(maybe_who.unwrap().status == AccountStatus::Frozen, Error::<T>::AccountNotFrozen);
+
+    Accounts::<T>::mutate(who_id, |maybe_who| {
+      if let Some(who) = maybe_who {
+        who.status = AccountStatus::Live;
+        who.freezer = None;
+      }
+    });
+
+    Self::deposit_event(Event::AccountUnfrozen(who_id.clone(), custodian_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn do_request_role_change(who_id: &T::AccountId, roles: &Roles, enterprise_id: &Option<T::AccountId>) -> Result<(), Error<T>> {
+    Self::ensure_live(who_id)?;
+    Self::ensure_valid_roles(roles, enterprise_id)?;
+
+    RoleChangeRequests::<T>::insert(
+      who_id,
+      RoleChange {
+        roles: roles.clone(),
+        enterprise: enterprise_id.clone(),
+      },
+    );
+
+    Self::deposit_event(Event::RoleChangeRequested(who_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn do_approve_role_change(custodian_id: &T::AccountId, who_id: &T::AccountId) -> Result<(), Error<T>> {
+    let maybe_change = RoleChangeRequests::<T>::get(who_id);
+
+    Self::ensure_custodian(custodian_id)?;
+    ensure!(maybe_change.is_some(), Error::<T>::NeverRequestedRoleChange);
+    ensure!(Accounts::<T>::contains_key(who_id), Error::<T>::AccountNotExisted);
+
+    // The linked enterprise may have changed its roles since the request was made.
+    let change = maybe_change.unwrap();
+    Self::ensure_valid_roles(&change.roles, &change.enterprise)?;
+
+    RoleChangeRequests::<T>::remove(who_id);
+    Accounts::<T>::mutate(who_id, |maybe_who| {
+      if let Some(who) = maybe_who {
+        if let Some(enterprise_id) = &who.enterprise {
+          Employees::<T>::remove(enterprise_id, who_id);
+        }
+        if let Some(enterprise_id) = &change.enterprise {
+          Employees::<T>::insert(enterprise_id, who_id, ());
+        }
+        who.roles = change.roles;
+        who.enterprise = change.enterprise;
+      }
+    });
+
+    Self::deposit_event(Event::RoleChangeApproved(who_id.clone(), custodian_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn do_reject_role_change(custodian_id: &T::AccountId, who_id: &T::AccountId) -> Result<(), Error<T>> {
+    Self::ensure_custodian(custodian_id)?;
+    ensure!(RoleChangeRequests::<T>::contains_key(who_id), Error::<T>::NeverRequestedRoleChange);
+
+    RoleChangeRequests::<T>::remove(who_id);
+
+    Self::deposit_event(Event::RoleChangeRejected(who_id.clone(), custodian_id.clone()));
+
     Ok(())
   }
 
@@ -41,8 +226,12 @@ impl<T: Config> Pallet<T> {
     ensure!(maybe_to.is_some(), Error::<T>::AccountNotExisted);
     ensure!(maybe_to.unwrap().status == AccountStatus::Live, Error::<T>::AccountNotLive);
     ensure!(!Connections::<T>::contains_key(from_id, to_id), Error::<T>::AlreadyConnecting);
+    Self::hold_deposit(from_id, Deposit::Connection(from_id.clone(), to_id.clone()), T::ConnectionDeposit::get())?;
 
     Connections::<T>::insert(from_id, to_id, Relation::Pending);
+    IncomingConnections::<T>::insert(to_id, from_id, ());
+
+    Self::deposit_event(Event::ConnectionRequested(from_id.clone(), to_id.clone()));
 
     Ok(())
   }
@@ -56,6 +245,8 @@ impl<T: Config> Pallet<T> {
     Connections::<T>::remove(from_id, to_id);
     Connections::<T>::insert(from_id, to_id, Relation::Connected);
 
+    Self::deposit_event(Event::Connected(from_id.clone(), to_id.clone()));
+
     Ok(())
   }
 
@@ -63,115 +254,1438 @@ impl<T: Config> Pallet<T> {
     ensure!(Connections::<T>::contains_key(from_id, to_id), Error::<T>::NeverConnecting);
 
     Connections::<T>::remove(from_id, to_id);
+    IncomingConnections::<T>::remove(to_id, from_id);
+    Self::release_deposit(from_id, Deposit::Connection(from_id.clone(), to_id.clone()));
+
+    Self::deposit_event(Event::Disconnected(from_id.clone(), to_id.clone()));
 
     Ok(())
   }
 
-  pub fn create_group(
+  pub fn do_create_group(
     admin_id: &T::AccountId,
     group_id: &GroupId,
     group_info: &GroupInfo,
     country: &Country,
-    region: &Region,
-    sub_region: &SubRegion,
+    parent_id: &Option<GroupId>,
+    visibility: &Visibility,
+    join_policy: &JoinPolicy,
   ) -> Result<(), Error<T>> {
+    let region = region_of(country.clone());
+    let sub_region = sub_region_of(country.clone());
     let maybe_admin = Accounts::<T>::get(admin_id);
 
     ensure!(!Groups::<T>::contains_key(group_id), Error::<T>::GroupAlreadyExisted);
     ensure!(maybe_admin.is_some(), Error::<T>::AccountNotExisted);
     ensure!(maybe_admin.unwrap().status == AccountStatus::Live, Error::<T>::AccountNotLive);
+    Self::ensure_valid_policy(visibility, join_policy)?;
+    if let Some(parent_id) = parent_id {
+      ensure!(Groups::<T>::contains_key(parent_id), Error::<T>::GroupNotExisted);
+      Self::ensure_admin(admin_id, parent_id)?;
+      ensure!(Self::ancestors_of(parent_id).len() + 1 < MAX_GROUP_DEPTH, Error::<T>::TooDeepGroup);
+      ensure!(
+        SubGroups::<T>::iter_prefix(parent_id).count() < MAX_SUB_GROUPS,
+        Error::<T>::TooManySubGroups
+      );
+    }
+    Self::reserve_deposit(admin_id, T::GroupDeposit::get().saturating_add(T::MemberDeposit::get()))?;
 
+    if let Some(parent_id) = parent_id {
+      SubGroups::<T>::insert(parent_id, group_id, ());
+    }
     Groups::<T>::insert(
       group_id,
       Group {
         owner: admin_id.clone(),
+        parent: parent_id.clone(),
         info: group_info.clone(),
         country: country.clone(),
         region: region.clone(),
         sub_region: sub_region.clone(),
+        visibility: *visibility,
+        join_policy: *join_policy,
         admins: 1,
         members: 1,
       },
     );
+    GroupsByCountry::<T>::insert(country, group_id, ());
+    GroupsBySubRegion::<T>::insert(&sub_region, group_id, ());
+    GroupsByRegion::<T>::insert(&region, group_id, ());
     AccessControls::<T>::insert(group_id, admin_id, AccessControl::SuperAdmin);
+    MemberGroups::<T>::insert(admin_id, group_id, ());
+    Deposits::<T>::insert(Deposit::Group(group_id.clone()), T::GroupDeposit::get());
+    Deposits::<T>::insert(Deposit::Member(group_id.clone(), admin_id.clone()), T::MemberDeposit::get());
+
+    Self::deposit_event(Event::GroupCreated(group_id.clone(), admin_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn do_set_group_policy(
+    invoker_id: &T::AccountId,
+    group_id: &GroupId,
+    visibility: &Visibility,
+    join_policy: &JoinPolicy,
+  ) -> Result<(), Error<T>> {
+    ensure!(Groups::<T>::contains_key(group_id), Error::<T>::GroupNotExisted);
+    Self::ensure_admin(invoker_id, group_id)?;
+    Self::ensure_valid_policy(visibility, join_policy)?;
+
+    Groups::<T>::mutate(group_id, |maybe_group| {
+      if let Some(group) = maybe_group {
+        group.visibility = *visibility;
+        group.join_policy = *join_policy;
+      }
+    });
+
+    Self::deposit_event(Event::GroupPolicyChanged(group_id.clone(), *visibility, *join_policy));
 
     Ok(())
   }
 
-  pub fn join(invoker_id: &T::AccountId, who_id: &T::AccountId, group_id: &GroupId, access_control: &AccessControl) -> Result<(), Error<T>> {
+  /// Add `who_id` to a group. Admins can add anyone at any access control; everyone else can only
+  /// add themselves to an `Open` group, as a `Customer`.
+  /// Join an `Open` group as a `Customer`. Other members are let in through an invitation or an
+  /// approved join request, so that the member itself pays its deposit.
+  pub fn join(who_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    let maybe_group = Groups::<T>::get(group_id);
     let maybe_who = Accounts::<T>::get(who_id);
-    let maybe_invoker = Accounts::<T>::get(invoker_id);
-    let maybe_invoker_access = AccessControls::<T>::get(group_id, invoker_id);
 
-    ensure!(Groups::<T>::contains_key(group_id), Error::<T>::GroupNotExisted);
+    ensure!(maybe_group.is_some(), Error::<T>::GroupNotExisted);
     ensure!(maybe_who.is_some(), Error::<T>::AccountNotExisted);
     ensure!(maybe_who.unwrap().status == AccountStatus::Live, Error::<T>::AccountNotLive);
-    ensure!(maybe_invoker.is_some(), Error::<T>::AccountNotExisted);
-    ensure!(maybe_invoker.unwrap().status == AccountStatus::Live, Error::<T>::AccountNotLive);
-    ensure!(maybe_invoker_access.is_some(), Error::<T>::NeverJoining);
+    ensure!(maybe_group.unwrap().join_policy == JoinPolicy::Open, Error::<T>::GroupNotOpen);
+
+    Self::insert_member(who_id, group_id, &AccessControl::Customer)?;
+
+    Self::deposit_event(Event::MemberJoined(group_id.clone(), who_id.clone(), AccessControl::Customer));
+
+    Ok(())
+  }
+
+  pub fn disjoin(invoker_id: &T::AccountId, who_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    let maybe_group = Groups::<T>::get(group_id);
+    let maybe_who_access = AccessControls::<T>::get(group_id, who_id);
+
+    ensure!(maybe_group.is_some(), Error::<T>::GroupNotExisted);
+    ensure!(maybe_who_access.is_some(), Error::<T>::NeverJoining);
+    Self::ensure_live(who_id)?;
+    Self::ensure_admin(invoker_id, group_id)?;
+    // The owner is the one `SuperAdmin` that always stays, so the group can never lose its last one.
+    ensure!(maybe_group.unwrap().owner != *who_id, Error::<T>::LastSuperAdmin);
+    if maybe_who_access.unwrap() == AccessControl::SuperAdmin {
+      Self::ensure_super_admin(invoker_id, group_id)?;
+    }
+
+    Self::delete_member(who_id, group_id)?;
+
+    Self::deposit_event(Event::MemberLeft(group_id.clone(), who_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn leave(who_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    let maybe_group = Groups::<T>::get(group_id);
+
+    ensure!(maybe_group.is_some(), Error::<T>::GroupNotExisted);
+    ensure!(AccessControls::<T>::contains_key(group_id, who_id), Error::<T>::NeverJoining);
+    ensure!(maybe_group.unwrap().owner != *who_id, Error::<T>::LastSuperAdmin);
+
+    Self::delete_member(who_id, group_id)?;
+
+    Self::deposit_event(Event::MemberLeft(group_id.clone(), who_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn set_access_control(
+    invoker_id: &T::AccountId,
+    who_id: &T::AccountId,
+    group_id: &GroupId,
+    access_control: &AccessControl,
+  ) -> Result<(), Error<T>> {
+    let maybe_group = Groups::<T>::get(group_id);
+    let maybe_who_access = AccessControls::<T>::get(group_id, who_id);
+
+    ensure!(maybe_group.is_some(), Error::<T>::GroupNotExisted);
+    ensure!(maybe_who_access.is_some(), Error::<T>::NeverJoining);
+    Self::ensure_admin(invoker_id, group_id)?;
+    ensure!(maybe_group.unwrap().owner != *who_id, Error::<T>::LastSuperAdmin);
+
+    let who_access = maybe_who_access.unwrap();
+    if who_access == AccessControl::SuperAdmin || *access_control == AccessControl::SuperAdmin {
+      Self::ensure_super_admin(invoker_id, group_id)?;
+    }
+
+    Self::update_member(who_id, group_id, &who_access, access_control)?;
+
+    Self::deposit_event(Event::RoleChanged(group_id.clone(), who_id.clone(), *access_control));
+
+    Ok(())
+  }
+
+  pub fn transfer_ownership(invoker_id: &T::AccountId, new_owner_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    let maybe_group = Groups::<T>::get(group_id);
+    let maybe_new_owner_access = AccessControls::<T>::get(group_id, new_owner_id);
+
+    ensure!(maybe_group.is_some(), Error::<T>::GroupNotExisted);
+    ensure!(maybe_group.unwrap().owner == *invoker_id, Error::<T>::OnlyOwnerAllowed);
+    ensure!(maybe_new_owner_access.is_some(), Error::<T>::NeverJoining);
+    Self::ensure_live(invoker_id)?;
+    Self::ensure_live(new_owner_id)?;
+
+    // The new owner puts up its own group deposit before the old owner's one is refunded; both keep
+    // their member deposits.
+    Self::reserve_deposit(new_owner_id, T::GroupDeposit::get())?;
+    Self::release_deposit(invoker_id, Deposit::Group(group_id.clone()));
+    Deposits::<T>::insert(Deposit::Group(group_id.clone()), T::GroupDeposit::get());
+
+    Self::update_member(new_owner_id, group_id, &maybe_new_owner_access.unwrap(), &AccessControl::SuperAdmin)?;
+    Groups::<T>::mutate(group_id, |maybe_group| {
+      if let Some(group) = maybe_group {
+        group.owner = new_owner_id.clone();
+      }
+    });
+
+    Self::deposit_event(Event::OwnershipTransferred(group_id.clone(), invoker_id.clone(), new_owner_id.clone()));
+
+    Ok(())
+  }
+
+  /// Delete a group together with all of its sub-groups. Deep trees have to be deleted bottom-up, so
+  /// that a single call never removes more than `MAX_CASCADE_DELETE` groups.
+  pub fn delete_group(invoker_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    let maybe_group = Groups::<T>::get(group_id);
+
+    ensure!(maybe_group.is_some(), Error::<T>::GroupNotExisted);
+
+    let group = maybe_group.unwrap();
+    ensure!(group.owner == *invoker_id, Error::<T>::OnlyOwnerAllowed);
+
+    let descendants = Self::descendants_within(group_id, MAX_CASCADE_DELETE - 1).ok_or(Error::<T>::TooManySubGroups)?;
+
+    if let Some(parent_id) = group.parent {
+      SubGroups::<T>::remove(&parent_id, group_id);
+    }
+    for id in descendants.iter().chain(sp_std::iter::once(group_id)) {
+      for (member_id, _) in AccessControls::<T>::drain_prefix(id) {
+        MemberGroups::<T>::remove(&member_id, id);
+        Self::release_deposit(&member_id, Deposit::Member(id.clone(), member_id.clone()));
+      }
+      for (who_id, _) in JoinRequests::<T>::drain_prefix(id) {
+        Self::release_deposit(&who_id, Deposit::JoinRequest(id.clone(), who_id.clone()));
+      }
+      Invitations::<T>::remove_prefix(id, None);
+      SubGroups::<T>::remove_prefix(id, None);
+      if let Some(group) = Groups::<T>::take(id) {
+        Self::release_deposit(&group.owner, Deposit::Group(id.clone()));
+        GroupsByCountry::<T>::remove(&group.country, id);
+        GroupsBySubRegion::<T>::remove(&group.sub_region, id);
+        GroupsByRegion::<T>::remove(&group.region, id);
+      }
+    }
+
+    Self::deposit_event(Event::GroupDeleted(group_id.clone(), invoker_id.clone()));
+
+    Ok(())
+  }
+
+  /// The access control `who_id` has on a group. A direct membership always wins; otherwise the
+  /// nearest ancestor `who_id` is a member of decides, and only its `SuperAdmin`s and `Admin`s are
+  /// inherited, as `Admin`s of the sub-group.
+  pub fn access_control_of(group_id: &GroupId, who_id: &T::AccountId) -> Option<AccessControl> {
+    if let Some(access_control) = AccessControls::<T>::get(group_id, who_id) {
+      return Some(access_control);
+    }
+
+    for ancestor_id in Self::ancestors_of(group_id) {
+      match AccessControls::<T>::get(&ancestor_id, who_id) {
+        Some(AccessControl::SuperAdmin) | Some(AccessControl::Admin) => return Some(AccessControl::Admin),
+        Some(_) => return None,
+        None => continue,
+      }
+    }
+
+    None
+  }
+
+  /// The ancestors of a group, nearest first.
+  pub fn ancestors_of(group_id: &GroupId) -> Vec<GroupId> {
+    let mut ancestors = Vec::new();
+    let mut maybe_parent = Groups::<T>::get(group_id).and_then(|group| group.parent);
+
+    while let Some(parent_id) = maybe_parent {
+      if ancestors.len() >= MAX_GROUP_DEPTH {
+        break;
+      }
+      maybe_parent = Groups::<T>::get(&parent_id).and_then(|group| group.parent);
+      ancestors.push(parent_id);
+    }
+
+    ancestors
+  }
+
+  /// The sub-groups of a group at any depth, breadth first.
+  pub fn descendants_of(group_id: &GroupId) -> Vec<GroupId> {
+    Self::descendants_within(group_id, usize::MAX).unwrap_or_default()
+  }
+
+  /// The sub-groups of a group at any depth, breadth first, or `None` as soon as there are more
+  /// than `limit` of them, without walking the rest of the tree.
+  fn descendants_within(group_id: &GroupId, limit: usize) -> Option<Vec<GroupId>> {
+    let mut descendants: Vec<GroupId> = Vec::new();
+    let mut parent_id = group_id.clone();
+    let mut index = 0;
+
+    loop {
+      for (id, _) in SubGroups::<T>::iter_prefix(&parent_id) {
+        if descendants.len() >= limit {
+          return None;
+        }
+        descendants.push(id);
+      }
+      match descendants.get(index) {
+        Some(id) => parent_id = id.clone(),
+        None => return Some(descendants),
+      }
+      index += 1;
+    }
+  }
+
+  pub fn do_request_join(who_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_group = Groups::<T>::get(group_id);
+
+    ensure!(maybe_group.is_some(), Error::<T>::GroupNotExisted);
+    ensure!(maybe_group.unwrap().join_policy != JoinPolicy::InviteOnly, Error::<T>::InvitationRequired);
+    Self::ensure_live(who_id)?;
+    ensure!(!AccessControls::<T>::contains_key(group_id, who_id), Error::<T>::AlreadyJoined);
     ensure!(
-      [AccessControl::SuperAdmin, AccessControl::Admin].contains(&maybe_invoker_access.unwrap()),
-      Error::<T>::OnlyAdminAllowed
+      JoinRequests::<T>::get(group_id, who_id).map_or(true, |expiry| expiry < now),
+      Error::<T>::AlreadyJoining
+    );
+    ensure!(
+      Invitations::<T>::get(group_id, who_id).map_or(true, |invitation| invitation.expiry < now),
+      Error::<T>::AlreadyInvited
     );
 
-    Groups::<T>::try_mutate(group_id, |maybe_group| -> Result<(), Error<T>> {
-      let mut group = maybe_group.take().ok_or(Error::<T>::GroupNotExisted)?;
+    // The deposit of a lapsed request is refunded only once the new one is reserved.
+    let deposit = T::JoinRequestDeposit::get();
+    Self::reserve_deposit(who_id, deposit)?;
+    Self::release_deposit(who_id, Deposit::JoinRequest(group_id.clone(), who_id.clone()));
+    Deposits::<T>::insert(Deposit::JoinRequest(group_id.clone(), who_id.clone()), deposit);
 
-      let members = group.members.checked_add(1).ok_or(Error::<T>::Overflow)?;
-      group.members = members;
+    let expiry = now.saturating_add(T::MembershipExpiry::get());
+    Self::schedule_lapse(group_id, who_id, &expiry);
+    JoinRequests::<T>::insert(group_id, who_id, expiry);
 
-      if [AccessControl::SuperAdmin, AccessControl::Admin].contains(access_control) {
-        let admins = group.admins.checked_add(1).ok_or(Error::<T>::Overflow)?;
+    Self::deposit_event(Event::JoinRequested(group_id.clone(), who_id.clone()));
 
-        group.admins = admins;
-      }
+    Ok(())
+  }
 
-      *maybe_group = Some(group);
+  pub fn do_cancel_join_request(who_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    ensure!(JoinRequests::<T>::contains_key(group_id, who_id), Error::<T>::NeverJoining);
 
-      Ok(())
-    })?;
+    Self::remove_join_request(group_id, who_id);
 
-    AccessControls::<T>::insert(group_id, who_id, access_control);
+    Self::deposit_event(Event::JoinRequestCancelled(group_id.clone(), who_id.clone()));
 
     Ok(())
   }
 
-  pub fn disjoin(invoker_id: &T::AccountId, who_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
-    let maybe_who = Accounts::<T>::get(who_id);
-    let maybe_who_access = AccessControls::<T>::get(group_id, who_id);
-    let maybe_invoker = Accounts::<T>::get(invoker_id);
-    let maybe_invoker_access = AccessControls::<T>::get(group_id, invoker_id);
+  pub fn do_approve_join(
+    invoker_id: &T::AccountId,
+    who_id: &T::AccountId,
+    group_id: &GroupId,
+    access_control: &AccessControl,
+  ) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_expiry = JoinRequests::<T>::get(group_id, who_id);
 
     ensure!(Groups::<T>::contains_key(group_id), Error::<T>::GroupNotExisted);
-    ensure!(AccessControls::<T>::contains_key(group_id, who_id), Error::<T>::NeverJoining);
-    ensure!(maybe_who.is_some(), Error::<T>::AccountNotExisted);
-    ensure!(maybe_who.unwrap().status == AccountStatus::Live, Error::<T>::AccountNotLive);
-    ensure!(maybe_invoker.is_some(), Error::<T>::AccountNotExisted);
-    ensure!(maybe_invoker.unwrap().status == AccountStatus::Live, Error::<T>::AccountNotLive);
-    ensure!(maybe_invoker_access.is_some(), Error::<T>::NeverJoining);
+    Self::ensure_admin(invoker_id, group_id)?;
+    if *access_control == AccessControl::SuperAdmin {
+      Self::ensure_super_admin(invoker_id, group_id)?;
+    }
+    ensure!(maybe_expiry.is_some(), Error::<T>::NeverJoining);
+    ensure!(maybe_expiry.unwrap() >= now, Error::<T>::MembershipExpired);
+    Self::ensure_live(who_id)?;
+
+    Self::insert_member(who_id, group_id, access_control)?;
+    Self::remove_join_request(group_id, who_id);
+
+    Self::deposit_event(Event::JoinApproved(group_id.clone(), who_id.clone(), invoker_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn do_reject_join(invoker_id: &T::AccountId, who_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    Self::ensure_admin(invoker_id, group_id)?;
+    ensure!(JoinRequests::<T>::contains_key(group_id, who_id), Error::<T>::NeverJoining);
+
+    Self::remove_join_request(group_id, who_id);
+
+    Self::deposit_event(Event::JoinRejected(group_id.clone(), who_id.clone(), invoker_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn do_invite(invoker_id: &T::AccountId, who_id: &T::AccountId, group_id: &GroupId, access_control: &AccessControl) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+
+    ensure!(Groups::<T>::contains_key(group_id), Error::<T>::GroupNotExisted);
+    Self::ensure_admin(invoker_id, group_id)?;
+    if *access_control == AccessControl::SuperAdmin {
+      Self::ensure_super_admin(invoker_id, group_id)?;
+    }
+    Self::ensure_live(who_id)?;
+    ensure!(!AccessControls::<T>::contains_key(group_id, who_id), Error::<T>::AlreadyJoined);
     ensure!(
-      [AccessControl::SuperAdmin, AccessControl::Admin].contains(&maybe_invoker_access.unwrap()),
-      Error::<T>::OnlyAdminAllowed
+      Invitations::<T>::get(group_id, who_id).map_or(true, |invitation| invitation.expiry < now),
+      Error::<T>::AlreadyInvited
+    );
+    ensure!(
+      JoinRequests::<T>::get(group_id, who_id).map_or(true, |expiry| expiry < now),
+      Error::<T>::AlreadyJoining
     );
 
-    Groups::<T>::try_mutate(group_id, |maybe_group| -> Result<(), Error<T>> {
-      let mut group = maybe_group.take().ok_or(Error::<T>::GroupNotExisted)?;
+    let expiry = now.saturating_add(T::MembershipExpiry::get());
+    Self::schedule_lapse(group_id, who_id, &expiry);
+    Invitations::<T>::insert(
+      group_id,
+      who_id,
+      Invitation {
+        inviter: invoker_id.clone(),
+        access_control: *access_control,
+        expiry,
+      },
+    );
 
-      let members = group.members.checked_sub(1).unwrap_or(0);
-      group.members = members;
+    Self::deposit_event(Event::MemberInvited(group_id.clone(), who_id.clone(), invoker_id.clone()));
 
-      if [AccessControl::SuperAdmin, AccessControl::Admin].contains(&maybe_who_access.unwrap()) {
-        let admins = group.admins.checked_sub(1).unwrap_or(0);
+    Ok(())
+  }
 
-        group.admins = admins;
+  pub fn do_revoke_invitation(invoker_id: &T::AccountId, who_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    Self::ensure_admin(invoker_id, group_id)?;
+    ensure!(Invitations::<T>::contains_key(group_id, who_id), Error::<T>::NeverInvited);
+
+    Invitations::<T>::remove(group_id, who_id);
+
+    Self::deposit_event(Event::InvitationRevoked(group_id.clone(), who_id.clone(), invoker_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn do_accept_invitation(who_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_invitation = Invitations::<T>::get(group_id, who_id);
+
+    ensure!(Groups::<T>::contains_key(group_id), Error::<T>::GroupNotExisted);
+    ensure!(maybe_invitation.is_some(), Error::<T>::NeverInvited);
+
+    let invitation = maybe_invitation.unwrap();
+    ensure!(invitation.expiry >= now, Error::<T>::MembershipExpired);
+    Self::ensure_live(who_id)?;
+
+    Self::insert_member(who_id, group_id, &invitation.access_control)?;
+    Invitations::<T>::remove(group_id, who_id);
+
+    Self::deposit_event(Event::InvitationAccepted(group_id.clone(), who_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn do_decline_invitation(who_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    ensure!(Invitations::<T>::contains_key(group_id, who_id), Error::<T>::NeverInvited);
+
+    Invitations::<T>::remove(group_id, who_id);
+
+    Self::deposit_event(Event::InvitationDeclined(group_id.clone(), who_id.clone()));
+
+    Ok(())
+  }
+
+  /// Remove the join request and the invitation of `who_id` to the group if they lapsed before `now`.
+  /// Those renewed since they were scheduled are left alone.
+  pub fn remove_lapsed_membership(group_id: &GroupId, who_id: &T::AccountId, now: &T::BlockNumber) {
+    if JoinRequests::<T>::get(group_id, who_id).map_or(false, |expiry| expiry < *now) {
+      Self::remove_join_request(group_id, who_id);
+    }
+    if Invitations::<T>::get(group_id, who_id).map_or(false, |invitation| invitation.expiry < *now) {
+      Invitations::<T>::remove(group_id, who_id);
+    }
+  }
+
+  fn remove_join_request(group_id: &GroupId, who_id: &T::AccountId) {
+    JoinRequests::<T>::remove(group_id, who_id);
+    Self::release_deposit(who_id, Deposit::JoinRequest(group_id.clone(), who_id.clone()));
+  }
+
+  /// Schedule the removal of a join request or an invitation at the block after its `expiry`, the last
+  /// one at which it can be accepted, or at the first block after it that is not full. Every check
+  /// compares the expiry with the current block, so one left unscheduled is ignored all the same,
+  /// and removed when renewed, cancelled or declined.
+  fn schedule_lapse(group_id: &GroupId, who_id: &T::AccountId, expiry: &T::BlockNumber) {
+    let mut at = expiry.saturating_add(One::one());
+
+    for _ in 0..=MAX_LAPSE_DELAY {
+      let scheduled = MembershipExpiries::<T>::try_mutate(at, |pending| pending.try_push((group_id.clone(), who_id.clone()))).is_ok();
+      if scheduled {
+        return;
       }
+      at = at.saturating_add(One::one());
+    }
+  }
 
-      *maybe_group = Some(group);
+  pub fn establish_guardianship(
+    appointer_id: &T::AccountId,
+    ward_id: &T::AccountId,
+    guardian_id: &T::AccountId,
+    expiry: &T::BlockNumber,
+  ) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
 
-      Ok(())
-    })?;
-    AccessControls::<T>::remove(group_id, who_id);
+    ensure!(ward_id != guardian_id, Error::<T>::AlreadyGuardian);
+    Self::ensure_live(ward_id)?;
+    Self::ensure_live(guardian_id)?;
+    ensure!(
+      *expiry > now && *expiry <= now + T::MaxGuardianshipPeriod::get(),
+      Error::<T>::InvalidExpiry
+    );
+    ensure!(!Guardians::<T>::contains_key(ward_id, guardian_id), Error::<T>::AlreadyGuardian);
+    ensure!(Guardians::<T>::iter_prefix(ward_id).count() < MAX_GUARDIANS, Error::<T>::TooManyGuardians);
+
+    Guardians::<T>::insert(
+      ward_id,
+      guardian_id,
+      Guardianship {
+        appointer: appointer_id.clone(),
+        expiry: *expiry,
+      },
+    );
+    Wards::<T>::insert(guardian_id, ward_id, ());
+
+    Self::deposit_event(Event::GuardianAdded(ward_id.clone(), guardian_id.clone(), *expiry));
+
+    Ok(())
+  }
+
+  pub fn end_guardianship(invoker_id: &T::AccountId, ward_id: &T::AccountId, guardian_id: &T::AccountId) -> Result<(), Error<T>> {
+    let maybe_guardianship = Guardians::<T>::get(ward_id, guardian_id);
+
+    ensure!(maybe_guardianship.is_some(), Error::<T>::NotGuardian);
+    ensure!(
+      invoker_id == guardian_id || maybe_guardianship.unwrap().appointer == *invoker_id,
+      Error::<T>::NotGuardian
+    );
+
+    Guardians::<T>::remove(ward_id, guardian_id);
+    Wards::<T>::remove(guardian_id, ward_id);
+
+    Self::deposit_event(Event::GuardianRemoved(ward_id.clone(), guardian_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn ensure_guardian(guardian_id: &T::AccountId, ward_id: &T::AccountId) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_guardianship = Guardians::<T>::get(ward_id, guardian_id);
+
+    ensure!(maybe_guardianship.is_some(), Error::<T>::NotGuardian);
+    ensure!(maybe_guardianship.unwrap().expiry >= now, Error::<T>::GuardianshipExpired);
+    Self::ensure_live(guardian_id)?;
+    Self::ensure_live(ward_id)?;
 
     Ok(())
   }
+
+  pub fn do_create_organization(organization_id: &T::AccountId, info: &AccountInfo) -> Result<(), Error<T>> {
+    Self::ensure_live(organization_id)?;
+    ensure!(
+      <Self as RoleProvider<T::AccountId>>::has_role(organization_id, &Role::Enterprise)
+        || <Self as RoleProvider<T::AccountId>>::has_role(organization_id, &Role::HealthFacility),
+      Error::<T>::InvalidRoles
+    );
+    ensure!(!Organizations::<T>::contains_key(organization_id), Error::<T>::OrganizationAlreadyExisted);
+
+    Organizations::<T>::insert(
+      organization_id,
+      Organization {
+        info: info.clone(),
+        admins: 0,
+        members: 0,
+      },
+    );
+
+    Self::deposit_event(Event::OrganizationCreated(organization_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn do_add_organization_member(
+    invoker_id: &T::AccountId,
+    organization_id: &T::AccountId,
+    who_id: &T::AccountId,
+    role: &OrganizationRole,
+  ) -> Result<(), Error<T>> {
+    ensure!(Organizations::<T>::contains_key(organization_id), Error::<T>::OrganizationNotExisted);
+    Self::ensure_organization_admin(invoker_id, organization_id)?;
+    Self::ensure_live(who_id)?;
+    ensure!(who_id != organization_id, Error::<T>::AlreadyOrganizationMember);
+    ensure!(
+      !OrganizationMembers::<T>::contains_key(organization_id, who_id),
+      Error::<T>::AlreadyOrganizationMember
+    );
+
+    Organizations::<T>::try_mutate(organization_id, |maybe_organization| -> Result<(), Error<T>> {
+      let mut organization = maybe_organization.take().ok_or(Error::<T>::OrganizationNotExisted)?;
+
+      organization.members = organization.members.checked_add(1).ok_or(Error::<T>::Overflow)?;
+      if *role == OrganizationRole::Admin {
+        organization.admins = organization.admins.checked_add(1).ok_or(Error::<T>::Overflow)?;
+      }
+
+      *maybe_organization = Some(organization);
+
+      Ok(())
+    })?;
+    OrganizationMembers::<T>::insert(organization_id, who_id, role);
+    MemberOrganizations::<T>::insert(who_id, organization_id, ());
+
+    Self::deposit_event(Event::OrganizationMemberAdded(organization_id.clone(), who_id.clone(), *role));
+
+    Ok(())
+  }
+
+  pub fn do_remove_organization_member(invoker_id: &T::AccountId, organization_id: &T::AccountId, who_id: &T::AccountId) -> Result<(), Error<T>> {
+    let maybe_role = OrganizationMembers::<T>::get(organization_id, who_id);
+
+    ensure!(Organizations::<T>::contains_key(organization_id), Error::<T>::OrganizationNotExisted);
+    ensure!(maybe_role.is_some(), Error::<T>::NotOrganizationMember);
+    if invoker_id != who_id {
+      Self::ensure_organization_admin(invoker_id, organization_id)?;
+    }
+
+    Organizations::<T>::try_mutate(organization_id, |maybe_organization| -> Result<(), Error<T>> {
+      let mut organization = maybe_organization.take().ok_or(Error::<T>::OrganizationNotExisted)?;
+
+      organization.members = organization.members.checked_sub(1).unwrap_or(0);
+      if maybe_role == Some(OrganizationRole::Admin) {
+        organization.admins = organization.admins.checked_sub(1).unwrap_or(0);
+      }
+
+      *maybe_organization = Some(organization);
+
+      Ok(())
+    })?;
+    OrganizationMembers::<T>::remove(organization_id, who_id);
+    MemberOrganizations::<T>::remove(who_id, organization_id);
+
+    Self::deposit_event(Event::OrganizationMemberRemoved(organization_id.clone(), who_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn add_issuer(custodian_id: &T::AccountId, issuer_id: &T::AccountId, country: &Country, info: &IssuerInfo) -> Result<(), Error<T>> {
+    Self::ensure_custodian(custodian_id)?;
+    ensure!(!Issuers::<T>::contains_key(issuer_id), Error::<T>::IssuerAlreadyRegistered);
+
+    Issuers::<T>::insert(
+      issuer_id,
+      Issuer {
+        registrar: custodian_id.clone(),
+        country: country.clone(),
+        info: info.clone(),
+      },
+    );
+
+    Self::deposit_event(Event::IssuerRegistered(issuer_id.clone(), custodian_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn remove_issuer(custodian_id: &T::AccountId, issuer_id: &T::AccountId) -> Result<(), Error<T>> {
+    Self::ensure_custodian(custodian_id)?;
+    ensure!(Issuers::<T>::contains_key(issuer_id), Error::<T>::IssuerNotRegistered);
+
+    Issuers::<T>::remove(issuer_id);
+
+    Self::deposit_event(Event::IssuerDeregistered(issuer_id.clone(), custodian_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn issue(
+    issuer_id: &T::AccountId,
+    holder_id: &T::AccountId,
+    kind: &CredentialKind,
+    specialty: &Option<TherapeuticArea>,
+    expiry: &T::BlockNumber,
+  ) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_issuer = Issuers::<T>::get(issuer_id);
+
+    ensure!(maybe_issuer.is_some(), Error::<T>::IssuerNotRegistered);
+    ensure!(Accounts::<T>::contains_key(holder_id), Error::<T>::AccountNotExisted);
+    ensure!(*expiry > now, Error::<T>::InvalidExpiry);
+
+    Credentials::<T>::insert(
+      holder_id,
+      kind,
+      Credential {
+        issuer: issuer_id.clone(),
+        country: maybe_issuer.unwrap().country,
+        specialty: *specialty,
+        expiry: *expiry,
+      },
+    );
+
+    Self::deposit_event(Event::CredentialIssued(holder_id.clone(), *kind, issuer_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn revoke(invoker_id: &T::AccountId, holder_id: &T::AccountId, kind: &CredentialKind) -> Result<(), Error<T>> {
+    let maybe_credential = Credentials::<T>::get(holder_id, kind);
+
+    ensure!(maybe_credential.is_some(), Error::<T>::CredentialNotExisted);
+    ensure!(
+      maybe_credential.unwrap().issuer == *invoker_id || Custodians::<T>::get().contains(invoker_id),
+      Error::<T>::OnlyCustodianAllowed
+    );
+
+    Credentials::<T>::remove(holder_id, kind);
+
+    Self::deposit_event(Event::CredentialRevoked(holder_id.clone(), *kind, invoker_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn do_open_channel(opener_id: &T::AccountId, other_id: &T::AccountId, group_id: &Option<GroupId>) -> Result<(), Error<T>> {
+    let (first, second) = Self::channel_of(opener_id, other_id);
+
+    ensure!(opener_id != other_id, Error::<T>::ChannelWithSelf);
+    ensure!(!Channels::<T>::contains_key(&first, &second), Error::<T>::ChannelAlreadyExisted);
+    Self::ensure_reachable(opener_id, other_id, group_id)?;
+
+    Channels::<T>::insert(
+      &first,
+      &second,
+      Channel {
+        opener: opener_id.clone(),
+        group: group_id.clone(),
+        opened_at: <frame_system::Pallet<T>>::block_number(),
+        messages: 0,
+      },
+    );
+    ReverseChannels::<T>::insert(&second, &first, ());
+
+    Self::deposit_event(Event::ChannelOpened(opener_id.clone(), other_id.clone()));
+
+    Ok(())
+  }
+
+  pub fn do_close_channel(closer_id: &T::AccountId, other_id: &T::AccountId) -> Result<(), Error<T>> {
+    let channel_id = Self::channel_of(closer_id, other_id);
+
+    ensure!(Channels::<T>::contains_key(&channel_id.0, &channel_id.1), Error::<T>::ChannelNotExisted);
+
+    Self::remove_channel(&channel_id);
+
+    Self::deposit_event(Event::ChannelClosed(closer_id.clone(), other_id.clone()));
+
+    Ok(())
+  }
+
+  /// Record a message on chain and hand its encrypted payload to offchain indexing. The relation
+  /// the channel was opened over is checked again, so disconnecting or leaving the shared group
+  /// silences the channel.
+  pub fn do_send_message(sender_id: &T::AccountId, recipient_id: &T::AccountId, payload: &EncryptedMessage) -> Result<(), Error<T>> {
+    let channel_id = Self::channel_of(sender_id, recipient_id);
+    let maybe_channel = Channels::<T>::get(&channel_id.0, &channel_id.1);
+    let maybe_key = T::Keys::encryption_key_of(recipient_id);
+
+    ensure!(maybe_channel.is_some(), Error::<T>::ChannelNotExisted);
+
+    let mut channel = maybe_channel.unwrap();
+    Self::ensure_reachable(sender_id, recipient_id, &channel.group)?;
+    ensure!(maybe_key.is_some(), Error::<T>::EncryptionKeyNotExisted);
+
+    let index = channel.messages;
+    let hash = T::Hashing::hash(payload);
+    ensure!(index < MAX_CHANNEL_MESSAGES, Error::<T>::ChannelFull);
+    channel.messages = index.checked_add(1).ok_or(Error::<T>::Overflow)?;
+
+    sp_io::offchain_index::set(&(MESSAGE_INDEX_PREFIX, &channel_id, index).encode(), payload);
+    Messages::<T>::insert(
+      &channel_id,
+      index,
+      Message {
+        sender: sender_id.clone(),
+        hash,
+        recipient_key: maybe_key.unwrap(),
+        sent_at: <frame_system::Pallet<T>>::block_number(),
+      },
+    );
+    Channels::<T>::insert(&channel_id.0, &channel_id.1, channel);
+    SentMessages::<T>::mutate(&channel_id, sender_id, |sent| *sent = sent.saturating_add(1));
+
+    Self::deposit_event(Event::MessageSent(sender_id.clone(), recipient_id.clone(), index, hash));
+
+    Ok(())
+  }
+
+  /// Mark the first `read` messages `sender_id` sent over the channel as read. Only messages sent by
+  /// the other end count, so a reader cannot acknowledge its own.
+  pub fn do_mark_read(reader_id: &T::AccountId, sender_id: &T::AccountId, read: u32) -> Result<(), Error<T>> {
+    let channel_id = Self::channel_of(reader_id, sender_id);
+
+    ensure!(Channels::<T>::contains_key(&channel_id.0, &channel_id.1), Error::<T>::ChannelNotExisted);
+    ensure!(
+      read <= SentMessages::<T>::get(&channel_id, sender_id) && read > ReadReceipts::<T>::get(&channel_id, reader_id),
+      Error::<T>::InvalidReadReceipt
+    );
+
+    ReadReceipts::<T>::insert(&channel_id, reader_id, read);
+
+    Self::deposit_event(Event::MessagesRead(reader_id.clone(), sender_id.clone(), read));
+
+    Ok(())
+  }
+
+  /// Remove a channel together with the on-chain trace of its messages.
+  fn remove_channel(channel_id: &(T::AccountId, T::AccountId)) {
+    Channels::<T>::remove(&channel_id.0, &channel_id.1);
+    ReverseChannels::<T>::remove(&channel_id.1, &channel_id.0);
+    Messages::<T>::remove_prefix(channel_id, Some(MAX_CHANNEL_MESSAGES));
+    ReadReceipts::<T>::remove_prefix(channel_id, Some(2));
+    SentMessages::<T>::remove_prefix(channel_id, Some(2));
+  }
+
+  /// The key of the channel between two accounts, which is the same whichever end asks for it.
+  pub fn channel_of(a: &T::AccountId, b: &T::AccountId) -> (T::AccountId, T::AccountId) {
+    if a <= b {
+      (a.clone(), b.clone())
+    } else {
+      (b.clone(), a.clone())
+    }
+  }
+
+  /// Both ends have to be live, and either connected or direct members of the same group.
+  fn ensure_reachable(who_id: &T::AccountId, other_id: &T::AccountId, group_id: &Option<GroupId>) -> Result<(), Error<T>> {
+    Self::ensure_live(who_id)?;
+    Self::ensure_live(other_id)?;
+
+    match group_id {
+      Some(group_id) => {
+        let who_access = AccessControls::<T>::get(group_id, who_id);
+        let other_access = AccessControls::<T>::get(group_id, other_id);
+
+        ensure!(who_access.is_some() && other_access.is_some(), Error::<T>::NeverJoining);
+        ensure!(
+          who_access != Some(AccessControl::Customer) || other_access != Some(AccessControl::Customer),
+          Error::<T>::OnlyStaffAllowed
+        );
+      },
+      None => ensure!(Self::is_connected(who_id, other_id), Error::<T>::OnlyConnectedAllowed),
+    }
+
+    Ok(())
+  }
+
+  pub fn configure_recovery(who_id: &T::AccountId, contacts: &Vec<T::AccountId>, threshold: u32) -> Result<(), Error<T>> {
+    let custodians = Custodians::<T>::get();
+
+    Self::ensure_live(who_id)?;
+    ensure!(contacts.len() <= MAX_RECOVERY_CONTACTS as usize, Error::<T>::InvalidRecoveryContacts);
+    ensure!(threshold > 0 && threshold as usize <= contacts.len(), Error::<T>::InvalidThreshold);
+    for (index, contact_id) in contacts.iter().enumerate() {
+      ensure!(
+        contact_id != who_id && !contacts[..index].contains(contact_id),
+        Error::<T>::InvalidRecoveryContacts
+      );
+      ensure!(
+        custodians.contains(contact_id) || Self::is_connected(who_id, contact_id),
+        Error::<T>::InvalidRecoveryContacts
+      );
+    }
+
+    let contacts = contacts.clone().try_into().map_err(|_| Error::<T>::InvalidRecoveryContacts)?;
+    RecoveryConfigs::<T>::insert(who_id, RecoveryConfig { contacts, threshold });
+
+    Self::deposit_event(Event::RecoveryConfigured(who_id.clone(), threshold));
+
+    Ok(())
+  }
+
+  pub fn remove_recovery_config(who_id: &T::AccountId) -> Result<(), Error<T>> {
+    ensure!(RecoveryConfigs::<T>::contains_key(who_id), Error::<T>::RecoveryNotConfigured);
+
+    RecoveryConfigs::<T>::remove(who_id);
+    Recoveries::<T>::remove_prefix(who_id, Some(MAX_RECOVERY_CONTACTS));
+
+    Self::deposit_event(Event::RecoveryConfigRemoved(who_id.clone()));
+
+    Ok(())
+  }
+
+  /// Record that `contact_id` vouches for `new_id` as the new key of `lost_id`. Each new key is
+  /// vouched for separately, and a contact vouches for a single one at a time, so a lost account
+  /// has at most `MAX_RECOVERY_CONTACTS` recoveries in progress.
+  pub fn vouch(contact_id: &T::AccountId, lost_id: &T::AccountId, new_id: &T::AccountId) -> Result<(), Error<T>> {
+    let maybe_config = RecoveryConfigs::<T>::get(lost_id);
+
+    ensure!(maybe_config.is_some(), Error::<T>::RecoveryNotConfigured);
+
+    let config = maybe_config.unwrap();
+    ensure!(config.contacts.contains(contact_id), Error::<T>::NotRecoveryContact);
+    ensure!(Accounts::<T>::contains_key(lost_id), Error::<T>::AccountNotExisted);
+    ensure!(!Accounts::<T>::contains_key(new_id), Error::<T>::AccountAlreadyExisted);
+    ensure!(
+      !Recoveries::<T>::iter_prefix_values(lost_id).any(|recovery| recovery.vouchers.contains(contact_id)),
+      Error::<T>::AlreadyVouched
+    );
+
+    let mut recovery = Recoveries::<T>::get(lost_id, new_id).unwrap_or(Recovery {
+      vouchers: Default::default(),
+      claimable_at: None,
+    });
+
+    recovery
+      .vouchers
+      .try_push(contact_id.clone())
+      .map_err(|_| Error::<T>::InvalidRecoveryContacts)?;
+
+    Self::deposit_event(Event::RecoveryVouched(lost_id.clone(), new_id.clone(), contact_id.clone()));
+
+    if recovery.claimable_at.is_none() && recovery.vouchers.len() >= config.threshold as usize {
+      let claimable_at = <frame_system::Pallet<T>>::block_number().saturating_add(T::RecoveryDelay::get());
+
+      recovery.claimable_at = Some(claimable_at);
+
+      Self::deposit_event(Event::RecoveryApproved(lost_id.clone(), new_id.clone(), claimable_at));
+    }
+    Recoveries::<T>::insert(lost_id, new_id, recovery);
+
+    Ok(())
+  }
+
+  /// Cancel every recovery of `lost_id` in progress, whichever new key it is for.
+  pub fn do_cancel_recovery(lost_id: &T::AccountId) -> Result<(), Error<T>> {
+    ensure!(Recoveries::<T>::iter_prefix(lost_id).next().is_some(), Error::<T>::RecoveryNotStarted);
+
+    for (new_id, _) in Recoveries::<T>::drain_prefix(lost_id) {
+      Self::deposit_event(Event::RecoveryCancelled(lost_id.clone(), new_id));
+    }
+
+    Ok(())
+  }
+
+  /// Move everything the social network keys by `lost_id` over to `new_id`, then let other pallets
+  /// do the same through `OnAccountRecovered`. Recovery contacts are not carried over, and channels
+  /// are closed, since the payloads kept offchain are indexed by the channel of the lost key.
+  pub fn recover(new_id: &T::AccountId, lost_id: &T::AccountId) -> Result<(), Error<T>> {
+    let now = <frame_system::Pallet<T>>::block_number();
+    let maybe_recovery = Recoveries::<T>::get(lost_id, new_id);
+    let maybe_detail = Accounts::<T>::get(lost_id);
+
+    ensure!(maybe_recovery.is_some(), Error::<T>::RecoveryNotStarted);
+    ensure!(
+      maybe_recovery.unwrap().claimable_at.map_or(false, |at| at <= now),
+      Error::<T>::RecoveryNotReady
+    );
+    ensure!(maybe_detail.is_some(), Error::<T>::AccountNotExisted);
+    ensure!(!Accounts::<T>::contains_key(new_id), Error::<T>::AccountAlreadyExisted);
+
+    // The deposits of everything that moves stay reserved under the new key; the deposits of the
+    // connections between the two keys, which are dropped, are freed. They are repatriated before
+    // anything moves, so that a failure leaves the lost account untouched.
+    let (moved, freed) = Self::recovered_deposits(lost_id, new_id);
+    ensure!(
+      T::Currency::reserved_balance(lost_id) >= moved.saturating_add(freed),
+      Error::<T>::InsufficientDeposit
+    );
+    Self::repatriate(lost_id, new_id, moved, BalanceStatus::Reserved)?;
+    Self::repatriate(lost_id, new_id, freed, BalanceStatus::Free)?;
+
+    let detail = maybe_detail.unwrap();
+    if let Some(enterprise_id) = &detail.enterprise {
+      Employees::<T>::remove(enterprise_id, lost_id);
+      Employees::<T>::insert(enterprise_id, new_id, ());
+    }
+    Accounts::<T>::remove(lost_id);
+    Accounts::<T>::insert(new_id, detail);
+    Deposits::<T>::insert(Deposit::Account(new_id.clone()), Deposits::<T>::take(Deposit::Account(lost_id.clone())));
+    if let Some(change) = RoleChangeRequests::<T>::take(lost_id) {
+      RoleChangeRequests::<T>::insert(new_id, change);
+    }
+
+    for (to_id, relation) in Connections::<T>::drain_prefix(lost_id) {
+      let deposit = Deposits::<T>::take(Deposit::Connection(lost_id.clone(), to_id.clone()));
+
+      IncomingConnections::<T>::remove(&to_id, lost_id);
+      if to_id != *new_id {
+        Connections::<T>::insert(new_id, &to_id, relation);
+        IncomingConnections::<T>::insert(&to_id, new_id, ());
+        Deposits::<T>::insert(Deposit::Connection(new_id.clone(), to_id.clone()), deposit);
+      }
+    }
+    for (from_id, _) in IncomingConnections::<T>::drain_prefix(lost_id) {
+      if let Some(relation) = Connections::<T>::take(&from_id, lost_id) {
+        let deposit = Deposits::<T>::take(Deposit::Connection(from_id.clone(), lost_id.clone()));
+
+        if from_id != *new_id {
+          Connections::<T>::insert(&from_id, new_id, relation);
+          IncomingConnections::<T>::insert(new_id, &from_id, ());
+          Deposits::<T>::insert(Deposit::Connection(from_id.clone(), new_id.clone()), deposit);
+        } else {
+          T::Currency::unreserve(new_id, deposit);
+        }
+      }
+    }
+
+    for (group_id, _) in MemberGroups::<T>::drain_prefix(lost_id) {
+      if let Some(access_control) = AccessControls::<T>::take(&group_id, lost_id) {
+        AccessControls::<T>::insert(&group_id, new_id, access_control);
+        MemberGroups::<T>::insert(new_id, &group_id, ());
+        Deposits::<T>::insert(
+          Deposit::Member(group_id.clone(), new_id.clone()),
+          Deposits::<T>::take(Deposit::Member(group_id.clone(), lost_id.clone())),
+        );
+      }
+      Groups::<T>::mutate(&group_id, |maybe_group| {
+        if let Some(group) = maybe_group {
+          if group.owner == *lost_id {
+            group.owner = new_id.clone();
+          }
+        }
+      });
+    }
+
+    for (guardian_id, mut guardianship) in Guardians::<T>::drain_prefix(lost_id) {
+      if guardianship.appointer == *lost_id {
+        guardianship.appointer = new_id.clone();
+      }
+      Wards::<T>::remove(&guardian_id, lost_id);
+      Wards::<T>::insert(&guardian_id, new_id, ());
+      Guardians::<T>::insert(new_id, &guardian_id, guardianship);
+    }
+    for (ward_id, _) in Wards::<T>::drain_prefix(lost_id) {
+      if let Some(guardianship) = Guardians::<T>::take(&ward_id, lost_id) {
+        Guardians::<T>::insert(&ward_id, new_id, guardianship);
+      }
+      Wards::<T>::insert(new_id, &ward_id, ());
+    }
+
+    for (kind, credential) in Credentials::<T>::drain_prefix(lost_id) {
+      Credentials::<T>::insert(new_id, kind, credential);
+    }
+
+    if let Some(organization) = Organizations::<T>::take(lost_id) {
+      Organizations::<T>::insert(new_id, organization);
+    }
+    for (member_id, role) in OrganizationMembers::<T>::drain_prefix(lost_id) {
+      MemberOrganizations::<T>::remove(&member_id, lost_id);
+      MemberOrganizations::<T>::insert(&member_id, new_id, ());
+      OrganizationMembers::<T>::insert(new_id, &member_id, role);
+    }
+    for (organization_id, _) in MemberOrganizations::<T>::drain_prefix(lost_id) {
+      if let Some(role) = OrganizationMembers::<T>::take(&organization_id, lost_id) {
+        OrganizationMembers::<T>::insert(&organization_id, new_id, role);
+      }
+      MemberOrganizations::<T>::insert(new_id, &organization_id, ());
+    }
+    for (employee_id, _) in Employees::<T>::drain_prefix(lost_id) {
+      Accounts::<T>::mutate(&employee_id, |maybe_employee| {
+        if let Some(employee) = maybe_employee {
+          employee.enterprise = Some(new_id.clone());
+        }
+      });
+      Employees::<T>::insert(new_id, &employee_id, ());
+    }
+
+    let others: Vec<T::AccountId> = Channels::<T>::iter_prefix(lost_id)
+      .map(|(other_id, _)| other_id)
+      .chain(ReverseChannels::<T>::iter_prefix(lost_id).map(|(other_id, _)| other_id))
+      .collect();
+    for other_id in others {
+      Self::remove_channel(&Self::channel_of(lost_id, &other_id));
+
+      Self::deposit_event(Event::ChannelClosed(lost_id.clone(), other_id));
+    }
+
+    RecoveryConfigs::<T>::remove(lost_id);
+    Recoveries::<T>::remove_prefix(lost_id, Some(MAX_RECOVERY_CONTACTS));
+
+    T::OnAccountRecovered::on_account_recovered(lost_id, new_id);
+
+    Self::deposit_event(Event::AccountRecovered(lost_id.clone(), new_id.clone()));
+
+    Ok(())
+  }
+
+  /// The deposits `lost_id` holds for what `recover` moves to `new_id`, and for the connections
+  /// between the two keys, which `recover` drops.
+  fn recovered_deposits(lost_id: &T::AccountId, new_id: &T::AccountId) -> (BalanceOf<T>, BalanceOf<T>) {
+    let mut moved = BalanceOf::<T>::zero();
+    let mut freed = BalanceOf::<T>::zero();
+
+    for (to_id, _) in Connections::<T>::iter_prefix(lost_id) {
+      let deposit = Deposits::<T>::get(Deposit::Connection(lost_id.clone(), to_id.clone()));
+
+      if to_id != *new_id {
+        moved = moved.saturating_add(deposit);
+      } else {
+        freed = freed.saturating_add(deposit);
+      }
+    }
+    for (group_id, _) in MemberGroups::<T>::iter_prefix(lost_id) {
+      if AccessControls::<T>::contains_key(&group_id, lost_id) {
+        moved = moved.saturating_add(Deposits::<T>::get(Deposit::Member(group_id.clone(), lost_id.clone())));
+      }
+      if Groups::<T>::get(&group_id).map_or(false, |group| group.owner == *lost_id) {
+        moved = moved.saturating_add(Deposits::<T>::get(Deposit::Group(group_id.clone())));
+      }
+    }
+
+    (moved, freed)
+  }
+
+  /// Move `amount` of the reserved balance of `from_id` to `to_id`, failing unless all of it moved.
+  fn repatriate(from_id: &T::AccountId, to_id: &T::AccountId, amount: BalanceOf<T>, status: BalanceStatus) -> Result<(), Error<T>> {
+    let unmoved = T::Currency::repatriate_reserved(from_id, to_id, amount, status).map_err(|_| Error::<T>::InsufficientDeposit)?;
+    ensure!(unmoved.is_zero(), Error::<T>::InsufficientDeposit);
+
+    Ok(())
+  }
+
+  /// The connections of `who_id` in either direction, optionally only those in `status`. The first
+  /// `cursor` entries are skipped and at most `limit` are returned, capped at `MAX_PAGE_SIZE`.
+  pub fn connections_of(who_id: &T::AccountId, status: &Option<Relation>, cursor: u32, limit: u32) -> Vec<ConnectionInfo<T::AccountId>> {
+    Self::iter_connections(who_id)
+      .filter(|connection| status.map_or(true, |status| connection.relation == status))
+      .skip(cursor as usize)
+      .take(limit.min(MAX_PAGE_SIZE) as usize)
+      .collect()
+  }
+
+  /// The groups `who_id` is a direct member of, with its access control in each.
+  pub fn groups_of(who_id: &T::AccountId) -> Vec<(GroupId, AccessControl)> {
+    MemberGroups::<T>::iter_prefix(who_id)
+      .filter_map(|(group_id, _)| AccessControls::<T>::get(&group_id, who_id).map(|access_control| (group_id, access_control)))
+      .collect()
+  }
+
+  /// The direct members of a group, paginated like `connections_of`. `Customer`s and the members
+  /// of `Private` groups are never disclosed.
+  pub fn members_of(group_id: &GroupId, cursor: u32, limit: u32) -> Vec<(T::AccountId, AccessControl)> {
+    if Groups::<T>::get(group_id).map_or(true, |group| group.visibility == Visibility::Private) {
+      return Vec::new();
+    }
+
+    AccessControls::<T>::iter_prefix(group_id)
+      .filter(|(_, access_control)| *access_control != AccessControl::Customer)
+      .skip(cursor as usize)
+      .take(limit.min(MAX_PAGE_SIZE) as usize)
+      .collect()
+  }
+
+  /// The accounts both `a` and `b` are connected to.
+  pub fn mutual_connections(a: &T::AccountId, b: &T::AccountId) -> Vec<T::AccountId> {
+    Self::iter_connections(a)
+      .filter(|connection| connection.relation == Relation::Connected)
+      .map(|connection| connection.account)
+      .filter(|account| account != b && Self::is_connected(b, account))
+      .collect()
+  }
+
+  /// `Public` groups near `country`: those in the country itself first, then those elsewhere in its
+  /// sub-region, then those elsewhere in its region. Paginated like `connections_of`.
+  pub fn nearby_groups(country: &Country, cursor: u32, limit: u32) -> Vec<(GroupId, Group<T::AccountId>)> {
+    let sub_region = sub_region_of(country.clone());
+    let region = region_of(country.clone());
+
+    let in_country = GroupsByCountry::<T>::iter_prefix(country).filter_map(|(group_id, _)| Self::group_entry(group_id));
+    let in_sub_region = GroupsBySubRegion::<T>::iter_prefix(&sub_region)
+      .filter_map(|(group_id, _)| Self::group_entry(group_id))
+      .filter(|(_, group)| group.country != *country);
+    let in_region = GroupsByRegion::<T>::iter_prefix(&region)
+      .filter_map(|(group_id, _)| Self::group_entry(group_id))
+      .filter(|(_, group)| group.sub_region != sub_region);
+
+    in_country
+      .chain(in_sub_region)
+      .chain(in_region)
+      .skip(cursor as usize)
+      .take(limit.min(MAX_PAGE_SIZE) as usize)
+      .collect()
+  }
+
+  pub fn is_connected(a: &T::AccountId, b: &T::AccountId) -> bool {
+    Connections::<T>::get(a, b) == Some(Relation::Connected) || Connections::<T>::get(b, a) == Some(Relation::Connected)
+  }
+
+  fn group_entry(group_id: GroupId) -> Option<(GroupId, Group<T::AccountId>)> {
+    Groups::<T>::get(&group_id)
+      .filter(|group| group.visibility == Visibility::Public)
+      .map(|group| (group_id, group))
+  }
+
+  /// Outgoing connections of `who_id` first, then incoming ones through `IncomingConnections`.
+  fn iter_connections<'a>(who_id: &'a T::AccountId) -> impl Iterator<Item = ConnectionInfo<T::AccountId>> + 'a {
+    let outgoing = Connections::<T>::iter_prefix(who_id).map(|(account, relation)| ConnectionInfo {
+      account,
+      relation,
+      incoming: false,
+    });
+    let incoming = IncomingConnections::<T>::iter_prefix(who_id).filter_map(move |(account, _)| {
+      Connections::<T>::get(&account, who_id).map(|relation| ConnectionInfo {
+        account,
+        relation,
+        incoming: true,
+      })
+    });
+
+    outgoing.chain(incoming)
+  }
+
+  /// Insert `who_id` into the group, reserving its member deposit, and keep the `members`/`admins`
+  /// counters of the group in sync.
+  fn insert_member(who_id: &T::AccountId, group_id: &GroupId, access_control: &AccessControl) -> Result<(), Error<T>> {
+    ensure!(!AccessControls::<T>::contains_key(group_id, who_id), Error::<T>::AlreadyJoined);
+
+    let mut group = Groups::<T>::get(group_id).ok_or(Error::<T>::GroupNotExisted)?;
+    group.members = group.members.checked_add(1).ok_or(Error::<T>::Overflow)?;
+    if [AccessControl::SuperAdmin, AccessControl::Admin].contains(access_control) {
+      group.admins = group.admins.checked_add(1).ok_or(Error::<T>::Overflow)?;
+    }
+    Self::hold_deposit(who_id, Deposit::Member(group_id.clone(), who_id.clone()), T::MemberDeposit::get())?;
+
+    Groups::<T>::insert(group_id, group);
+    AccessControls::<T>::insert(group_id, who_id, access_control);
+    MemberGroups::<T>::insert(who_id, group_id, ());
+
+    Ok(())
+  }
+
+  /// Remove `who_id` from the group, refunding its member deposit, and keep the `members`/`admins`
+  /// counters of the group in sync.
+  fn delete_member(who_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    let access_control = AccessControls::<T>::get(group_id, who_id).ok_or(Error::<T>::NeverJoining)?;
+
+    Groups::<T>::try_mutate(group_id, |maybe_group| -> Result<(), Error<T>> {
+      let mut group = maybe_group.take().ok_or(Error::<T>::GroupNotExisted)?;
+
+      let members = group.members.checked_sub(1).unwrap_or(0);
+      group.members = members;
+
+      if [AccessControl::SuperAdmin, AccessControl::Admin].contains(&access_control) {
+        let admins = group.admins.checked_sub(1).unwrap_or(0);
+
+        group.admins = admins;
+      }
+
+      *maybe_group = Some(group);
+
+      Ok(())
+    })?;
+    AccessControls::<T>::remove(group_id, who_id);
+    MemberGroups::<T>::remove(who_id, group_id);
+    Self::release_deposit(who_id, Deposit::Member(group_id.clone(), who_id.clone()));
+
+    Ok(())
+  }
+
+  /// Replace the access control of a member, moving it in or out of the `admins` counter when needed.
+  fn update_member(who_id: &T::AccountId, group_id: &GroupId, from: &AccessControl, to: &AccessControl) -> Result<(), Error<T>> {
+    let was_admin = [AccessControl::SuperAdmin, AccessControl::Admin].contains(from);
+    let is_admin = [AccessControl::SuperAdmin, AccessControl::Admin].contains(to);
+
+    if was_admin != is_admin {
+      Groups::<T>::try_mutate(group_id, |maybe_group| -> Result<(), Error<T>> {
+        let mut group = maybe_group.take().ok_or(Error::<T>::GroupNotExisted)?;
+
+        if is_admin {
+          group.admins = group.admins.checked_add(1).ok_or(Error::<T>::Overflow)?;
+        } else {
+          group.admins = group.admins.checked_sub(1).unwrap_or(0);
+        }
+
+        *maybe_group = Some(group);
+
+        Ok(())
+      })?;
+    }
+    AccessControls::<T>::insert(group_id, who_id, to);
+
+    Ok(())
+  }
+
+  /// A `Private` group is not disclosed to anyone, so it cannot take join requests from strangers.
+  fn ensure_valid_policy(visibility: &Visibility, join_policy: &JoinPolicy) -> Result<(), Error<T>> {
+    ensure!(
+      *visibility != Visibility::Private || *join_policy == JoinPolicy::InviteOnly,
+      Error::<T>::InvalidGroupPolicy
+    );
+
+    Ok(())
+  }
+
+  fn reserve_deposit(who_id: &T::AccountId, deposit: BalanceOf<T>) -> Result<(), Error<T>> {
+    T::Currency::reserve(who_id, deposit).map_err(|_| Error::<T>::InsufficientDeposit)
+  }
+
+  /// Reserve `amount` from `who_id` for `deposit`, and record it so that exactly that is refunded.
+  fn hold_deposit(who_id: &T::AccountId, deposit: Deposit<T::AccountId>, amount: BalanceOf<T>) -> Result<(), Error<T>> {
+    Self::reserve_deposit(who_id, amount)?;
+    Deposits::<T>::insert(deposit, amount);
+
+    Ok(())
+  }
+
+  /// Refund to `who_id` what it reserved for `deposit`.
+  fn release_deposit(who_id: &T::AccountId, deposit: Deposit<T::AccountId>) {
+    T::Currency::unreserve(who_id, Deposits::<T>::take(deposit));
+  }
+
+  fn ensure_live(who_id: &T::AccountId) -> Result<(), Error<T>> {
+    let maybe_who = Accounts::<T>::get(who_id);
+
+    ensure!(maybe_who.is_some(), Error::<T>::AccountNotExisted);
+    ensure!(maybe_who.unwrap().status == AccountStatus::Live, Error::<T>::AccountNotLive);
+
+    Ok(())
+  }
+
+  fn ensure_admin(invoker_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    let maybe_invoker_access = Self::access_control_of(group_id, invoker_id);
+
+    Self::ensure_live(invoker_id)?;
+    ensure!(maybe_invoker_access.is_some(), Error::<T>::NeverJoining);
+    ensure!(
+      [AccessControl::SuperAdmin, AccessControl::Admin].contains(&maybe_invoker_access.unwrap()),
+      Error::<T>::OnlyAdminAllowed
+    );
+
+    Ok(())
+  }
+
+  /// Roles must be unique and non-empty, `Custodian` is only granted through `Custodians`, and an
+  /// `EnterpriseEmployee`, and only an `EnterpriseEmployee`, is linked to a live `Enterprise` account.
+  fn ensure_valid_roles(roles: &Roles, enterprise_id: &Option<T::AccountId>) -> Result<(), Error<T>> {
+    ensure!(!roles.is_empty(), Error::<T>::InvalidRoles);
+    ensure!(!roles.contains(&Role::Custodian), Error::<T>::InvalidRoles);
+    for (index, role) in roles.iter().enumerate() {
+      ensure!(!roles[index + 1..].contains(role), Error::<T>::InvalidRoles);
+    }
+
+    match enterprise_id {
+      Some(enterprise_id) => {
+        ensure!(roles.contains(&Role::EnterpriseEmployee), Error::<T>::InvalidRoles);
+        ensure!(
+          <Self as RoleProvider<T::AccountId>>::has_role(enterprise_id, &Role::Enterprise),
+          Error::<T>::InvalidRoles
+        );
+      },
+      None => ensure!(!roles.contains(&Role::EnterpriseEmployee), Error::<T>::InvalidRoles),
+    }
+
+    Ok(())
+  }
+
+  fn ensure_organization_admin(invoker_id: &T::AccountId, organization_id: &T::AccountId) -> Result<(), Error<T>> {
+    Self::ensure_live(invoker_id)?;
+    ensure!(
+      invoker_id == organization_id || OrganizationMembers::<T>::get(organization_id, invoker_id) == Some(OrganizationRole::Admin),
+      Error::<T>::OnlyOrganizationAdminAllowed
+    );
+
+    Ok(())
+  }
+
+  pub fn ensure_custodian(who_id: &T::AccountId) -> Result<(), Error<T>> {
+    ensure!(Custodians::<T>::get().contains(who_id), Error::<T>::OnlyCustodianAllowed);
+
+    Ok(())
+  }
+
+  fn ensure_super_admin(invoker_id: &T::AccountId, group_id: &GroupId) -> Result<(), Error<T>> {
+    ensure!(
+      AccessControls::<T>::get(group_id, invoker_id) == Some(AccessControl::SuperAdmin),
+      Error::<T>::OnlySuperAdminAllowed
+    );
+
+    Ok(())
+  }
+}
+
+impl<T: Config> GroupMembership<T::AccountId> for Pallet<T> {
+  fn can_read(group_id: &GroupId, who_id: &T::AccountId) -> bool {
+    let is_live = Accounts::<T>::get(who_id).map_or(false, |who| who.status == AccountStatus::Live);
+
+    is_live && Self::access_control_of(group_id, who_id).map_or(false, |access_control| access_control != AccessControl::Customer)
+  }
+}
+
+impl<T: Config> CredentialProvider<T::AccountId> for Pallet<T> {
+  fn has_credential(who_id: &T::AccountId, kind: &CredentialKind) -> bool {
+    let now = <frame_system::Pallet<T>>::block_number();
+
+    Credentials::<T>::get(who_id, kind).map_or(false, |credential| {
+      credential.expiry >= now && Issuers::<T>::contains_key(&credential.issuer)
+    })
+  }
+}
+
+impl<T: Config> RoleProvider<T::AccountId> for Pallet<T> {
+  fn roles_of(who_id: &T::AccountId) -> Vec<Role> {
+    Accounts::<T>::get(who_id)
+      .filter(|who| who.status == AccountStatus::Live)
+      .map_or(Vec::new(), |who| who.roles.into_inner())
+  }
+
+  fn enterprise_of(who_id: &T::AccountId) -> Option<T::AccountId> {
+    Accounts::<T>::get(who_id)
+      .filter(|who| who.status == AccountStatus::Live)
+      .and_then(|who| who.enterprise)
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn assign_role(who_id: &T::AccountId, role: &Role) {
+    Accounts::<T>::insert(
+      who_id,
+      AccountDetail {
+        issuer: who_id.clone(),
+        freezer: None,
+        status: AccountStatus::Live,
+        roles: sp_std::vec![*role].try_into().unwrap(),
+        enterprise: None,
+        info: Default::default(),
+      },
+    );
+  }
+}
+
+impl<T: Config> CustodianProvider<T::AccountId> for Pallet<T> {
+  fn custodians() -> Vec<T::AccountId> {
+    Custodians::<T>::get()
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn register_custodian(who_id: &T::AccountId) {
+    let _ = Self::add_custodian(who_id);
+  }
+}
+
+impl<T: Config> OrganizationProvider<T::AccountId> for Pallet<T> {
+  fn can_act_for(organization_id: &T::AccountId, who_id: &T::AccountId) -> bool {
+    let is_live = |id: &T::AccountId| Accounts::<T>::get(id).map_or(false, |account| account.status == AccountStatus::Live);
+
+    is_live(organization_id)
+      && is_live(who_id)
+      && (organization_id == who_id
+        || (Organizations::<T>::contains_key(organization_id) && OrganizationMembers::<T>::contains_key(organization_id, who_id)))
+  }
 }
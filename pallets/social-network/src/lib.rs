@@ -2,10 +2,12 @@
 
 pub use pallet::*;
 
-mod constants;
+pub mod constants;
+pub mod traits;
+pub mod types;
+pub mod weights;
+
 mod impls;
-mod traits;
-mod types;
 
 #[cfg(test)]
 mod mock;
@@ -18,11 +20,26 @@ mod benchmarking;
 
 #[frame_support::pallet]
 pub mod pallet {
-  use crate::traits::ConnectionRules;
-  use crate::types::{AccessControl, AccountDetail, Group, GroupId, Relation};
-  use frame_support::pallet_prelude::*;
+  use crate::types::{
+    AccessControl, AccountDetail, AccountInfo, Channel, Credential, CredentialKind, Deposit, EncryptedMessage, Group, GroupId, GroupInfo,
+    Guardianship, Invitation, Issuer, IssuerInfo, JoinPolicy, Message, Organization, OrganizationRole, PendingMemberships, Recovery, RecoveryConfig,
+    Relation, RoleChange, Roles, Visibility,
+  };
+  use crate::{
+    constants::{MAX_CASCADE_DELETE, MAX_CHANNEL_MESSAGES},
+    traits::{ConnectionRules, OnAccountRecovered},
+    weights::WeightInfo,
+  };
+  use frame_support::{
+    dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+    pallet_prelude::*,
+    traits::{Contains, Currency, ReservableCurrency},
+  };
   use frame_system::pallet_prelude::*;
-  use sp_std::vec::Vec;
+  use healthcare::TherapeuticArea;
+  use pallet_key_registry::traits::KeyProvider;
+  use region::{Country, Region, SubRegion};
+  use sp_std::{boxed::Box, vec::Vec};
 
   /// Configure the pallet by specifying the parameters and types on which it depends.
   #[pallet::config]
@@ -30,8 +47,64 @@ pub mod pallet {
     type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
     type ConnectionRules: ConnectionRules<Self::AccountId>;
+
+    /// The number of blocks a join request or an invitation stays open.
+    #[pallet::constant]
+    type MembershipExpiry: Get<Self::BlockNumber>;
+
+    /// The overarching call type, so that guardians can dispatch calls on behalf of their wards.
+    type Call: Parameter
+      + Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+      + GetDispatchInfo
+      + From<frame_system::Call<Self>>
+      + From<Call<Self>>;
+
+    /// The calls a guardian is allowed to dispatch on behalf of its ward.
+    type GuardianFilter: Contains<<Self as Config>::Call>;
+
+    /// The longest a guardianship can last, in blocks.
+    #[pallet::constant]
+    type MaxGuardianshipPeriod: Get<Self::BlockNumber>;
+
+    /// Where the keys direct messages are encrypted to are registered.
+    type Keys: KeyProvider<Self::AccountId>;
+
+    /// Other pallets moving what a recovered account owned to its new key.
+    type OnAccountRecovered: OnAccountRecovered<Self::AccountId>;
+
+    /// How long a vouched recovery waits, so that the old key can still cancel it.
+    #[pallet::constant]
+    type RecoveryDelay: Get<Self::BlockNumber>;
+
+    /// The currency deposits are reserved in.
+    type Currency: ReservableCurrency<Self::AccountId>;
+
+    /// Reserved from the custodian onboarding an account, until the account is closed.
+    #[pallet::constant]
+    type AccountDeposit: Get<BalanceOf<Self>>;
+
+    /// Reserved from the requester of a connection, until the connection is removed.
+    #[pallet::constant]
+    type ConnectionDeposit: Get<BalanceOf<Self>>;
+
+    /// Reserved from the owner of a group, on top of its `MemberDeposit`, until the group is dissolved.
+    #[pallet::constant]
+    type GroupDeposit: Get<BalanceOf<Self>>;
+
+    /// Reserved from every member of a group, until it leaves the group.
+    #[pallet::constant]
+    type MemberDeposit: Get<BalanceOf<Self>>;
+
+    /// Reserved from an account asking to join a group, until its request is settled or lapses.
+    #[pallet::constant]
+    type JoinRequestDeposit: Get<BalanceOf<Self>>;
+
+    /// Weight information for the extrinsics of this pallet.
+    type WeightInfo: WeightInfo;
   }
 
+  pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
   #[pallet::pallet]
   #[pallet::generate_store(pub(super) trait Store)]
   pub struct Pallet<T>(_);
@@ -48,6 +121,11 @@ pub mod pallet {
   #[pallet::getter(fn connections)]
   pub type Connections<T: Config> = StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::AccountId, Relation>;
 
+  /// The reverse of `Connections`, keyed by the requested account then the requester.
+  #[pallet::storage]
+  #[pallet::getter(fn incoming_connections)]
+  pub type IncomingConnections<T: Config> = StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::AccountId, ()>;
+
   #[pallet::storage]
   #[pallet::getter(fn groups)]
   pub type Groups<T: Config> = StorageMap<_, Twox64Concat, GroupId, Group<T::AccountId>>;
@@ -56,39 +134,821 @@ pub mod pallet {
   #[pallet::getter(fn group_members)]
   pub type AccessControls<T: Config> = StorageDoubleMap<_, Twox64Concat, GroupId, Twox64Concat, T::AccountId, AccessControl>;
 
+  /// Groups located in a country, keyed by country then group.
+  #[pallet::storage]
+  #[pallet::getter(fn groups_by_country)]
+  pub type GroupsByCountry<T: Config> = StorageDoubleMap<_, Twox64Concat, Country, Twox64Concat, GroupId, ()>;
+
+  /// Groups located in a sub-region, keyed by sub-region then group.
+  #[pallet::storage]
+  #[pallet::getter(fn groups_by_sub_region)]
+  pub type GroupsBySubRegion<T: Config> = StorageDoubleMap<_, Twox64Concat, SubRegion, Twox64Concat, GroupId, ()>;
+
+  /// Groups located in a region, keyed by region then group.
+  #[pallet::storage]
+  #[pallet::getter(fn groups_by_region)]
+  pub type GroupsByRegion<T: Config> = StorageDoubleMap<_, Twox64Concat, Region, Twox64Concat, GroupId, ()>;
+
+  /// The reverse of `AccessControls`, keyed by member then group.
+  #[pallet::storage]
+  #[pallet::getter(fn member_groups)]
+  pub type MemberGroups<T: Config> = StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, GroupId, ()>;
+
+  /// The direct sub-groups of a group, keyed by parent then child.
+  #[pallet::storage]
+  #[pallet::getter(fn sub_groups)]
+  pub type SubGroups<T: Config> = StorageDoubleMap<_, Twox64Concat, GroupId, Twox64Concat, GroupId, ()>;
+
+  /// Guardians allowed to act on behalf of a ward, keyed by ward then guardian.
+  #[pallet::storage]
+  #[pallet::getter(fn guardians)]
+  pub type Guardians<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::AccountId, Guardianship<T::AccountId, T::BlockNumber>>;
+
+  /// The reverse of `Guardians`, keyed by guardian then ward.
+  #[pallet::storage]
+  #[pallet::getter(fn wards)]
+  pub type Wards<T: Config> = StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::AccountId, ()>;
+
+  /// Role changes asked for by accounts, waiting for a custodian to approve them.
+  #[pallet::storage]
+  #[pallet::getter(fn role_change_requests)]
+  pub type RoleChangeRequests<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, RoleChange<T::AccountId>>;
+
+  /// Organizations, keyed by the `Enterprise` or `HealthFacility` account that runs them.
+  #[pallet::storage]
+  #[pallet::getter(fn organizations)]
+  pub type Organizations<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Organization>;
+
+  /// Admins and employees of an organization, keyed by organization then member.
+  #[pallet::storage]
+  #[pallet::getter(fn organization_members)]
+  pub type OrganizationMembers<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, OrganizationRole>;
+
+  /// The reverse of `OrganizationMembers`, keyed by member then organization.
+  #[pallet::storage]
+  #[pallet::getter(fn member_organizations)]
+  pub type MemberOrganizations<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, ()>;
+
+  /// The reverse of `AccountDetail.enterprise`, keyed by enterprise then employee.
+  #[pallet::storage]
+  #[pallet::getter(fn employees)]
+  pub type Employees<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, ()>;
+
+  /// Trusted issuers, such as licensing boards, registered by custodians.
+  #[pallet::storage]
+  #[pallet::getter(fn issuers)]
+  pub type Issuers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Issuer<T::AccountId>>;
+
+  /// Credentials attested by trusted issuers, keyed by holder then kind.
+  #[pallet::storage]
+  #[pallet::getter(fn credentials)]
+  pub type Credentials<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, CredentialKind, Credential<T::AccountId, T::BlockNumber>>;
+
+  /// Pending requests to join a group, with the last block at which they can be approved.
+  #[pallet::storage]
+  #[pallet::getter(fn join_requests)]
+  pub type JoinRequests<T: Config> = StorageDoubleMap<_, Twox64Concat, GroupId, Twox64Concat, T::AccountId, T::BlockNumber>;
+
+  /// Pending invitations to join a group, waiting for the invitee to accept.
+  #[pallet::storage]
+  #[pallet::getter(fn invitations)]
+  pub type Invitations<T: Config> = StorageDoubleMap<_, Twox64Concat, GroupId, Twox64Concat, T::AccountId, Invitation<T::AccountId, T::BlockNumber>>;
+
+  /// The join requests and invitations that lapse at a block, removed when the next one starts.
+  #[pallet::storage]
+  pub type MembershipExpiries<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, PendingMemberships<T::AccountId>, ValueQuery>;
+
+  /// The deposits reserved for accounts, connections, groups and memberships, so that what was
+  /// reserved is refunded even if the configured deposits changed in the meantime.
+  #[pallet::storage]
+  #[pallet::getter(fn deposits)]
+  pub type Deposits<T: Config> = StorageMap<_, Blake2_128Concat, Deposit<T::AccountId>, BalanceOf<T>, ValueQuery>;
+
+  /// The contacts each account trusts to recover it.
+  #[pallet::storage]
+  #[pallet::getter(fn recovery_configs)]
+  pub type RecoveryConfigs<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, RecoveryConfig<T::AccountId>>;
+
+  /// Recoveries in progress, keyed by the lost account then the new key vouched for.
+  #[pallet::storage]
+  #[pallet::getter(fn recoveries)]
+  pub type Recoveries<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, Recovery<T::AccountId, T::BlockNumber>>;
+
+  /// Direct messaging channels, keyed by the two ends in ascending order.
+  #[pallet::storage]
+  #[pallet::getter(fn channels)]
+  pub type Channels<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, Channel<T::AccountId, T::BlockNumber>>;
+
+  /// The reverse of `Channels`, keyed by the greater end then the lesser one.
+  #[pallet::storage]
+  #[pallet::getter(fn reverse_channels)]
+  pub type ReverseChannels<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, ()>;
+
+  /// The on-chain trace of direct messages, keyed by channel then index. The encrypted payloads
+  /// themselves are kept in offchain storage through offchain indexing.
+  #[pallet::storage]
+  #[pallet::getter(fn messages)]
+  pub type Messages<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, (T::AccountId, T::AccountId), Twox64Concat, u32, Message<T::AccountId, T::Hash, T::BlockNumber>>;
+
+  /// The number of messages of a channel each end has read, keyed by channel then reader.
+  #[pallet::storage]
+  #[pallet::getter(fn read_receipts)]
+  pub type ReadReceipts<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, (T::AccountId, T::AccountId), Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+  /// The number of messages of a channel each end has sent, keyed by channel then sender.
+  #[pallet::storage]
+  #[pallet::getter(fn sent_messages)]
+  pub type SentMessages<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, (T::AccountId, T::AccountId), Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
   #[pallet::event]
   #[pallet::generate_deposit(pub(super) fn deposit_event)]
   pub enum Event<T: Config> {
-    SomethingStored(u32, T::AccountId),
+    /// A custodian was added. \[custodian\]
+    CustodianAdded(T::AccountId),
+    /// A custodian was removed. \[custodian\]
+    CustodianRemoved(T::AccountId),
+    /// A custodian onboarded an account. \[who, custodian\]
+    AccountOnboarded(T::AccountId, T::AccountId),
+    /// An account was closed and the deposit of its custodian refunded. \[who\]
+    AccountClosed(T::AccountId),
+    /// A custodian froze an account. \[who, custodian\]
+    AccountFrozen(T::AccountId, T::AccountId),
+    /// A custodian unfroze an account. \[who, custodian\]
+    AccountUnfrozen(T::AccountId, T::AccountId),
+    /// An account asked another one for a connection. \[from, to\]
+    ConnectionRequested(T::AccountId, T::AccountId),
+    /// A connection request was accepted. \[from, to\]
+    Connected(T::AccountId, T::AccountId),
+    /// A connection, or a pending request for one, was removed. \[from, to\]
+    Disconnected(T::AccountId, T::AccountId),
+    /// An account asked a custodian to change its roles. \[who\]
+    RoleChangeRequested(T::AccountId),
+    /// A custodian approved a role change. \[who, custodian\]
+    RoleChangeApproved(T::AccountId, T::AccountId),
+    /// A custodian rejected a role change. \[who, custodian\]
+    RoleChangeRejected(T::AccountId, T::AccountId),
+    /// An account asked to join a group. \[group_id, who\]
+    JoinRequested(GroupId, T::AccountId),
+    /// An account withdrew its join request. \[group_id, who\]
+    JoinRequestCancelled(GroupId, T::AccountId),
+    /// An admin approved a join request. \[group_id, who, admin\]
+    JoinApproved(GroupId, T::AccountId, T::AccountId),
+    /// An admin rejected a join request. \[group_id, who, admin\]
+    JoinRejected(GroupId, T::AccountId, T::AccountId),
+    /// An admin invited an account to a group. \[group_id, who, admin\]
+    MemberInvited(GroupId, T::AccountId, T::AccountId),
+    /// An admin withdrew an invitation. \[group_id, who, admin\]
+    InvitationRevoked(GroupId, T::AccountId, T::AccountId),
+    /// An account accepted an invitation and joined the group. \[group_id, who\]
+    InvitationAccepted(GroupId, T::AccountId),
+    /// An account declined an invitation. \[group_id, who\]
+    InvitationDeclined(GroupId, T::AccountId),
+    /// A group was created. \[group_id, owner\]
+    GroupCreated(GroupId, T::AccountId),
+    /// An admin changed who can find a group and how to join it. \[group_id, visibility, join_policy\]
+    GroupPolicyChanged(GroupId, Visibility, JoinPolicy),
+    /// A member was added to a group by an admin, or joined an open group. \[group_id, who, access_control\]
+    MemberJoined(GroupId, T::AccountId, AccessControl),
+    /// A member was removed from a group. \[group_id, who\]
+    MemberLeft(GroupId, T::AccountId),
+    /// The access control of a member changed. \[group_id, who, access_control\]
+    RoleChanged(GroupId, T::AccountId, AccessControl),
+    /// The ownership of a group was handed over. \[group_id, old_owner, new_owner\]
+    OwnershipTransferred(GroupId, T::AccountId, T::AccountId),
+    /// A group, its sub-groups and all of their memberships were removed. \[group_id, owner\]
+    GroupDeleted(GroupId, T::AccountId),
+    /// A guardian can now act on behalf of a ward. \[ward, guardian, expiry\]
+    GuardianAdded(T::AccountId, T::AccountId, T::BlockNumber),
+    /// A guardian can no longer act on behalf of a ward. \[ward, guardian\]
+    GuardianRemoved(T::AccountId, T::AccountId),
+    /// A guardian dispatched a call on behalf of a ward. \[ward, guardian, result\]
+    ActedForWard(T::AccountId, T::AccountId, DispatchResult),
+    /// An `Enterprise` or `HealthFacility` account set up its organization. \[organization\]
+    OrganizationCreated(T::AccountId),
+    /// An account joined an organization. \[organization, who, role\]
+    OrganizationMemberAdded(T::AccountId, T::AccountId, OrganizationRole),
+    /// An account was offboarded from an organization. \[organization, who\]
+    OrganizationMemberRemoved(T::AccountId, T::AccountId),
+    /// A custodian registered a trusted issuer. \[issuer, custodian\]
+    IssuerRegistered(T::AccountId, T::AccountId),
+    /// A custodian deregistered a trusted issuer. \[issuer, custodian\]
+    IssuerDeregistered(T::AccountId, T::AccountId),
+    /// An issuer attested a credential. \[holder, kind, issuer\]
+    CredentialIssued(T::AccountId, CredentialKind, T::AccountId),
+    /// A credential was revoked. \[holder, kind, revoker\]
+    CredentialRevoked(T::AccountId, CredentialKind, T::AccountId),
+    /// A direct messaging channel was opened. \[opener, other\]
+    ChannelOpened(T::AccountId, T::AccountId),
+    /// A direct messaging channel and its history were closed. \[closer, other\]
+    ChannelClosed(T::AccountId, T::AccountId),
+    /// An encrypted message was sent. \[sender, recipient, index, hash\]
+    MessageSent(T::AccountId, T::AccountId, u32, T::Hash),
+    /// The messages of a channel were read up to an index. \[reader, sender, read\]
+    MessagesRead(T::AccountId, T::AccountId, u32),
+    /// An account set its recovery contacts. \[who, threshold\]
+    RecoveryConfigured(T::AccountId, u32),
+    /// An account removed its recovery contacts. \[who\]
+    RecoveryConfigRemoved(T::AccountId),
+    /// A contact vouched for a new key of a lost account. \[lost, new, contact\]
+    RecoveryVouched(T::AccountId, T::AccountId, T::AccountId),
+    /// Enough contacts vouched, the recovery can be claimed after the delay. \[lost, new, claimable_at\]
+    RecoveryApproved(T::AccountId, T::AccountId, T::BlockNumber),
+    /// The old key cancelled a recovery. \[lost, new\]
+    RecoveryCancelled(T::AccountId, T::AccountId),
+    /// An account moved to its new key. \[lost, new\]
+    AccountRecovered(T::AccountId, T::AccountId),
   }
 
   // Errors inform users that something went wrong.
   #[pallet::error]
   pub enum Error<T> {
+    AccountAlreadyExisted,
     AccountNotExisted,
     AccountNotLive,
+    AccountNotFrozen,
     AlreadyConnected,
     AlreadyConnecting,
     AlreadyJoined,
     AlreadyJoining,
+    AlreadyInvited,
+    AlreadyOrganizationMember,
+    AlreadyGuardian,
+    CallNotAllowed,
+    GuardianshipExpired,
+    InvalidExpiry,
+    InvalidRoles,
     GroupAlreadyExisted,
     GroupNotExisted,
     NeverConnecting,
     NeverJoining,
+    NeverInvited,
+    NotGuardian,
+    NeverRequestedRoleChange,
+    NotOrganizationMember,
+    MembershipExpired,
     OnlyAdminAllowed,
+    OnlySuperAdminAllowed,
+    OnlyOwnerAllowed,
+    LastSuperAdmin,
+    OnlyCustodianAllowed,
+    OnlyOrganizationAdminAllowed,
+    OrganizationAlreadyExisted,
+    OrganizationNotExisted,
+    TooDeepGroup,
+    TooManyGuardians,
+    TooManySubGroups,
     OnlyPendingAllowed,
     Overflow,
     TooManyCustodians,
     TooFewCustodians,
     CustodianAlreadyRegistered,
     CustodianNotRegistered,
+    CredentialNotExisted,
+    IssuerAlreadyRegistered,
+    IssuerNotRegistered,
+    ChannelAlreadyExisted,
+    ChannelNotExisted,
+    ChannelWithSelf,
+    EncryptionKeyNotExisted,
+    InvalidReadReceipt,
+    OnlyConnectedAllowed,
+    AlreadyVouched,
+    InvalidRecoveryContacts,
+    InvalidThreshold,
+    NotRecoveryContact,
+    RecoveryAlreadyStarted,
+    RecoveryNotConfigured,
+    RecoveryNotReady,
+    RecoveryNotStarted,
+    InvalidGroupPolicy,
+    InvitationRequired,
+    GroupNotOpen,
+    OnlyStaffAllowed,
+    InsufficientDeposit,
+    AccountInUse,
+    ChannelFull,
+  }
+
+  #[pallet::hooks]
+  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    /// Remove the join requests and invitations scheduled to be removed at this block, which
+    /// lapsed before it.
+    fn on_initialize(now: T::BlockNumber) -> Weight {
+      let expired = MembershipExpiries::<T>::take(now);
+      let count = expired.len() as u64;
+
+      for (group_id, who_id) in expired {
+        Self::remove_lapsed_membership(&group_id, &who_id, &now);
+      }
+
+      T::DbWeight::get().reads_writes(1 + 4 * count, 1 + 4 * count)
+    }
   }
 
   #[pallet::call]
   impl<T: Config> Pallet<T> {
-    #[pallet::weight(10_000)]
-    pub fn do_something(origin: OriginFor<T>) -> DispatchResult {
-      let creator_id = ensure_signed(origin)?;
+    /// Onboard an account with its healthcare roles. Only custodians can onboard accounts. An
+    /// `EnterpriseEmployee` has to be linked to the `Enterprise` account it works for.
+    #[pallet::weight(T::WeightInfo::onboard_account())]
+    pub fn onboard_account(
+      origin: OriginFor<T>,
+      who: T::AccountId,
+      roles: Roles,
+      enterprise: Option<T::AccountId>,
+      info: AccountInfo,
+    ) -> DispatchResult {
+      let custodian = ensure_signed(origin)?;
+
+      Self::onboard(&custodian, &who, &roles, &enterprise, &info)?;
+
+      Ok(())
+    }
+
+    /// Close the signer's account, refunding the deposit of the custodian that onboarded it. A live
+    /// account has to leave its groups and organizations, remove its connections and channels, and
+    /// stop being a guardian first; its own guardians and credentials are removed with it.
+    #[pallet::weight(T::WeightInfo::close_account())]
+    pub fn close_account(origin: OriginFor<T>) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::offboard(&who)?;
+
+      Ok(())
+    }
+
+    /// Freeze a live account. Only custodians can freeze accounts.
+    #[pallet::weight(T::WeightInfo::freeze_account())]
+    pub fn freeze_account(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+      let custodian = ensure_signed(origin)?;
+
+      Self::freeze(&custodian, &who)?;
+
+      Ok(())
+    }
+
+    #[pallet::weight(T::WeightInfo::unfreeze_account())]
+    pub fn unfreeze_account(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+      let custodian = ensure_signed(origin)?;
+
+      Self::unfreeze(&custodian, &who)?;
+
+      Ok(())
+    }
+
+    /// Ask for new roles. They replace the current roles once a custodian approves them.
+    #[pallet::weight(T::WeightInfo::request_role_change())]
+    pub fn request_role_change(origin: OriginFor<T>, roles: Roles, enterprise: Option<T::AccountId>) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_request_role_change(&who, &roles, &enterprise)?;
+
+      Ok(())
+    }
+
+    #[pallet::weight(T::WeightInfo::approve_role_change())]
+    pub fn approve_role_change(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+      let custodian = ensure_signed(origin)?;
+
+      Self::do_approve_role_change(&custodian, &who)?;
+
+      Ok(())
+    }
+
+    #[pallet::weight(T::WeightInfo::reject_role_change())]
+    pub fn reject_role_change(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+      let custodian = ensure_signed(origin)?;
+
+      Self::do_reject_role_change(&custodian, &who)?;
+
+      Ok(())
+    }
+
+    /// Ask `to` for a connection. The connection is pending until `to` accepts it.
+    #[pallet::weight(T::WeightInfo::request_connection())]
+    pub fn request_connection(origin: OriginFor<T>, to: T::AccountId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::connect(&who, &to)?;
+
+      Ok(())
+    }
+
+    #[pallet::weight(T::WeightInfo::accept_connection())]
+    pub fn accept_connection(origin: OriginFor<T>, from: T::AccountId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_connect(&from, &who)?;
+
+      Ok(())
+    }
+
+    /// Remove a connection, or a pending request for one, in either direction.
+    #[pallet::weight(T::WeightInfo::remove_connection())]
+    pub fn remove_connection(origin: OriginFor<T>, other: T::AccountId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      if Connections::<T>::contains_key(&who, &other) {
+        Self::disconnect(&who, &other)?;
+      } else {
+        Self::disconnect(&other, &who)?;
+      }
+
+      Ok(())
+    }
+
+    /// Create a group owned by the signer, located in the region and sub-region of `country`. A
+    /// sub-group can only be created by an admin of its parent. A `Private` group has to be `InviteOnly`.
+    #[pallet::weight(T::WeightInfo::create_group())]
+    pub fn create_group(
+      origin: OriginFor<T>,
+      group_id: GroupId,
+      info: GroupInfo,
+      country: Country,
+      parent: Option<GroupId>,
+      visibility: Visibility,
+      join_policy: JoinPolicy,
+    ) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::do_create_group(&owner, &group_id, &info, &country, &parent, &visibility, &join_policy)?;
+
+      Ok(())
+    }
+
+    /// Change who can find a group and how to join it. Only affects future joins.
+    #[pallet::weight(T::WeightInfo::set_group_policy())]
+    pub fn set_group_policy(origin: OriginFor<T>, group_id: GroupId, visibility: Visibility, join_policy: JoinPolicy) -> DispatchResult {
+      let admin = ensure_signed(origin)?;
+
+      Self::do_set_group_policy(&admin, &group_id, &visibility, &join_policy)?;
+
+      Ok(())
+    }
+
+    /// Join an `Open` group as a `Customer`.
+    #[pallet::weight(T::WeightInfo::join_group())]
+    pub fn join_group(origin: OriginFor<T>, group_id: GroupId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::join(&who, &group_id)?;
+
+      Ok(())
+    }
+
+    /// Ask to join a group. An admin of the group has to approve the request before it expires.
+    #[pallet::weight(T::WeightInfo::request_join())]
+    pub fn request_join(origin: OriginFor<T>, group_id: GroupId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_request_join(&who, &group_id)?;
+
+      Ok(())
+    }
+
+    #[pallet::weight(T::WeightInfo::cancel_join_request())]
+    pub fn cancel_join_request(origin: OriginFor<T>, group_id: GroupId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_cancel_join_request(&who, &group_id)?;
+
+      Ok(())
+    }
+
+    #[pallet::weight(T::WeightInfo::approve_join())]
+    pub fn approve_join(origin: OriginFor<T>, group_id: GroupId, who: T::AccountId, access_control: AccessControl) -> DispatchResult {
+      let admin = ensure_signed(origin)?;
+
+      Self::do_approve_join(&admin, &who, &group_id, &access_control)?;
+
+      Ok(())
+    }
+
+    #[pallet::weight(T::WeightInfo::reject_join())]
+    pub fn reject_join(origin: OriginFor<T>, group_id: GroupId, who: T::AccountId) -> DispatchResult {
+      let admin = ensure_signed(origin)?;
+
+      Self::do_reject_join(&admin, &who, &group_id)?;
+
+      Ok(())
+    }
+
+    /// Invite an account to a group. The account only becomes a member once it accepts.
+    #[pallet::weight(T::WeightInfo::invite())]
+    pub fn invite(origin: OriginFor<T>, group_id: GroupId, who: T::AccountId, access_control: AccessControl) -> DispatchResult {
+      let admin = ensure_signed(origin)?;
+
+      Self::do_invite(&admin, &who, &group_id, &access_control)?;
+
+      Ok(())
+    }
+
+    #[pallet::weight(T::WeightInfo::revoke_invitation())]
+    pub fn revoke_invitation(origin: OriginFor<T>, group_id: GroupId, who: T::AccountId) -> DispatchResult {
+      let admin = ensure_signed(origin)?;
+
+      Self::do_revoke_invitation(&admin, &who, &group_id)?;
+
+      Ok(())
+    }
+
+    #[pallet::weight(T::WeightInfo::accept_invitation())]
+    pub fn accept_invitation(origin: OriginFor<T>, group_id: GroupId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_accept_invitation(&who, &group_id)?;
+
+      Ok(())
+    }
+
+    #[pallet::weight(T::WeightInfo::decline_invitation())]
+    pub fn decline_invitation(origin: OriginFor<T>, group_id: GroupId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_decline_invitation(&who, &group_id)?;
+
+      Ok(())
+    }
+
+    /// Remove a member from a group. Only admins can remove members, and only a `SuperAdmin` can remove another
+    /// `SuperAdmin`. The owner can never be removed.
+    #[pallet::weight(T::WeightInfo::remove_member())]
+    pub fn remove_member(origin: OriginFor<T>, group_id: GroupId, who: T::AccountId) -> DispatchResult {
+      let admin = ensure_signed(origin)?;
+
+      Self::disjoin(&admin, &who, &group_id)?;
+
+      Ok(())
+    }
+
+    /// Leave a group. The owner has to hand the group over before leaving it.
+    #[pallet::weight(T::WeightInfo::leave_group())]
+    pub fn leave_group(origin: OriginFor<T>, group_id: GroupId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::leave(&who, &group_id)?;
+
+      Ok(())
+    }
+
+    /// Promote or demote a member. Granting or taking away `SuperAdmin` needs a `SuperAdmin`.
+    #[pallet::weight(T::WeightInfo::change_access_control())]
+    pub fn change_access_control(origin: OriginFor<T>, group_id: GroupId, who: T::AccountId, access_control: AccessControl) -> DispatchResult {
+      let admin = ensure_signed(origin)?;
+
+      Self::set_access_control(&admin, &who, &group_id, &access_control)?;
+
+      Ok(())
+    }
+
+    /// Hand the group over to another member, who becomes a `SuperAdmin`. The previous owner keeps its
+    /// `SuperAdmin` access until the new owner changes it.
+    #[pallet::weight(T::WeightInfo::transfer_group_ownership())]
+    pub fn transfer_group_ownership(origin: OriginFor<T>, group_id: GroupId, new_owner: T::AccountId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::transfer_ownership(&owner, &new_owner, &group_id)?;
+
+      Ok(())
+    }
+
+    /// Dissolve a group and its sub-groups, removing every membership, join request and invitation
+    /// attached to them.
+    #[pallet::weight(T::WeightInfo::dissolve_group(MAX_CASCADE_DELETE as u32))]
+    pub fn dissolve_group(origin: OriginFor<T>, group_id: GroupId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      Self::delete_group(&owner, &group_id)?;
+
+      Ok(())
+    }
+
+    /// Let `guardian` act on behalf of the signer until `expiry`. Signing this is the consent of the ward.
+    #[pallet::weight(T::WeightInfo::add_guardian())]
+    pub fn add_guardian(origin: OriginFor<T>, guardian: T::AccountId, expiry: T::BlockNumber) -> DispatchResult {
+      let ward = ensure_signed(origin)?;
+
+      Self::establish_guardianship(&ward, &ward, &guardian, &expiry)?;
+
+      Ok(())
+    }
+
+    /// Appoint a guardian for a ward who cannot consent on its own, such as a minor.
+    #[pallet::weight(T::WeightInfo::appoint_guardian())]
+    pub fn appoint_guardian(origin: OriginFor<T>, ward: T::AccountId, guardian: T::AccountId, expiry: T::BlockNumber) -> DispatchResult {
+      let custodian = ensure_signed(origin)?;
+
+      Self::ensure_custodian(&custodian)?;
+      Self::establish_guardianship(&custodian, &ward, &guardian, &expiry)?;
+
+      Ok(())
+    }
+
+    /// End a guardianship. The guardian and the appointer can always end it; the ward can only end the
+    /// guardianships it established itself.
+    #[pallet::weight(T::WeightInfo::remove_guardian())]
+    pub fn remove_guardian(origin: OriginFor<T>, ward: T::AccountId, guardian: T::AccountId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::end_guardianship(&who, &ward, &guardian)?;
+
+      Ok(())
+    }
+
+    /// Dispatch `call` with the ward as the signed origin. Only the calls allowed by `GuardianFilter`
+    /// can be dispatched this way.
+    #[pallet::weight({
+      let dispatch_info = call.get_dispatch_info();
+      (dispatch_info.weight.saturating_add(T::WeightInfo::act_for_ward()), dispatch_info.class)
+    })]
+    pub fn act_for_ward(origin: OriginFor<T>, ward: T::AccountId, call: Box<<T as Config>::Call>) -> DispatchResult {
+      let guardian = ensure_signed(origin)?;
+
+      Self::ensure_guardian(&guardian, &ward)?;
+      ensure!(T::GuardianFilter::contains(&call), Error::<T>::CallNotAllowed);
+
+      let result = call.dispatch(frame_system::RawOrigin::Signed(ward.clone()).into());
+
+      Self::deposit_event(Event::ActedForWard(ward, guardian, result.map(|_| ()).map_err(|e| e.error)));
+
+      Ok(())
+    }
+
+    /// Set up the organization of the signing `Enterprise` or `HealthFacility` account.
+    #[pallet::weight(T::WeightInfo::create_organization())]
+    pub fn create_organization(origin: OriginFor<T>, info: AccountInfo) -> DispatchResult {
+      let organization = ensure_signed(origin)?;
+
+      Self::do_create_organization(&organization, &info)?;
+
+      Ok(())
+    }
+
+    /// Add an admin or an employee to an organization. Only the organization account and its admins
+    /// can add members.
+    #[pallet::weight(T::WeightInfo::add_organization_member())]
+    pub fn add_organization_member(origin: OriginFor<T>, organization: T::AccountId, who: T::AccountId, role: OrganizationRole) -> DispatchResult {
+      let admin = ensure_signed(origin)?;
+
+      Self::do_add_organization_member(&admin, &organization, &who, &role)?;
+
+      Ok(())
+    }
+
+    /// Offboard a member from an organization. Everything the member could do on behalf of the
+    /// organization is revoked at once. Members can also offboard themselves.
+    #[pallet::weight(T::WeightInfo::remove_organization_member())]
+    pub fn remove_organization_member(origin: OriginFor<T>, organization: T::AccountId, who: T::AccountId) -> DispatchResult {
+      let admin = ensure_signed(origin)?;
+
+      Self::do_remove_organization_member(&admin, &organization, &who)?;
+
+      Ok(())
+    }
+
+    /// Register a trusted issuer, such as a licensing board. Only custodians can register issuers.
+    #[pallet::weight(T::WeightInfo::register_issuer())]
+    pub fn register_issuer(origin: OriginFor<T>, issuer: T::AccountId, country: Country, info: IssuerInfo) -> DispatchResult {
+      let custodian = ensure_signed(origin)?;
+
+      Self::add_issuer(&custodian, &issuer, &country, &info)?;
+
+      Ok(())
+    }
+
+    /// Deregister an issuer. The credentials it attested stop being valid at once.
+    #[pallet::weight(T::WeightInfo::deregister_issuer())]
+    pub fn deregister_issuer(origin: OriginFor<T>, issuer: T::AccountId) -> DispatchResult {
+      let custodian = ensure_signed(origin)?;
+
+      Self::remove_issuer(&custodian, &issuer)?;
+
+      Ok(())
+    }
+
+    /// Attest a credential for `holder`, replacing any credential of the same kind it already holds.
+    #[pallet::weight(T::WeightInfo::issue_credential())]
+    pub fn issue_credential(
+      origin: OriginFor<T>,
+      holder: T::AccountId,
+      kind: CredentialKind,
+      specialty: Option<TherapeuticArea>,
+      expiry: T::BlockNumber,
+    ) -> DispatchResult {
+      let issuer = ensure_signed(origin)?;
+
+      Self::issue(&issuer, &holder, &kind, &specialty, &expiry)?;
+
+      Ok(())
+    }
+
+    /// Revoke a credential. Either the issuer of the credential or a custodian can revoke it.
+    #[pallet::weight(T::WeightInfo::revoke_credential())]
+    pub fn revoke_credential(origin: OriginFor<T>, holder: T::AccountId, kind: CredentialKind) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::revoke(&who, &holder, &kind)?;
+
+      Ok(())
+    }
+
+    /// Open a direct messaging channel with `other`. Both ends have to be connected, or both have
+    /// to be members of `group`.
+    #[pallet::weight(T::WeightInfo::open_channel())]
+    pub fn open_channel(origin: OriginFor<T>, other: T::AccountId, group: Option<GroupId>) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_open_channel(&who, &other, &group)?;
+
+      Ok(())
+    }
+
+    /// Close a channel, dropping the on-chain trace of its messages. Either end can close it.
+    #[pallet::weight(T::WeightInfo::close_channel(MAX_CHANNEL_MESSAGES))]
+    pub fn close_channel(origin: OriginFor<T>, other: T::AccountId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_close_channel(&who, &other)?;
+
+      Ok(())
+    }
+
+    /// Send a payload encrypted to the recipient's registered key. Only its hash is kept on chain,
+    /// and a channel holds at most `MAX_CHANNEL_MESSAGES` messages.
+    #[pallet::weight(T::WeightInfo::send_message(payload.len() as u32))]
+    pub fn send_message(origin: OriginFor<T>, to: T::AccountId, payload: EncryptedMessage) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_send_message(&who, &to, &payload)?;
+
+      Ok(())
+    }
+
+    /// Mark the first `read` messages `from` sent over the channel as read.
+    #[pallet::weight(T::WeightInfo::mark_read())]
+    pub fn mark_read(origin: OriginFor<T>, from: T::AccountId, read: u32) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_mark_read(&who, &from, read)?;
+
+      Ok(())
+    }
+
+    /// Set the contacts that can recover the signer, replacing any previous ones. Contacts have to
+    /// be connected to the signer, or be custodians.
+    #[pallet::weight(T::WeightInfo::set_recovery_contacts(contacts.len() as u32))]
+    pub fn set_recovery_contacts(origin: OriginFor<T>, contacts: Vec<T::AccountId>, threshold: u32) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::configure_recovery(&who, &contacts, threshold)?;
+
+      Ok(())
+    }
+
+    #[pallet::weight(T::WeightInfo::remove_recovery_contacts())]
+    pub fn remove_recovery_contacts(origin: OriginFor<T>) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::remove_recovery_config(&who)?;
+
+      Ok(())
+    }
+
+    /// Vouch that `new` is the new key of `lost`. Once enough contacts vouched, `new` can claim the
+    /// account after `RecoveryDelay`.
+    #[pallet::weight(T::WeightInfo::vouch_recovery())]
+    pub fn vouch_recovery(origin: OriginFor<T>, lost: T::AccountId, new: T::AccountId) -> DispatchResult {
+      let contact = ensure_signed(origin)?;
+
+      Self::vouch(&contact, &lost, &new)?;
+
+      Ok(())
+    }
+
+    /// Cancel every recovery of the signer, which proves the key was not lost after all.
+    #[pallet::weight(T::WeightInfo::cancel_recovery())]
+    pub fn cancel_recovery(origin: OriginFor<T>) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_cancel_recovery(&who)?;
+
+      Ok(())
+    }
+
+    /// Move the account details, connections, group memberships, guardianships, credentials,
+    /// organization and registries of `lost` to the signer, once the recovery delay passed. The
+    /// channels of `lost` are closed.
+    #[pallet::weight(T::WeightInfo::claim_recovery())]
+    pub fn claim_recovery(origin: OriginFor<T>, lost: T::AccountId) -> DispatchResult {
+      let new = ensure_signed(origin)?;
+
+      Self::recover(&new, &lost)?;
 
       Ok(())
     }
@@ -1,6 +1,7 @@
 use crate as pallet_template;
 use frame_support::parameter_types;
 use frame_system as system;
+use pallet_key_registry::{traits::KeyProvider, types::EncryptionKey};
 use sp_core::H256;
 use sp_runtime::{
   testing::Header,
@@ -18,6 +19,7 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
         TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
     }
 );
@@ -25,6 +27,15 @@ frame_support::construct_runtime!(
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const SS58Prefix: u8 = 42;
+    pub const MembershipExpiry: u64 = 10;
+    pub const MaxGuardianshipPeriod: u64 = 100;
+    pub const RecoveryDelay: u64 = 5;
+    pub const ExistentialDeposit: u64 = 1;
+    pub const AccountDeposit: u64 = 10;
+    pub const ConnectionDeposit: u64 = 1;
+    pub const GroupDeposit: u64 = 5;
+    pub const MemberDeposit: u64 = 2;
+    pub const JoinRequestDeposit: u64 = 3;
 }
 
 impl system::Config for Test {
@@ -45,7 +56,7 @@ impl system::Config for Test {
   type BlockHashCount = BlockHashCount;
   type Version = ();
   type PalletInfo = PalletInfo;
-  type AccountData = ();
+  type AccountData = pallet_balances::AccountData<u64>;
   type OnNewAccount = ();
   type OnKilledAccount = ();
   type SystemWeightInfo = ();
@@ -53,11 +64,61 @@ impl system::Config for Test {
   type OnSetCode = ();
 }
 
+impl pallet_balances::Config for Test {
+  type MaxLocks = ();
+  type MaxReserves = ();
+  type ReserveIdentifier = [u8; 8];
+  type Balance = u64;
+  type Event = Event;
+  type DustRemoval = ();
+  type ExistentialDeposit = ExistentialDeposit;
+  type AccountStore = System;
+  type WeightInfo = ();
+}
+
 impl pallet_template::Config for Test {
   type Event = Event;
+  type ConnectionRules = ();
+  type MembershipExpiry = MembershipExpiry;
+  type Call = Call;
+  type GuardianFilter = frame_support::traits::Everything;
+  type MaxGuardianshipPeriod = MaxGuardianshipPeriod;
+  type Keys = MockKeys;
+  type OnAccountRecovered = ();
+  type RecoveryDelay = RecoveryDelay;
+  type Currency = Balances;
+  type AccountDeposit = AccountDeposit;
+  type ConnectionDeposit = ConnectionDeposit;
+  type GroupDeposit = GroupDeposit;
+  type MemberDeposit = MemberDeposit;
+  type JoinRequestDeposit = JoinRequestDeposit;
+  type WeightInfo = ();
+}
+
+/// Accounts 100 to 199 have not registered an encryption key.
+pub struct MockKeys;
+
+impl KeyProvider<u64> for MockKeys {
+  fn encryption_key_of(who: &u64) -> Option<EncryptionKey> {
+    if (100..200).contains(who) {
+      None
+    } else {
+      Some([*who as u8; 32])
+    }
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn register_encryption_key(_who: &u64, _key: EncryptionKey) {}
 }
 
 // Build genesis storage according to the mock runtime.
+/// Accounts 0 to 249 are endowed; the others cannot afford any deposit.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-  system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+  let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+  pallet_balances::GenesisConfig::<Test> {
+    balances: (0..250).map(|who| (who, 1_000)).collect(),
+  }
+  .assimilate_storage(&mut storage)
+  .unwrap();
+  storage.into()
 }
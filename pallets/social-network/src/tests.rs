@@ -1,20 +1,1555 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+  constants::MAX_EXPIRIES_PER_BLOCK,
+  mock::*,
+  traits::{CredentialProvider, CustodianProvider, GroupMembership, OrganizationProvider, RoleProvider},
+  types::{
+    AccessControl, AccountDetail, AccountStatus, CredentialKind, Deposit, EncryptedMessage, GroupId, JoinPolicy, OrganizationRole,
+    PendingMemberships, Relation, Roles, Visibility,
+  },
+  Accounts, Error, MembershipExpiries,
+};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use healthcare::{Role, TherapeuticArea};
+use region::{Country, Region, SubRegion};
+
+fn group_id(id: &[u8]) -> GroupId {
+  id.to_vec().try_into().unwrap()
+}
+
+fn live_account(who: u64) {
+  Accounts::<Test>::insert(
+    who,
+    AccountDetail {
+      issuer: 0,
+      freezer: None,
+      status: AccountStatus::Live,
+      roles: vec![Role::Patient].try_into().unwrap(),
+      enterprise: None,
+      info: Default::default(),
+    },
+  );
+}
+
+fn care_team(owner: u64) -> GroupId {
+  let id = group_id(b"care-team");
+
+  live_account(owner);
+  assert_ok!(TemplateModule::do_create_group(
+    &owner,
+    &id,
+    &Default::default(),
+    &Country::Netherlands,
+    &None,
+    &Visibility::Public,
+    &JoinPolicy::RequestApproval
+  ));
+
+  id
+}
+
+/// Let `who` in a group as `access_control`, through an invitation of `admin`.
+fn joined(admin: u64, who: u64, group_id: &GroupId, access_control: AccessControl) {
+  assert_ok!(TemplateModule::invite(Origin::signed(admin), group_id.clone(), who, access_control));
+  assert_ok!(TemplateModule::accept_invitation(Origin::signed(who), group_id.clone()));
+}
+
+#[test]
+fn join_request_is_approved_by_admin() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = care_team(1);
+    live_account(2);
+
+    assert_ok!(TemplateModule::request_join(Origin::signed(2), id.clone()));
+    assert_eq!(TemplateModule::join_requests(&id, 2), Some(11));
+    assert_noop!(TemplateModule::request_join(Origin::signed(2), id.clone()), Error::<Test>::AlreadyJoining);
+
+    assert_noop!(
+      TemplateModule::approve_join(Origin::signed(2), id.clone(), 2, AccessControl::ReadOnly),
+      Error::<Test>::NeverJoining
+    );
+    assert_ok!(TemplateModule::approve_join(Origin::signed(1), id.clone(), 2, AccessControl::ReadOnly));
+
+    assert_eq!(TemplateModule::join_requests(&id, 2), None);
+    assert_eq!(TemplateModule::group_members(&id, 2), Some(AccessControl::ReadOnly));
+    let group = TemplateModule::groups(&id).unwrap();
+    assert_eq!((group.members, group.admins), (2, 1));
+    System::assert_last_event(Event::TemplateModule(crate::Event::JoinApproved(id, 2, 1)));
+  });
+}
+
+#[test]
+fn expired_join_request_cannot_be_approved() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = care_team(1);
+    live_account(2);
+
+    assert_ok!(TemplateModule::request_join(Origin::signed(2), id.clone()));
+    System::set_block_number(12);
+
+    assert_noop!(
+      TemplateModule::approve_join(Origin::signed(1), id.clone(), 2, AccessControl::ReadOnly),
+      Error::<Test>::MembershipExpired
+    );
+    // An expired request can be renewed.
+    assert_ok!(TemplateModule::request_join(Origin::signed(2), id.clone()));
+    assert_eq!(TemplateModule::join_requests(&id, 2), Some(22));
+  });
+}
+
+#[test]
+fn invited_account_joins_only_after_accepting() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = care_team(1);
+    live_account(2);
+
+    assert_ok!(TemplateModule::invite(Origin::signed(1), id.clone(), 2, AccessControl::Admin));
+    assert_eq!(TemplateModule::group_members(&id, 2), None);
+    assert_noop!(
+      TemplateModule::invite(Origin::signed(1), id.clone(), 2, AccessControl::Admin),
+      Error::<Test>::AlreadyInvited
+    );
+
+    assert_ok!(TemplateModule::accept_invitation(Origin::signed(2), id.clone()));
+
+    assert_eq!(TemplateModule::invitations(&id, 2), None);
+    assert_eq!(TemplateModule::group_members(&id, 2), Some(AccessControl::Admin));
+    let group = TemplateModule::groups(&id).unwrap();
+    assert_eq!((group.members, group.admins), (2, 2));
+    System::assert_last_event(Event::TemplateModule(crate::Event::InvitationAccepted(id, 2)));
+  });
+}
+
+#[test]
+fn declined_invitation_leaves_group_untouched() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = care_team(1);
+    live_account(2);
+
+    assert_ok!(TemplateModule::invite(Origin::signed(1), id.clone(), 2, AccessControl::ReadWrite));
+    assert_ok!(TemplateModule::decline_invitation(Origin::signed(2), id.clone()));
+
+    assert_noop!(
+      TemplateModule::accept_invitation(Origin::signed(2), id.clone()),
+      Error::<Test>::NeverInvited
+    );
+    assert_eq!(TemplateModule::groups(&id).unwrap().members, 1);
+  });
+}
+
+#[test]
+fn only_super_admins_let_others_in_as_super_admin() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = care_team(1);
+    live_account(2);
+    live_account(3);
+    assert_ok!(TemplateModule::invite(Origin::signed(1), id.clone(), 2, AccessControl::Admin));
+    assert_ok!(TemplateModule::accept_invitation(Origin::signed(2), id.clone()));
+
+    assert_noop!(
+      TemplateModule::invite(Origin::signed(2), id.clone(), 3, AccessControl::SuperAdmin),
+      Error::<Test>::OnlySuperAdminAllowed
+    );
+    assert_ok!(TemplateModule::request_join(Origin::signed(3), id.clone()));
+    assert_noop!(
+      TemplateModule::approve_join(Origin::signed(2), id.clone(), 3, AccessControl::SuperAdmin),
+      Error::<Test>::OnlySuperAdminAllowed
+    );
+
+    assert_ok!(TemplateModule::approve_join(Origin::signed(1), id.clone(), 3, AccessControl::SuperAdmin));
+    assert_eq!(TemplateModule::group_members(&id, 3), Some(AccessControl::SuperAdmin));
+  });
+}
+
+#[test]
+fn lapsed_join_requests_and_invitations_are_removed() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = care_team(1);
+    live_account(2);
+    live_account(3);
+
+    assert_ok!(TemplateModule::request_join(Origin::signed(2), id.clone()));
+    assert_ok!(TemplateModule::invite(Origin::signed(1), id.clone(), 3, AccessControl::ReadOnly));
+
+    // Both can still be accepted at their expiry block.
+    TemplateModule::on_initialize(11);
+    assert_eq!(TemplateModule::join_requests(&id, 2), Some(11));
+    assert!(TemplateModule::invitations(&id, 3).is_some());
+
+    TemplateModule::on_initialize(12);
+    assert_eq!(TemplateModule::join_requests(&id, 2), None);
+    assert_eq!(TemplateModule::invitations(&id, 3), None);
+  });
+}
+
+#[test]
+fn lapses_are_pushed_back_when_their_block_is_full() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = care_team(1);
+    live_account(2);
+    let full: Vec<(GroupId, u64)> = (0..MAX_EXPIRIES_PER_BLOCK as u64).map(|who| (group_id(b"other"), 1_000 + who)).collect();
+    MembershipExpiries::<Test>::insert(12, PendingMemberships::<u64>::try_from(full).unwrap());
+
+    assert_ok!(TemplateModule::request_join(Origin::signed(2), id.clone()));
+    assert_eq!(MembershipExpiries::<Test>::get(13).into_inner(), vec![(id.clone(), 2)]);
+
+    // The request lapsed all the same, and cannot be approved anymore.
+    System::set_block_number(12);
+    TemplateModule::on_initialize(12);
+    assert_noop!(
+      TemplateModule::approve_join(Origin::signed(1), id.clone(), 2, AccessControl::ReadOnly),
+      Error::<Test>::MembershipExpired
+    );
+    TemplateModule::on_initialize(13);
+    assert_eq!(TemplateModule::join_requests(&id, 2), None);
+  });
+}
+
+#[test]
+fn join_requests_hold_a_deposit_until_settled() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = care_team(1);
+    live_account(2);
+    live_account(300);
+
+    assert_noop!(
+      TemplateModule::request_join(Origin::signed(300), id.clone()),
+      Error::<Test>::InsufficientDeposit
+    );
+
+    assert_ok!(TemplateModule::request_join(Origin::signed(2), id.clone()));
+    assert_eq!(Balances::reserved_balance(2), JoinRequestDeposit::get());
+    assert_ok!(TemplateModule::cancel_join_request(Origin::signed(2), id.clone()));
+    assert_eq!(Balances::reserved_balance(2), 0);
+
+    assert_ok!(TemplateModule::request_join(Origin::signed(2), id.clone()));
+    assert_ok!(TemplateModule::reject_join(Origin::signed(1), id.clone(), 2));
+    assert_eq!(Balances::reserved_balance(2), 0);
+
+    // Renewing a lapsed request keeps a single deposit.
+    assert_ok!(TemplateModule::request_join(Origin::signed(2), id.clone()));
+    System::set_block_number(12);
+    assert_ok!(TemplateModule::request_join(Origin::signed(2), id.clone()));
+    assert_eq!(Balances::reserved_balance(2), JoinRequestDeposit::get());
+
+    TemplateModule::on_initialize(23);
+    assert_eq!(Balances::reserved_balance(2), 0);
+
+    System::set_block_number(23);
+    assert_ok!(TemplateModule::request_join(Origin::signed(2), id.clone()));
+    assert_ok!(TemplateModule::approve_join(Origin::signed(1), id.clone(), 2, AccessControl::ReadOnly));
+    assert_eq!(Balances::reserved_balance(2), MemberDeposit::get());
+  });
+}
+
+#[test]
+fn promoting_and_demoting_keeps_admins_in_sync() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = care_team(1);
+    live_account(2);
+    joined(1, 2, &id, AccessControl::ReadOnly);
+
+    assert_ok!(TemplateModule::change_access_control(
+      Origin::signed(1),
+      id.clone(),
+      2,
+      AccessControl::Admin
+    ));
+    assert_eq!(TemplateModule::groups(&id).unwrap().admins, 2);
+
+    // Only a `SuperAdmin` can hand out `SuperAdmin`.
+    assert_noop!(
+      TemplateModule::change_access_control(Origin::signed(2), id.clone(), 2, AccessControl::SuperAdmin),
+      Error::<Test>::OnlySuperAdminAllowed
+    );
+
+    assert_ok!(TemplateModule::change_access_control(
+      Origin::signed(1),
+      id.clone(),
+      2,
+      AccessControl::ReadWrite
+    ));
+    let group = TemplateModule::groups(&id).unwrap();
+    assert_eq!((group.members, group.admins), (2, 1));
+    System::assert_last_event(Event::TemplateModule(crate::Event::RoleChanged(id, 2, AccessControl::ReadWrite)));
+  });
+}
+
+#[test]
+fn owner_can_never_be_demoted_or_removed() {
+  new_test_ext().execute_with(|| {
+    let id = care_team(1);
+    live_account(2);
+    joined(1, 2, &id, AccessControl::SuperAdmin);
+
+    assert_noop!(
+      TemplateModule::change_access_control(Origin::signed(2), id.clone(), 1, AccessControl::ReadOnly),
+      Error::<Test>::LastSuperAdmin
+    );
+    assert_noop!(
+      TemplateModule::remove_member(Origin::signed(2), id.clone(), 1),
+      Error::<Test>::LastSuperAdmin
+    );
+  });
+}
+
+#[test]
+fn ownership_is_transferred_to_a_member() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = care_team(1);
+    live_account(2);
+    joined(1, 2, &id, AccessControl::ReadWrite);
+
+    assert_noop!(
+      TemplateModule::transfer_group_ownership(Origin::signed(2), id.clone(), 2),
+      Error::<Test>::OnlyOwnerAllowed
+    );
+    assert_ok!(TemplateModule::transfer_group_ownership(Origin::signed(1), id.clone(), 2));
+
+    let group = TemplateModule::groups(&id).unwrap();
+    assert_eq!((group.owner, group.admins), (2, 2));
+    assert_eq!(TemplateModule::group_members(&id, 2), Some(AccessControl::SuperAdmin));
+
+    // The previous owner is now an ordinary `SuperAdmin` and can be removed by the new owner.
+    assert_ok!(TemplateModule::remove_member(Origin::signed(2), id.clone(), 1));
+    let group = TemplateModule::groups(&id).unwrap();
+    assert_eq!((group.members, group.admins), (1, 1));
+  });
+}
+
+#[test]
+fn deleting_a_group_clears_its_access_controls() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = care_team(1);
+    live_account(2);
+    live_account(3);
+    joined(1, 2, &id, AccessControl::ReadOnly);
+    assert_ok!(TemplateModule::invite(Origin::signed(1), id.clone(), 3, AccessControl::ReadOnly));
+
+    assert_noop!(
+      TemplateModule::dissolve_group(Origin::signed(2), id.clone()),
+      Error::<Test>::OnlyOwnerAllowed
+    );
+    assert_ok!(TemplateModule::dissolve_group(Origin::signed(1), id.clone()));
+
+    assert_eq!(TemplateModule::groups(&id), None);
+    assert_eq!(TemplateModule::group_members(&id, 1), None);
+    assert_eq!(TemplateModule::group_members(&id, 2), None);
+    assert_eq!(TemplateModule::invitations(&id, 3), None);
+    System::assert_last_event(Event::TemplateModule(crate::Event::GroupDeleted(id, 1)));
+  });
+}
+
+#[test]
+fn guardian_signs_allowed_calls_for_the_ward() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = care_team(1);
+    live_account(2);
+    live_account(3);
+
+    assert_ok!(TemplateModule::add_guardian(Origin::signed(2), 3, 50));
+    assert_ok!(TemplateModule::act_for_ward(
+      Origin::signed(3),
+      2,
+      Box::new(Call::TemplateModule(crate::Call::request_join { group_id: id.clone() }))
+    ));
+
+    assert_eq!(TemplateModule::join_requests(&id, 2), Some(11));
+    assert_eq!(TemplateModule::join_requests(&id, 3), None);
+    System::assert_last_event(Event::TemplateModule(crate::Event::ActedForWard(2, 3, Ok(()))));
+  });
+}
+
+#[test]
+fn guardianship_is_time_bounded() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    live_account(2);
+    live_account(3);
+
+    assert_noop!(TemplateModule::add_guardian(Origin::signed(2), 3, 500), Error::<Test>::InvalidExpiry);
+    assert_ok!(TemplateModule::add_guardian(Origin::signed(2), 3, 50));
+    assert_event(crate::Event::GuardianAdded(2, 3, 50));
+
+    System::set_block_number(51);
+    assert_noop!(
+      TemplateModule::act_for_ward(
+        Origin::signed(3),
+        2,
+        Box::new(Call::TemplateModule(crate::Call::leave_group {
+          group_id: group_id(b"care-team")
+        }))
+      ),
+      Error::<Test>::GuardianshipExpired
+    );
+  });
+}
+
+#[test]
+fn only_custodians_appoint_guardians_and_wards_cannot_remove_them() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    live_account(2);
+    live_account(3);
+    assert_ok!(TemplateModule::add_custodian(&9));
+
+    assert_noop!(
+      TemplateModule::appoint_guardian(Origin::signed(4), 2, 3, 50),
+      Error::<Test>::OnlyCustodianAllowed
+    );
+    assert_ok!(TemplateModule::appoint_guardian(Origin::signed(9), 2, 3, 50));
+    assert_event(crate::Event::GuardianAdded(2, 3, 50));
+
+    assert_noop!(TemplateModule::remove_guardian(Origin::signed(2), 2, 3), Error::<Test>::NotGuardian);
+    assert_ok!(TemplateModule::remove_guardian(Origin::signed(9), 2, 3));
+    assert_event(crate::Event::GuardianRemoved(2, 3));
+    assert_eq!(TemplateModule::guardians(2, 3), None);
+  });
+}
+
+#[test]
+fn credentials_are_valid_only_while_unexpired_and_issuer_registered() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    live_account(2);
+    assert_ok!(TemplateModule::add_custodian(&9));
+
+    assert_noop!(
+      TemplateModule::register_issuer(Origin::signed(4), 5, Country::Netherlands, Default::default()),
+      Error::<Test>::OnlyCustodianAllowed
+    );
+    assert_ok!(TemplateModule::register_issuer(
+      Origin::signed(9),
+      5,
+      Country::Netherlands,
+      Default::default()
+    ));
+    assert_event(crate::Event::IssuerRegistered(5, 9));
+    assert_ok!(TemplateModule::issue_credential(
+      Origin::signed(5),
+      2,
+      CredentialKind::MedicalLicense,
+      Some(TherapeuticArea::Cardiovascular),
+      20
+    ));
+    assert_event(crate::Event::CredentialIssued(2, CredentialKind::MedicalLicense, 5));
+
+    assert!(<TemplateModule as CredentialProvider<u64>>::has_credential(
+      &2,
+      &CredentialKind::MedicalLicense
+    ));
+    assert!(!<TemplateModule as CredentialProvider<u64>>::has_credential(
+      &2,
+      &CredentialKind::NursingLicense
+    ));
+    assert_eq!(
+      TemplateModule::credentials(2, CredentialKind::MedicalLicense).unwrap().country,
+      Country::Netherlands
+    );
+
+    System::set_block_number(21);
+    assert!(!<TemplateModule as CredentialProvider<u64>>::has_credential(
+      &2,
+      &CredentialKind::MedicalLicense
+    ));
+
+    System::set_block_number(1);
+    assert_ok!(TemplateModule::deregister_issuer(Origin::signed(9), 5));
+    assert_event(crate::Event::IssuerDeregistered(5, 9));
+    assert!(!<TemplateModule as CredentialProvider<u64>>::has_credential(
+      &2,
+      &CredentialKind::MedicalLicense
+    ));
+  });
+}
+
+#[test]
+fn credentials_are_revoked_by_issuer_or_custodian() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    live_account(2);
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_ok!(TemplateModule::register_issuer(
+      Origin::signed(9),
+      5,
+      Country::Netherlands,
+      Default::default()
+    ));
+    assert_ok!(TemplateModule::issue_credential(
+      Origin::signed(5),
+      2,
+      CredentialKind::MedicalLicense,
+      None,
+      20
+    ));
+
+    assert_noop!(
+      TemplateModule::revoke_credential(Origin::signed(2), 2, CredentialKind::MedicalLicense),
+      Error::<Test>::OnlyCustodianAllowed
+    );
+    assert_ok!(TemplateModule::revoke_credential(Origin::signed(5), 2, CredentialKind::MedicalLicense));
+
+    assert!(!<TemplateModule as CredentialProvider<u64>>::has_credential(
+      &2,
+      &CredentialKind::MedicalLicense
+    ));
+    System::assert_last_event(Event::TemplateModule(crate::Event::CredentialRevoked(
+      2,
+      CredentialKind::MedicalLicense,
+      5,
+    )));
+  });
+}
+
+fn roles(roles: Vec<Role>) -> Roles {
+  roles.try_into().unwrap()
+}
+
+#[test]
+fn custodians_onboard_accounts_with_roles() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    assert_ok!(TemplateModule::add_custodian(&9));
+
+    assert_noop!(
+      TemplateModule::onboard_account(Origin::signed(4), 2, roles(vec![Role::Patient]), None, Default::default()),
+      Error::<Test>::OnlyCustodianAllowed
+    );
+    assert_noop!(
+      TemplateModule::onboard_account(Origin::signed(9), 2, roles(vec![Role::Patient, Role::Patient]), None, Default::default()),
+      Error::<Test>::InvalidRoles
+    );
+    assert_ok!(TemplateModule::onboard_account(
+      Origin::signed(9),
+      2,
+      roles(vec![Role::Patient, Role::Researcher]),
+      None,
+      Default::default()
+    ));
+
+    assert!(<TemplateModule as RoleProvider<u64>>::has_role(&2, &Role::Researcher));
+    assert_eq!(TemplateModule::accounts(2).unwrap().issuer, 9);
+    System::assert_last_event(Event::TemplateModule(crate::Event::AccountOnboarded(2, 9)));
+  });
+}
+
+#[test]
+fn enterprise_employees_are_linked_to_an_enterprise() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_ok!(TemplateModule::onboard_account(
+      Origin::signed(9),
+      5,
+      roles(vec![Role::Enterprise]),
+      None,
+      Default::default()
+    ));
+    assert_ok!(TemplateModule::onboard_account(
+      Origin::signed(9),
+      6,
+      roles(vec![Role::Patient]),
+      None,
+      Default::default()
+    ));
+
+    assert_noop!(
+      TemplateModule::onboard_account(Origin::signed(9), 2, roles(vec![Role::EnterpriseEmployee]), None, Default::default()),
+      Error::<Test>::InvalidRoles
+    );
+    assert_noop!(
+      TemplateModule::onboard_account(Origin::signed(9), 2, roles(vec![Role::EnterpriseEmployee]), Some(6), Default::default()),
+      Error::<Test>::InvalidRoles
+    );
+    assert_ok!(TemplateModule::onboard_account(
+      Origin::signed(9),
+      2,
+      roles(vec![Role::EnterpriseEmployee]),
+      Some(5),
+      Default::default()
+    ));
+
+    assert_eq!(<TemplateModule as RoleProvider<u64>>::enterprise_of(&2), Some(5));
+  });
+}
+
+#[test]
+fn role_changes_need_custodian_approval() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_ok!(TemplateModule::onboard_account(
+      Origin::signed(9),
+      2,
+      roles(vec![Role::Patient]),
+      None,
+      Default::default()
+    ));
+
+    assert_ok!(TemplateModule::request_role_change(
+      Origin::signed(2),
+      roles(vec![Role::Patient, Role::MedicalProfessional]),
+      None
+    ));
+    assert_event(crate::Event::RoleChangeRequested(2));
+    assert!(!<TemplateModule as RoleProvider<u64>>::has_role(&2, &Role::MedicalProfessional));
+
+    assert_noop!(
+      TemplateModule::approve_role_change(Origin::signed(2), 2),
+      Error::<Test>::OnlyCustodianAllowed
+    );
+    assert_ok!(TemplateModule::approve_role_change(Origin::signed(9), 2));
+    assert_event(crate::Event::RoleChangeApproved(2, 9));
+
+    assert!(<TemplateModule as RoleProvider<u64>>::has_role(&2, &Role::MedicalProfessional));
+    assert_eq!(TemplateModule::role_change_requests(2), None);
+
+    assert_ok!(TemplateModule::request_role_change(Origin::signed(2), roles(vec![Role::Patient]), None));
+    assert_ok!(TemplateModule::reject_role_change(Origin::signed(9), 2));
+    assert_event(crate::Event::RoleChangeRejected(2, 9));
+    assert!(<TemplateModule as RoleProvider<u64>>::has_role(&2, &Role::MedicalProfessional));
+  });
+}
+
+#[test]
+fn offboarded_employees_can_no_longer_act_for_the_organization() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_ok!(TemplateModule::onboard_account(
+      Origin::signed(9),
+      5,
+      roles(vec![Role::HealthFacility]),
+      None,
+      Default::default()
+    ));
+    live_account(2);
+    live_account(3);
+
+    assert_noop!(
+      TemplateModule::create_organization(Origin::signed(2), Default::default()),
+      Error::<Test>::InvalidRoles
+    );
+    assert_ok!(TemplateModule::create_organization(Origin::signed(5), Default::default()));
+    assert_event(crate::Event::OrganizationCreated(5));
+    assert_ok!(TemplateModule::add_organization_member(Origin::signed(5), 5, 2, OrganizationRole::Admin));
+    assert_event(crate::Event::OrganizationMemberAdded(5, 2, OrganizationRole::Admin));
+    assert_ok!(TemplateModule::add_organization_member(
+      Origin::signed(2),
+      5,
+      3,
+      OrganizationRole::Employee
+    ));
+
+    assert!(<TemplateModule as OrganizationProvider<u64>>::can_act_for(&5, &3));
+    let organization = TemplateModule::organizations(5).unwrap();
+    assert_eq!((organization.members, organization.admins), (2, 1));
+
+    assert_noop!(
+      TemplateModule::remove_organization_member(Origin::signed(3), 5, 2),
+      Error::<Test>::OnlyOrganizationAdminAllowed
+    );
+    assert_ok!(TemplateModule::remove_organization_member(Origin::signed(2), 5, 3));
+
+    assert!(!<TemplateModule as OrganizationProvider<u64>>::can_act_for(&5, &3));
+    System::assert_last_event(Event::TemplateModule(crate::Event::OrganizationMemberRemoved(5, 3)));
+  });
+}
+
+fn sub_group(owner: u64, id: &[u8], parent: &GroupId) -> GroupId {
+  let id = group_id(id);
+
+  assert_ok!(TemplateModule::create_group(
+    Origin::signed(owner),
+    id.clone(),
+    Default::default(),
+    Country::Netherlands,
+    Some(parent.clone()),
+    Visibility::Public,
+    JoinPolicy::RequestApproval
+  ));
+
+  id
+}
+
+#[test]
+fn parent_admins_are_admins_of_sub_groups_unless_overridden() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let hospital = care_team(1);
+    live_account(2);
+    live_account(3);
+    joined(1, 2, &hospital, AccessControl::Admin);
+    joined(1, 3, &hospital, AccessControl::ReadOnly);
+
+    let department = sub_group(2, b"cardiology", &hospital);
+    let team = sub_group(2, b"heart-failure", &department);
+
+    assert_eq!(TemplateModule::access_control_of(&team, &1), Some(AccessControl::Admin));
+    assert_eq!(TemplateModule::access_control_of(&team, &3), None);
+    assert!(TemplateModule::can_read(&team, &1));
+    assert!(!TemplateModule::can_read(&team, &3));
+    assert_ok!(TemplateModule::invite(Origin::signed(1), team.clone(), 3, AccessControl::ReadWrite));
+
+    joined(2, 1, &department, AccessControl::ReadOnly);
+    assert_eq!(TemplateModule::access_control_of(&team, &1), None);
+    assert!(!TemplateModule::can_read(&team, &1));
+    assert_noop!(
+      TemplateModule::invite(Origin::signed(1), team.clone(), 4, AccessControl::ReadWrite),
+      Error::<Test>::NeverJoining
+    );
+  });
+}
+
+#[test]
+fn group_depth_is_bounded() {
+  new_test_ext().execute_with(|| {
+    let root = care_team(1);
+    let first = sub_group(1, b"first", &root);
+    let second = sub_group(1, b"second", &first);
+    let third = sub_group(1, b"third", &second);
+
+    assert_noop!(
+      TemplateModule::create_group(
+        Origin::signed(1),
+        group_id(b"fourth"),
+        Default::default(),
+        Country::Netherlands,
+        Some(third.clone()),
+        Visibility::Public,
+        JoinPolicy::RequestApproval
+      ),
+      Error::<Test>::TooDeepGroup
+    );
+    assert_eq!(TemplateModule::descendants_of(&root), vec![first, second, third]);
+  });
+}
+
+#[test]
+fn deleting_a_group_deletes_its_sub_groups() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let hospital = care_team(1);
+    live_account(2);
+    let department = sub_group(1, b"cardiology", &hospital);
+    let team = sub_group(1, b"heart-failure", &department);
+    joined(1, 2, &team, AccessControl::ReadWrite);
+
+    assert_ok!(TemplateModule::dissolve_group(Origin::signed(1), department.clone()));
+
+    assert!(TemplateModule::groups(&hospital).is_some());
+    assert_eq!(TemplateModule::groups(&department), None);
+    assert_eq!(TemplateModule::groups(&team), None);
+    assert_eq!(TemplateModule::group_members(&team, 2), None);
+    assert_eq!(TemplateModule::descendants_of(&hospital), Vec::<GroupId>::new());
+  });
+}
+
+fn connected(a: u64, b: u64) {
+  live_account(a);
+  live_account(b);
+  assert_ok!(TemplateModule::request_connection(Origin::signed(a), b));
+  assert_ok!(TemplateModule::accept_connection(Origin::signed(b), a));
+}
+
+fn encrypted(payload: &[u8]) -> EncryptedMessage {
+  payload.to_vec().try_into().unwrap()
+}
+
+#[test]
+fn connected_accounts_exchange_encrypted_messages() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    connected(1, 2);
+
+    assert_ok!(TemplateModule::open_channel(Origin::signed(2), 1, None));
+    assert_event(crate::Event::ChannelOpened(2, 1));
+    assert_ok!(TemplateModule::send_message(Origin::signed(1), 2, encrypted(b"ciphertext")));
+
+    let message = TemplateModule::messages((1, 2), 0).unwrap();
+    assert_event(crate::Event::MessageSent(1, 2, 0, message.hash));
+    assert_eq!(message.sender, 1);
+    assert_eq!(message.recipient_key, [2; 32]);
+    assert_eq!(TemplateModule::channels(1, 2).unwrap().messages, 1);
+
+    assert_ok!(TemplateModule::mark_read(Origin::signed(2), 1, 1));
+    System::assert_last_event(Event::TemplateModule(crate::Event::MessagesRead(2, 1, 1)));
+    assert_eq!(TemplateModule::read_receipts((1, 2), 2), 1);
+
+    // Only what the other end sent can be marked as read.
+    assert_ok!(TemplateModule::send_message(Origin::signed(2), 1, encrypted(b"ciphertext")));
+    assert_eq!(TemplateModule::channels(1, 2).unwrap().messages, 2);
+    assert_noop!(TemplateModule::mark_read(Origin::signed(2), 1, 2), Error::<Test>::InvalidReadReceipt);
+  });
+}
+
+#[test]
+fn channels_need_a_connection_or_a_shared_group() {
+  new_test_ext().execute_with(|| {
+    let team = care_team(1);
+    live_account(2);
+    live_account(3);
+
+    assert_noop!(
+      TemplateModule::open_channel(Origin::signed(1), 2, None),
+      Error::<Test>::OnlyConnectedAllowed
+    );
+    assert_noop!(
+      TemplateModule::open_channel(Origin::signed(1), 2, Some(team.clone())),
+      Error::<Test>::NeverJoining
+    );
+
+    joined(1, 2, &team, AccessControl::ReadOnly);
+    assert_ok!(TemplateModule::open_channel(Origin::signed(1), 2, Some(team.clone())));
+    assert_ok!(TemplateModule::send_message(Origin::signed(2), 1, encrypted(b"ciphertext")));
+
+    assert_ok!(TemplateModule::leave_group(Origin::signed(2), team));
+    assert_noop!(
+      TemplateModule::send_message(Origin::signed(2), 1, encrypted(b"ciphertext")),
+      Error::<Test>::NeverJoining
+    );
+  });
+}
+
+#[test]
+fn messages_need_the_recipient_key() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    connected(1, 100);
+
+    assert_ok!(TemplateModule::open_channel(Origin::signed(1), 100, None));
+    assert_noop!(
+      TemplateModule::send_message(Origin::signed(1), 100, encrypted(b"ciphertext")),
+      Error::<Test>::EncryptionKeyNotExisted
+    );
+    assert_ok!(TemplateModule::send_message(Origin::signed(100), 1, encrypted(b"ciphertext")));
+
+    assert_ok!(TemplateModule::close_channel(Origin::signed(100), 1));
+    assert_event(crate::Event::ChannelClosed(100, 1));
+    assert_eq!(TemplateModule::messages((1, 100), 0), None);
+  });
+}
+
+#[test]
+fn recovery_moves_the_account_to_the_vouched_key() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let team = care_team(1);
+    connected(1, 2);
+    connected(3, 1);
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_ok!(TemplateModule::set_recovery_contacts(Origin::signed(1), vec![2, 3, 9], 2));
+    assert_event(crate::Event::RecoveryConfigured(1, 2));
+
+    assert_ok!(TemplateModule::vouch_recovery(Origin::signed(2), 1, 10));
+    assert_event(crate::Event::RecoveryVouched(1, 10, 2));
+    assert_ok!(TemplateModule::vouch_recovery(Origin::signed(9), 1, 10));
+    System::assert_last_event(Event::TemplateModule(crate::Event::RecoveryApproved(1, 10, 6)));
+    assert_noop!(TemplateModule::claim_recovery(Origin::signed(10), 1), Error::<Test>::RecoveryNotReady);
+
+    System::set_block_number(6);
+    assert_ok!(TemplateModule::claim_recovery(Origin::signed(10), 1));
+    assert_event(crate::Event::AccountRecovered(1, 10));
+
+    assert_eq!(TemplateModule::accounts(1), None);
+    assert!(TemplateModule::accounts(10).is_some());
+    assert!(TemplateModule::connections(10, 2) == Some(Relation::Connected));
+    assert!(TemplateModule::connections(3, 10) == Some(Relation::Connected));
+    assert!(TemplateModule::connections(3, 1).is_none());
+    assert_eq!(TemplateModule::groups(&team).unwrap().owner, 10);
+    assert_eq!(TemplateModule::group_members(&team, 10), Some(AccessControl::SuperAdmin));
+    assert_eq!(TemplateModule::member_groups(1, &team), None);
+  });
+}
+
+/// Recover `lost` to `new` through custodian 9 alone.
+fn recovered(lost: u64, new: u64) {
+  let now = System::block_number();
+
+  assert_ok!(TemplateModule::set_recovery_contacts(Origin::signed(lost), vec![9], 1));
+  assert_ok!(TemplateModule::vouch_recovery(Origin::signed(9), lost, new));
+  System::set_block_number(now + 5);
+  assert_ok!(TemplateModule::claim_recovery(Origin::signed(new), lost));
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn recovery_moves_guardianships() {
   new_test_ext().execute_with(|| {
-    // Dispatch a signed extrinsic.
-    assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-    // Read pallet storage and assert an expected result.
-    assert_eq!(TemplateModule::something(), Some(42));
+    System::set_block_number(1);
+    live_account(2);
+    live_account(3);
+    live_account(4);
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_ok!(TemplateModule::add_guardian(Origin::signed(2), 3, 50));
+    assert_ok!(TemplateModule::add_guardian(Origin::signed(4), 2, 50));
+
+    recovered(2, 12);
+
+    assert_eq!(TemplateModule::guardians(2, 3), None);
+    assert_eq!(TemplateModule::guardians(12, 3).unwrap().appointer, 12);
+    assert_eq!(TemplateModule::wards(3, 2), None);
+    assert!(TemplateModule::wards(3, 12).is_some());
+    assert_eq!(TemplateModule::guardians(4, 2), None);
+    assert!(TemplateModule::guardians(4, 12).is_some());
+    assert!(TemplateModule::wards(12, 4).is_some());
   });
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn recovery_moves_credentials() {
   new_test_ext().execute_with(|| {
-    // Ensure the expected error is thrown when no value is present.
-    assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+    System::set_block_number(1);
+    live_account(2);
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_ok!(TemplateModule::register_issuer(
+      Origin::signed(9),
+      5,
+      Country::Netherlands,
+      Default::default()
+    ));
+    assert_ok!(TemplateModule::issue_credential(
+      Origin::signed(5),
+      2,
+      CredentialKind::MedicalLicense,
+      None,
+      20
+    ));
+
+    recovered(2, 12);
+
+    assert!(TemplateModule::credentials(2, CredentialKind::MedicalLicense).is_none());
+    assert!(<TemplateModule as CredentialProvider<u64>>::has_credential(
+      &12,
+      &CredentialKind::MedicalLicense
+    ));
+  });
+}
+
+#[test]
+fn recovery_moves_the_organization_and_its_employees() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_ok!(TemplateModule::onboard_account(
+      Origin::signed(9),
+      5,
+      roles(vec![Role::Enterprise]),
+      None,
+      Default::default()
+    ));
+    assert_ok!(TemplateModule::onboard_account(
+      Origin::signed(9),
+      6,
+      roles(vec![Role::EnterpriseEmployee]),
+      Some(5),
+      Default::default()
+    ));
+    live_account(2);
+    assert_ok!(TemplateModule::create_organization(Origin::signed(5), Default::default()));
+    assert_ok!(TemplateModule::add_organization_member(Origin::signed(5), 5, 2, OrganizationRole::Admin));
+
+    recovered(5, 15);
+
+    assert_eq!(TemplateModule::organizations(5), None);
+    assert!(TemplateModule::organizations(15).is_some());
+    assert!(<TemplateModule as OrganizationProvider<u64>>::can_act_for(&15, &2));
+    assert_eq!(<TemplateModule as RoleProvider<u64>>::enterprise_of(&6), Some(15));
+    assert!(TemplateModule::employees(15, 6).is_some());
+
+    recovered(6, 16);
+    recovered(2, 12);
+
+    assert_eq!(<TemplateModule as RoleProvider<u64>>::enterprise_of(&16), Some(15));
+    assert_eq!(TemplateModule::employees(15, 6), None);
+    assert!(TemplateModule::employees(15, 16).is_some());
+    assert!(!<TemplateModule as OrganizationProvider<u64>>::can_act_for(&15, &2));
+    assert!(<TemplateModule as OrganizationProvider<u64>>::can_act_for(&15, &12));
+    assert!(TemplateModule::member_organizations(12, 15).is_some());
+  });
+}
+
+#[test]
+fn recovery_closes_channels() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    connected(1, 2);
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_ok!(TemplateModule::open_channel(Origin::signed(2), 1, None));
+    assert_ok!(TemplateModule::send_message(Origin::signed(1), 2, encrypted(b"ciphertext")));
+
+    recovered(2, 12);
+
+    assert_event(crate::Event::ChannelClosed(2, 1));
+    assert_eq!(TemplateModule::channels(1, 2), None);
+    assert_eq!(TemplateModule::reverse_channels(2, 1), None);
+    assert_eq!(TemplateModule::messages((1, 2), 0), None);
+    assert_ok!(TemplateModule::open_channel(Origin::signed(12), 1, None));
+  });
+}
+
+#[test]
+fn recovery_moves_a_pending_role_change() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_ok!(TemplateModule::onboard_account(
+      Origin::signed(9),
+      2,
+      roles(vec![Role::Patient]),
+      None,
+      Default::default()
+    ));
+    assert_ok!(TemplateModule::request_role_change(
+      Origin::signed(2),
+      roles(vec![Role::Patient, Role::MedicalProfessional]),
+      None
+    ));
+
+    recovered(2, 12);
+
+    assert_eq!(TemplateModule::role_change_requests(2), None);
+    assert_ok!(TemplateModule::approve_role_change(Origin::signed(9), 12));
+    assert!(<TemplateModule as RoleProvider<u64>>::has_role(&12, &Role::MedicalProfessional));
+  });
+}
+
+#[test]
+fn the_old_key_can_cancel_a_recovery() {
+  new_test_ext().execute_with(|| {
+    connected(1, 2);
+    connected(1, 3);
+    live_account(4);
+    assert_ok!(TemplateModule::set_recovery_contacts(Origin::signed(1), vec![2, 3], 2));
+
+    assert_noop!(
+      TemplateModule::vouch_recovery(Origin::signed(4), 1, 10),
+      Error::<Test>::NotRecoveryContact
+    );
+    assert_ok!(TemplateModule::vouch_recovery(Origin::signed(2), 1, 10));
+    assert_ok!(TemplateModule::vouch_recovery(Origin::signed(3), 1, 11));
+    assert_noop!(TemplateModule::vouch_recovery(Origin::signed(2), 1, 11), Error::<Test>::AlreadyVouched);
+
+    assert_ok!(TemplateModule::cancel_recovery(Origin::signed(1)));
+    assert_eq!(TemplateModule::recoveries(1, 10), None);
+    assert_eq!(TemplateModule::recoveries(1, 11), None);
+    assert_noop!(TemplateModule::claim_recovery(Origin::signed(10), 1), Error::<Test>::RecoveryNotStarted);
+  });
+}
+
+#[test]
+fn a_contact_vouching_for_the_wrong_key_does_not_block_the_recovery() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    connected(1, 2);
+    connected(1, 3);
+    connected(1, 4);
+    assert_ok!(TemplateModule::set_recovery_contacts(Origin::signed(1), vec![2, 3, 4], 2));
+
+    assert_ok!(TemplateModule::vouch_recovery(Origin::signed(2), 1, 10));
+    assert_ok!(TemplateModule::vouch_recovery(Origin::signed(3), 1, 11));
+    assert_ok!(TemplateModule::vouch_recovery(Origin::signed(4), 1, 11));
+    System::assert_last_event(Event::TemplateModule(crate::Event::RecoveryApproved(1, 11, 6)));
+    assert_eq!(TemplateModule::recoveries(1, 10).unwrap().claimable_at, None);
+
+    System::set_block_number(6);
+    assert_noop!(TemplateModule::claim_recovery(Origin::signed(10), 1), Error::<Test>::RecoveryNotReady);
+    assert_ok!(TemplateModule::claim_recovery(Origin::signed(11), 1));
+    assert!(TemplateModule::accounts(11).is_some());
+    assert_eq!(TemplateModule::recoveries(1, 10), None);
+  });
+}
+
+#[test]
+fn recovery_contacts_must_be_connected_or_custodians() {
+  new_test_ext().execute_with(|| {
+    connected(1, 2);
+    live_account(4);
+
+    assert_noop!(
+      TemplateModule::set_recovery_contacts(Origin::signed(1), vec![2, 4], 1),
+      Error::<Test>::InvalidRecoveryContacts
+    );
+    assert_noop!(
+      TemplateModule::set_recovery_contacts(Origin::signed(1), vec![2], 2),
+      Error::<Test>::InvalidThreshold
+    );
+  });
+}
+
+fn assert_event(event: crate::Event<Test>) {
+  System::assert_last_event(Event::TemplateModule(event));
+}
+
+#[test]
+fn custodian_and_freeze_events() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    live_account(2);
+
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_event(crate::Event::CustodianAdded(9));
+    assert!(<TemplateModule as CustodianProvider<u64>>::custodians().contains(&9));
+
+    assert_ok!(TemplateModule::freeze_account(Origin::signed(9), 2));
+    assert_event(crate::Event::AccountFrozen(2, 9));
+    assert_eq!(TemplateModule::accounts(2).unwrap().freezer, Some(9));
+    assert_noop!(TemplateModule::freeze_account(Origin::signed(9), 2), Error::<Test>::AccountNotLive);
+
+    assert_ok!(TemplateModule::unfreeze_account(Origin::signed(9), 2));
+    assert_event(crate::Event::AccountUnfrozen(2, 9));
+    assert_noop!(TemplateModule::unfreeze_account(Origin::signed(9), 2), Error::<Test>::AccountNotFrozen);
+    assert_noop!(TemplateModule::freeze_account(Origin::signed(2), 9), Error::<Test>::OnlyCustodianAllowed);
+
+    assert_ok!(TemplateModule::add_custodian(&8));
+    assert_ok!(TemplateModule::remove_custodian(&8));
+    assert_event(crate::Event::CustodianRemoved(8));
+    assert!(!<TemplateModule as CustodianProvider<u64>>::custodians().contains(&8));
+  });
+}
+
+#[test]
+fn recovery_events() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    connected(1, 2);
+
+    assert_ok!(TemplateModule::set_recovery_contacts(Origin::signed(1), vec![2], 1));
+    assert_ok!(TemplateModule::vouch_recovery(Origin::signed(2), 1, 10));
+    assert_ok!(TemplateModule::cancel_recovery(Origin::signed(1)));
+    assert_event(crate::Event::RecoveryCancelled(1, 10));
+
+    assert_ok!(TemplateModule::remove_recovery_contacts(Origin::signed(1)));
+    assert_event(crate::Event::RecoveryConfigRemoved(1));
+  });
+}
+
+#[test]
+fn connection_events() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    live_account(1);
+    live_account(2);
+
+    assert_ok!(TemplateModule::request_connection(Origin::signed(1), 2));
+    assert_event(crate::Event::ConnectionRequested(1, 2));
+
+    assert_ok!(TemplateModule::accept_connection(Origin::signed(2), 1));
+    assert_event(crate::Event::Connected(1, 2));
+
+    assert_ok!(TemplateModule::remove_connection(Origin::signed(2), 1));
+    assert_event(crate::Event::Disconnected(1, 2));
+  });
+}
+
+#[test]
+fn membership_events() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let team = care_team(1);
+    assert_event(crate::Event::GroupCreated(team.clone(), 1));
+    live_account(2);
+    live_account(3);
+
+    joined(1, 2, &team, AccessControl::ReadWrite);
+    assert_event(crate::Event::InvitationAccepted(team.clone(), 2));
+
+    assert_ok!(TemplateModule::request_join(Origin::signed(3), team.clone()));
+    assert_event(crate::Event::JoinRequested(team.clone(), 3));
+    assert_ok!(TemplateModule::cancel_join_request(Origin::signed(3), team.clone()));
+    assert_event(crate::Event::JoinRequestCancelled(team.clone(), 3));
+    assert_ok!(TemplateModule::request_join(Origin::signed(3), team.clone()));
+    assert_ok!(TemplateModule::reject_join(Origin::signed(1), team.clone(), 3));
+    assert_event(crate::Event::JoinRejected(team.clone(), 3, 1));
+
+    assert_ok!(TemplateModule::invite(Origin::signed(1), team.clone(), 3, AccessControl::ReadOnly));
+    assert_event(crate::Event::MemberInvited(team.clone(), 3, 1));
+    assert_ok!(TemplateModule::revoke_invitation(Origin::signed(1), team.clone(), 3));
+    assert_event(crate::Event::InvitationRevoked(team.clone(), 3, 1));
+    assert_ok!(TemplateModule::invite(Origin::signed(1), team.clone(), 3, AccessControl::ReadOnly));
+    assert_ok!(TemplateModule::decline_invitation(Origin::signed(3), team.clone()));
+    assert_event(crate::Event::InvitationDeclined(team.clone(), 3));
+
+    assert_ok!(TemplateModule::transfer_group_ownership(Origin::signed(1), team.clone(), 2));
+    assert_event(crate::Event::OwnershipTransferred(team.clone(), 1, 2));
+
+    assert_ok!(TemplateModule::leave_group(Origin::signed(1), team.clone()));
+    assert_event(crate::Event::MemberLeft(team, 1));
+  });
+}
+
+#[test]
+fn connections_are_listed_in_both_directions() {
+  new_test_ext().execute_with(|| {
+    connected(1, 2);
+    connected(3, 1);
+    live_account(4);
+    assert_ok!(TemplateModule::request_connection(Origin::signed(4), 1));
+
+    let connected_to_one = TemplateModule::connections_of(&1, &Some(Relation::Connected), 0, 10);
+    assert_eq!(connected_to_one.len(), 2);
+    assert!(connected_to_one.iter().any(|connection| connection.account == 3 && connection.incoming));
+
+    let pending = TemplateModule::connections_of(&1, &Some(Relation::Pending), 0, 10);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].account, 4);
+
+    assert_eq!(TemplateModule::connections_of(&1, &None, 1, 1).len(), 1);
+    assert_eq!(TemplateModule::connections_of(&1, &None, 3, 10).len(), 0);
+  });
+}
+
+#[test]
+fn groups_members_and_mutual_connections_are_listed() {
+  new_test_ext().execute_with(|| {
+    let team = care_team(1);
+    connected(1, 2);
+    connected(2, 3);
+    connected(3, 1);
+    joined(1, 2, &team, AccessControl::ReadOnly);
+
+    assert_eq!(TemplateModule::groups_of(&2), vec![(team.clone(), AccessControl::ReadOnly)]);
+    assert_eq!(TemplateModule::members_of(&team, 0, 10).len(), 2);
+    assert_eq!(TemplateModule::members_of(&team, 0, 1).len(), 1);
+    assert_eq!(TemplateModule::mutual_connections(&1, &2), vec![3]);
+  });
+}
+
+#[test]
+fn groups_are_located_from_their_country() {
+  new_test_ext().execute_with(|| {
+    let team = care_team(1);
+    let group = TemplateModule::groups(&team).unwrap();
+
+    assert_eq!(group.region, Region::Europe);
+    assert_eq!(group.sub_region, SubRegion::WesternEurope);
+    assert!(TemplateModule::groups_by_sub_region(SubRegion::WesternEurope, &team).is_some());
+
+    assert_ok!(TemplateModule::dissolve_group(Origin::signed(1), team.clone()));
+    assert_eq!(TemplateModule::groups_by_country(Country::Netherlands, &team), None);
+  });
+}
+
+#[test]
+fn nearby_groups_come_from_the_country_then_its_sub_region_then_its_region() {
+  new_test_ext().execute_with(|| {
+    live_account(1);
+    for (id, country) in [
+      (&b"amsterdam"[..], Country::Netherlands),
+      (&b"berlin"[..], Country::Germany),
+      (&b"madrid"[..], Country::Spain),
+      (&b"tokyo"[..], Country::Japan),
+    ] {
+      assert_ok!(TemplateModule::create_group(
+        Origin::signed(1),
+        group_id(id),
+        Default::default(),
+        country,
+        None,
+        Visibility::Public,
+        JoinPolicy::RequestApproval
+      ));
+    }
+
+    let nearby: Vec<GroupId> = TemplateModule::nearby_groups(&Country::Netherlands, 0, 10)
+      .into_iter()
+      .map(|(id, _)| id)
+      .collect();
+    assert_eq!(nearby, vec![group_id(b"amsterdam"), group_id(b"berlin"), group_id(b"madrid")]);
+    assert_eq!(TemplateModule::nearby_groups(&Country::Netherlands, 1, 1).len(), 1);
+  });
+}
+
+fn clinic_community(owner: u64, visibility: Visibility, join_policy: JoinPolicy) -> GroupId {
+  let id = group_id(b"clinic-community");
+
+  live_account(owner);
+  assert_ok!(TemplateModule::do_create_group(
+    &owner,
+    &id,
+    &Default::default(),
+    &Country::Netherlands,
+    &None,
+    &visibility,
+    &join_policy
+  ));
+
+  id
+}
+
+#[test]
+fn anyone_can_join_an_open_group_as_customer() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let id = clinic_community(1, Visibility::Public, JoinPolicy::Open);
+    live_account(2);
+
+    assert_ok!(TemplateModule::join_group(Origin::signed(2), id.clone()));
+    assert_eq!(TemplateModule::group_members(&id, 2), Some(AccessControl::Customer));
+    assert_event(crate::Event::MemberJoined(id, 2, AccessControl::Customer));
+  });
+}
+
+#[test]
+fn only_open_groups_can_be_joined_without_an_admin() {
+  new_test_ext().execute_with(|| {
+    let id = care_team(1);
+    live_account(2);
+
+    assert_noop!(TemplateModule::join_group(Origin::signed(2), id), Error::<Test>::GroupNotOpen);
+  });
+}
+
+#[test]
+fn invite_only_groups_reject_join_requests() {
+  new_test_ext().execute_with(|| {
+    let id = clinic_community(1, Visibility::Unlisted, JoinPolicy::InviteOnly);
+    live_account(2);
+
+    assert_noop!(
+      TemplateModule::request_join(Origin::signed(2), id.clone()),
+      Error::<Test>::InvitationRequired
+    );
+    assert_ok!(TemplateModule::invite(Origin::signed(1), id.clone(), 2, AccessControl::ReadOnly));
+    assert_ok!(TemplateModule::accept_invitation(Origin::signed(2), id));
+  });
+}
+
+#[test]
+fn private_groups_have_to_be_invite_only() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    live_account(1);
+
+    assert_noop!(
+      TemplateModule::create_group(
+        Origin::signed(1),
+        group_id(b"private"),
+        Default::default(),
+        Country::Netherlands,
+        None,
+        Visibility::Private,
+        JoinPolicy::RequestApproval
+      ),
+      Error::<Test>::InvalidGroupPolicy
+    );
+
+    let id = care_team(1);
+    assert_noop!(
+      TemplateModule::set_group_policy(Origin::signed(1), id.clone(), Visibility::Private, JoinPolicy::Open),
+      Error::<Test>::InvalidGroupPolicy
+    );
+    assert_ok!(TemplateModule::set_group_policy(
+      Origin::signed(1),
+      id.clone(),
+      Visibility::Private,
+      JoinPolicy::InviteOnly
+    ));
+    assert_event(crate::Event::GroupPolicyChanged(id.clone(), Visibility::Private, JoinPolicy::InviteOnly));
+    assert_eq!(TemplateModule::groups(&id).unwrap().join_policy, JoinPolicy::InviteOnly);
+  });
+}
+
+#[test]
+fn only_public_groups_are_discovered() {
+  new_test_ext().execute_with(|| {
+    let id = clinic_community(1, Visibility::Unlisted, JoinPolicy::Open);
+
+    assert!(TemplateModule::nearby_groups(&Country::Netherlands, 0, 10).is_empty());
+
+    assert_ok!(TemplateModule::set_group_policy(
+      Origin::signed(1),
+      id.clone(),
+      Visibility::Public,
+      JoinPolicy::Open
+    ));
+    assert_eq!(TemplateModule::nearby_groups(&Country::Netherlands, 0, 10).len(), 1);
+  });
+}
+
+#[test]
+fn customers_are_not_disclosed_and_cannot_reach_each_other() {
+  new_test_ext().execute_with(|| {
+    let id = clinic_community(1, Visibility::Public, JoinPolicy::Open);
+    live_account(2);
+    live_account(3);
+    assert_ok!(TemplateModule::join_group(Origin::signed(2), id.clone()));
+    assert_ok!(TemplateModule::join_group(Origin::signed(3), id.clone()));
+
+    assert_eq!(TemplateModule::members_of(&id, 0, 10), vec![(1, AccessControl::SuperAdmin)]);
+    assert_noop!(
+      TemplateModule::open_channel(Origin::signed(2), 3, Some(id.clone())),
+      Error::<Test>::OnlyStaffAllowed
+    );
+    assert_ok!(TemplateModule::open_channel(Origin::signed(2), 1, Some(id)));
+  });
+}
+
+#[test]
+fn group_deposits_are_reserved_and_refunded() {
+  new_test_ext().execute_with(|| {
+    let team = care_team(1);
+    live_account(2);
+    live_account(3);
+    assert_eq!(Balances::reserved_balance(1), GroupDeposit::get() + MemberDeposit::get());
+
+    joined(1, 2, &team, AccessControl::ReadWrite);
+    joined(1, 3, &team, AccessControl::ReadOnly);
+    assert_eq!(Balances::reserved_balance(2), MemberDeposit::get());
+
+    assert_ok!(TemplateModule::leave_group(Origin::signed(3), team.clone()));
+    assert_eq!(Balances::reserved_balance(3), 0);
+
+    assert_ok!(TemplateModule::transfer_group_ownership(Origin::signed(1), team.clone(), 2));
+    assert_eq!(Balances::reserved_balance(1), MemberDeposit::get());
+    assert_eq!(Balances::reserved_balance(2), GroupDeposit::get() + MemberDeposit::get());
+
+    assert_ok!(TemplateModule::dissolve_group(Origin::signed(2), team));
+    assert_eq!(Balances::reserved_balance(1), 0);
+    assert_eq!(Balances::reserved_balance(2), 0);
+  });
+}
+
+#[test]
+fn new_owners_put_up_their_own_group_deposit() {
+  new_test_ext().execute_with(|| {
+    let team = care_team(1);
+    live_account(300);
+    Balances::make_free_balance_be(&300, MemberDeposit::get() + GroupDeposit::get() - 1);
+    joined(1, 300, &team, AccessControl::ReadWrite);
+
+    assert_noop!(
+      TemplateModule::transfer_group_ownership(Origin::signed(1), team.clone(), 300),
+      Error::<Test>::InsufficientDeposit
+    );
+    assert_eq!(Balances::reserved_balance(1), GroupDeposit::get() + MemberDeposit::get());
+
+    Balances::make_free_balance_be(&300, GroupDeposit::get());
+    assert_ok!(TemplateModule::transfer_group_ownership(Origin::signed(1), team.clone(), 300));
+    assert_eq!(Balances::free_balance(300), 0);
+    assert_eq!(Balances::reserved_balance(300), GroupDeposit::get() + MemberDeposit::get());
+    assert_eq!(Balances::reserved_balance(1), MemberDeposit::get());
+    assert_eq!(Balances::free_balance(1), 1000 - MemberDeposit::get());
+    assert_eq!(TemplateModule::deposits(Deposit::Group(team)), GroupDeposit::get());
+  });
+}
+
+#[test]
+fn connection_deposits_are_refunded_to_the_requester() {
+  new_test_ext().execute_with(|| {
+    live_account(1);
+    live_account(2);
+
+    assert_ok!(TemplateModule::request_connection(Origin::signed(1), 2));
+    assert_ok!(TemplateModule::accept_connection(Origin::signed(2), 1));
+    assert_eq!(Balances::reserved_balance(1), ConnectionDeposit::get());
+    assert_eq!(Balances::reserved_balance(2), 0);
+
+    assert_ok!(TemplateModule::remove_connection(Origin::signed(2), 1));
+    assert_eq!(Balances::reserved_balance(1), 0);
+  });
+}
+
+#[test]
+fn accounts_without_funds_cannot_create_or_join() {
+  new_test_ext().execute_with(|| {
+    let team = care_team(1);
+    live_account(300);
+
+    assert_noop!(
+      TemplateModule::create_group(
+        Origin::signed(300),
+        group_id(b"clinic"),
+        Default::default(),
+        Country::Netherlands,
+        None,
+        Visibility::Public,
+        JoinPolicy::Open
+      ),
+      Error::<Test>::InsufficientDeposit
+    );
+    assert_ok!(TemplateModule::invite(Origin::signed(1), team.clone(), 300, AccessControl::ReadOnly));
+    assert_noop!(
+      TemplateModule::accept_invitation(Origin::signed(300), team.clone()),
+      Error::<Test>::InsufficientDeposit
+    );
+    assert_noop!(
+      TemplateModule::request_connection(Origin::signed(300), 1),
+      Error::<Test>::InsufficientDeposit
+    );
+  });
+}
+
+#[test]
+fn closing_an_account_refunds_the_custodian() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    live_account(3);
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_ok!(TemplateModule::onboard_account(
+      Origin::signed(9),
+      2,
+      roles(vec![Role::Patient]),
+      None,
+      Default::default()
+    ));
+    assert_eq!(Balances::reserved_balance(9), AccountDeposit::get());
+
+    assert_ok!(TemplateModule::request_connection(Origin::signed(3), 2));
+    assert_noop!(TemplateModule::close_account(Origin::signed(2)), Error::<Test>::AccountInUse);
+    assert_ok!(TemplateModule::remove_connection(Origin::signed(2), 3));
+
+    assert_ok!(TemplateModule::close_account(Origin::signed(2)));
+    assert_event(crate::Event::AccountClosed(2));
+    assert_eq!(TemplateModule::accounts(2), None);
+    assert_eq!(Balances::reserved_balance(9), 0);
+    assert_eq!(TemplateModule::deposits(Deposit::Account(2)), 0);
+  });
+}
+
+#[test]
+fn closing_an_account_leaves_nothing_behind() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    live_account(2);
+    live_account(3);
+    assert_ok!(TemplateModule::add_custodian(&9));
+    assert_ok!(TemplateModule::register_issuer(
+      Origin::signed(9),
+      5,
+      Country::Netherlands,
+      Default::default()
+    ));
+
+    assert_ok!(TemplateModule::add_guardian(Origin::signed(3), 2, 50));
+    assert_noop!(TemplateModule::close_account(Origin::signed(2)), Error::<Test>::AccountInUse);
+    assert_ok!(TemplateModule::remove_guardian(Origin::signed(2), 3, 2));
+
+    assert_ok!(TemplateModule::freeze_account(Origin::signed(9), 2));
+    assert_noop!(TemplateModule::close_account(Origin::signed(2)), Error::<Test>::AccountNotLive);
+    assert_ok!(TemplateModule::unfreeze_account(Origin::signed(9), 2));
+
+    assert_ok!(TemplateModule::add_guardian(Origin::signed(2), 3, 50));
+    assert_ok!(TemplateModule::issue_credential(
+      Origin::signed(5),
+      2,
+      CredentialKind::MedicalLicense,
+      None,
+      20
+    ));
+    assert_ok!(TemplateModule::close_account(Origin::signed(2)));
+
+    assert_eq!(TemplateModule::guardians(2, 3), None);
+    assert_eq!(TemplateModule::wards(3, 2), None);
+    assert_eq!(TemplateModule::credentials(2, CredentialKind::MedicalLicense), None);
   });
 }
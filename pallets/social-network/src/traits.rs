@@ -1,5 +1,7 @@
-use crate::Config;
+use crate::types::{CredentialKind, GroupId};
 use frame_support::dispatch::DispatchResultWithPostInfo;
+use healthcare::Role;
+use sp_std::vec::Vec;
 
 pub trait ConnectionRules<AccountId> {
   /// Check if `from` can connect to `to`. If `false`,the connection will be rejected.
@@ -50,3 +52,111 @@ impl<AccountId> ConnectionRules<AccountId> for () {
     Ok(().into())
   }
 }
+
+pub trait GroupMembership<AccountId> {
+  /// Check if `who` is a live member of `group_id`, directly or as an admin of an ancestor, whose
+  /// access control lets it read what is shared with the group. Nothing is cached, so the answer
+  /// changes as soon as the membership does.
+  fn can_read(group_id: &GroupId, who: &AccountId) -> bool;
+}
+
+impl<AccountId> GroupMembership<AccountId> for () {
+  #[allow(unused_variables)]
+  fn can_read(group_id: &GroupId, who: &AccountId) -> bool {
+    false
+  }
+}
+
+pub trait CredentialProvider<AccountId> {
+  /// Check if `who` holds an unexpired credential of `kind` attested by a currently registered issuer.
+  fn has_credential(who: &AccountId, kind: &CredentialKind) -> bool;
+}
+
+impl<AccountId> CredentialProvider<AccountId> for () {
+  #[allow(unused_variables)]
+  fn has_credential(who: &AccountId, kind: &CredentialKind) -> bool {
+    false
+  }
+}
+
+pub trait RoleProvider<AccountId> {
+  /// The roles of `who`. Accounts that are missing or not live have no roles.
+  fn roles_of(who: &AccountId) -> Vec<Role>;
+
+  /// Check if `who` is a live account holding `role`.
+  fn has_role(who: &AccountId, role: &Role) -> bool {
+    Self::roles_of(who).contains(role)
+  }
+
+  /// The `Enterprise` account `who` works for, if it is an `EnterpriseEmployee`.
+  fn enterprise_of(who: &AccountId) -> Option<AccountId>;
+
+  /// Make `who` a live account holding `role`, so that benchmarks can act with it.
+  #[cfg(feature = "runtime-benchmarks")]
+  fn assign_role(who: &AccountId, role: &Role);
+}
+
+impl<AccountId> RoleProvider<AccountId> for () {
+  #[allow(unused_variables)]
+  fn roles_of(who: &AccountId) -> Vec<Role> {
+    Vec::new()
+  }
+
+  #[allow(unused_variables)]
+  fn enterprise_of(who: &AccountId) -> Option<AccountId> {
+    None
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  #[allow(unused_variables)]
+  fn assign_role(who: &AccountId, role: &Role) {}
+}
+
+pub trait OrganizationProvider<AccountId> {
+  /// Check if `who` can act on behalf of the organization `organization`, either because it is the
+  /// organization account itself or a live member of it.
+  fn can_act_for(organization: &AccountId, who: &AccountId) -> bool;
+}
+
+impl<AccountId: PartialEq> OrganizationProvider<AccountId> for () {
+  fn can_act_for(organization: &AccountId, who: &AccountId) -> bool {
+    organization == who
+  }
+}
+
+pub trait CustodianProvider<AccountId> {
+  /// The accounts currently registered as custodians.
+  fn custodians() -> Vec<AccountId>;
+
+  /// Register `who` as a custodian, so that benchmarks can fill dispute panels.
+  #[cfg(feature = "runtime-benchmarks")]
+  fn register_custodian(who: &AccountId);
+}
+
+impl<AccountId> CustodianProvider<AccountId> for () {
+  fn custodians() -> Vec<AccountId> {
+    Vec::new()
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  #[allow(unused_variables)]
+  fn register_custodian(who: &AccountId) {}
+}
+
+pub trait OnAccountRecovered<AccountId> {
+  /// Called once `lost` was recovered to `new`, after the social network moved its own state, so
+  /// that other pallets can move whatever `lost` owned.
+  fn on_account_recovered(lost: &AccountId, new: &AccountId);
+}
+
+impl<AccountId> OnAccountRecovered<AccountId> for () {
+  #[allow(unused_variables)]
+  fn on_account_recovered(lost: &AccountId, new: &AccountId) {}
+}
+
+impl<AccountId, A: OnAccountRecovered<AccountId>, B: OnAccountRecovered<AccountId>> OnAccountRecovered<AccountId> for (A, B) {
+  fn on_account_recovered(lost: &AccountId, new: &AccountId) {
+    A::on_account_recovered(lost, new);
+    B::on_account_recovered(lost, new);
+  }
+}
@@ -1,32 +1,76 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use crate::constants::{GROUP_INFO_MAX_LEN, GROUP_OID_MAX_LEN, USER_INFO_MAX_LEN};
+use crate::constants::{
+  GROUP_INFO_MAX_LEN, GROUP_OID_MAX_LEN, ISSUER_INFO_MAX_LEN, MAX_EXPIRIES_PER_BLOCK, MAX_MESSAGE_LEN, MAX_RECOVERY_CONTACTS, MAX_ROLES,
+  USER_INFO_MAX_LEN,
+};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::pallet_prelude::*;
+use healthcare::{Role, TherapeuticArea};
+use pallet_key_registry::types::EncryptionKey;
 use region::{Country, Region, SubRegion};
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_std::cmp::{Eq, PartialEq};
 
 pub type GroupInfo = BoundedVec<u8, ConstU32<GROUP_INFO_MAX_LEN>>;
 pub type AccountInfo = BoundedVec<u8, ConstU32<USER_INFO_MAX_LEN>>;
 pub type GroupId = BoundedVec<u8, ConstU32<GROUP_OID_MAX_LEN>>;
+pub type IssuerInfo = BoundedVec<u8, ConstU32<ISSUER_INFO_MAX_LEN>>;
+pub type Roles = BoundedVec<Role, ConstU32<MAX_ROLES>>;
+pub type RecoveryContacts<AccountId> = BoundedVec<AccountId, ConstU32<MAX_RECOVERY_CONTACTS>>;
+/// A message payload, already encrypted to the recipient's key.
+pub type EncryptedMessage = BoundedVec<u8, ConstU32<MAX_MESSAGE_LEN>>;
+/// The join requests and invitations lapsing at a block, as `(group, account)` pairs.
+pub type PendingMemberships<AccountId> = BoundedVec<(GroupId, AccountId), ConstU32<MAX_EXPIRIES_PER_BLOCK>>;
 
-#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Copy)]
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum AccessControl {
   SuperAdmin,
   Admin,
   ReadWrite,
   ReadOnly,
+  /// The public-facing tier, such as the patients in a clinic's community. Customers can reach the
+  /// staff of the group but not each other, cannot read what is shared with it and are never listed
+  /// as its members.
   Customer,
 }
 
-#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Copy)]
+/// Who can find a group.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Visibility {
+  /// Listed in discovery queries.
+  Public,
+  /// Reachable by anyone who knows its id, but never listed.
+  Unlisted,
+  /// Neither listed nor open to join requests, and its members are not disclosed.
+  Private,
+}
+
+/// How an account becomes a member of a group.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum JoinPolicy {
+  /// Anyone live can join by themselves, as a `Customer`.
+  Open,
+  /// Join requests have to be approved by an admin.
+  RequestApproval,
+  /// Only invited accounts can join.
+  InviteOnly,
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Relation {
   Pending,
   Connected,
 }
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum AccountStatus {
   /// The account is active and able to do activity in the network
   Live,
@@ -45,17 +89,189 @@ pub struct AccountDetail<AccountId> {
   /// The status of this account to do activity in the network
   pub status: AccountStatus,
 
+  /// The healthcare roles a custodian assigned to this account
+  pub roles: Roles,
+
+  /// The `Enterprise` account an `EnterpriseEmployee` works for
+  pub enterprise: Option<AccountId>,
+
   /// The externally stored account information
   pub info: AccountInfo,
 }
 
+/// One end of a connection, as seen from the account it was looked up for.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ConnectionInfo<AccountId> {
+  pub account: AccountId,
+  pub relation: Relation,
+
+  /// Whether `account` requested the connection, rather than the account it was looked up for
+  pub incoming: bool,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct RoleChange<AccountId> {
+  /// The roles the account asks for, replacing its current ones
+  pub roles: Roles,
+
+  /// The `Enterprise` account to link to, required for `EnterpriseEmployee`
+  pub enterprise: Option<AccountId>,
+}
+
 #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Group<AccountId> {
   pub owner: AccountId,
+  /// The group this group is a sub-group of, such as the department of a care team
+  pub parent: Option<GroupId>,
   pub info: GroupInfo,
   pub country: Country,
   pub region: Region,
   pub sub_region: SubRegion,
+  pub visibility: Visibility,
+  pub join_policy: JoinPolicy,
   pub admins: u32,
   pub members: u32,
 }
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Invitation<AccountId, BlockNumber> {
+  /// Who invited the account to the group
+  pub inviter: AccountId,
+
+  /// The access control granted once the invitation is accepted
+  pub access_control: AccessControl,
+
+  /// The last block at which the invitation can be accepted
+  pub expiry: BlockNumber,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Guardianship<AccountId, BlockNumber> {
+  /// Who established the guardianship, either the ward itself or the custodian that appointed the guardian
+  pub appointer: AccountId,
+
+  /// The last block at which the guardian can act on behalf of the ward
+  pub expiry: BlockNumber,
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
+pub enum CredentialKind {
+  MedicalLicense,
+  NursingLicense,
+  PharmacyLicense,
+  ResearchAccreditation,
+  FacilityAccreditation,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Issuer<AccountId> {
+  /// The custodian that registered the issuer
+  pub registrar: AccountId,
+
+  /// The jurisdiction the issuer licenses in
+  pub country: Country,
+
+  /// The externally stored issuer information
+  pub info: IssuerInfo,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Credential<AccountId, BlockNumber> {
+  /// The issuer that attested the credential
+  pub issuer: AccountId,
+
+  /// The jurisdiction the credential is valid in
+  pub country: Country,
+
+  /// The specialty the credential covers, if any
+  pub specialty: Option<TherapeuticArea>,
+
+  /// The last block at which the credential is valid
+  pub expiry: BlockNumber,
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Copy)]
+pub enum OrganizationRole {
+  /// Manages the members of the organization and can act on its behalf
+  Admin,
+  /// Acts on behalf of the organization
+  Employee,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Organization {
+  /// The externally stored organization information
+  pub info: AccountInfo,
+
+  /// The number of members holding `OrganizationRole::Admin`
+  pub admins: u32,
+
+  /// The number of members, admins included
+  pub members: u32,
+}
+
+/// What a deposit was reserved for.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum Deposit<AccountId> {
+  /// An onboarded account, reserved by its custodian
+  Account(AccountId),
+
+  /// A connection, reserved by its requester
+  Connection(AccountId, AccountId),
+
+  /// A group, reserved by its owner
+  Group(GroupId),
+
+  /// A group membership, reserved by the member
+  Member(GroupId, AccountId),
+
+  /// A request to join a group, reserved by the requester
+  JoinRequest(GroupId, AccountId),
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Channel<AccountId, BlockNumber> {
+  /// Who opened the channel
+  pub opener: AccountId,
+
+  /// The group both ends share, if the channel was not opened over a connection
+  pub group: Option<GroupId>,
+
+  /// When the channel was opened
+  pub opened_at: BlockNumber,
+
+  /// The number of messages sent so far, which is also the index of the next one
+  pub messages: u32,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Message<AccountId, Hash, BlockNumber> {
+  pub sender: AccountId,
+
+  /// The hash of the encrypted payload, which itself is only kept in offchain storage
+  pub hash: Hash,
+
+  /// The recipient's key the payload was encrypted to
+  pub recipient_key: EncryptionKey,
+
+  pub sent_at: BlockNumber,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct RecoveryConfig<AccountId> {
+  /// Connected accounts or custodians trusted to vouch for a new key
+  pub contacts: RecoveryContacts<AccountId>,
+
+  /// How many contacts have to vouch for the same new key
+  pub threshold: u32,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Recovery<AccountId, BlockNumber> {
+  /// The contacts who vouched so far
+  pub vouchers: RecoveryContacts<AccountId>,
+
+  /// When the recovery can be claimed, set once the threshold is reached
+  pub claimable_at: Option<BlockNumber>,
+}
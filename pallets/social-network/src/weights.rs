@@ -0,0 +1,550 @@
+//! Weights for pallet_social_network.
+//!
+//! The `SubstrateWeight` values below are placeholders, estimated by hand from the storage accesses
+//! of each call rather than measured. They are to be replaced by running the benchmarks in
+//! `benchmarking.rs` against the reference hardware:
+//!
+//! ./target/release/meta benchmark --chain=dev --steps=50 --repeat=20 --pallet=pallet_social_network
+//!   --extrinsic='*' --execution=wasm --wasm-execution=compiled --heap-pages=4096
+//!   --output=./pallets/social-network/src/weights.rs --template=./.maintain/frame-weight-template.hbs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+  traits::Get,
+  weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_social_network.
+pub trait WeightInfo {
+  fn onboard_account() -> Weight;
+  fn close_account() -> Weight;
+  fn freeze_account() -> Weight;
+  fn unfreeze_account() -> Weight;
+  fn request_role_change() -> Weight;
+  fn approve_role_change() -> Weight;
+  fn reject_role_change() -> Weight;
+  fn request_connection() -> Weight;
+  fn accept_connection() -> Weight;
+  fn remove_connection() -> Weight;
+  fn create_group() -> Weight;
+  fn set_group_policy() -> Weight;
+  fn join_group() -> Weight;
+  fn request_join() -> Weight;
+  fn cancel_join_request() -> Weight;
+  fn approve_join() -> Weight;
+  fn reject_join() -> Weight;
+  fn invite() -> Weight;
+  fn revoke_invitation() -> Weight;
+  fn accept_invitation() -> Weight;
+  fn decline_invitation() -> Weight;
+  fn remove_member() -> Weight;
+  fn leave_group() -> Weight;
+  fn change_access_control() -> Weight;
+  fn transfer_group_ownership() -> Weight;
+  fn dissolve_group(g: u32) -> Weight;
+  fn add_guardian() -> Weight;
+  fn appoint_guardian() -> Weight;
+  fn remove_guardian() -> Weight;
+  fn act_for_ward() -> Weight;
+  fn create_organization() -> Weight;
+  fn add_organization_member() -> Weight;
+  fn remove_organization_member() -> Weight;
+  fn register_issuer() -> Weight;
+  fn deregister_issuer() -> Weight;
+  fn issue_credential() -> Weight;
+  fn revoke_credential() -> Weight;
+  fn open_channel() -> Weight;
+  fn close_channel(m: u32) -> Weight;
+  fn send_message(l: u32) -> Weight;
+  fn mark_read() -> Weight;
+  fn set_recovery_contacts(c: u32) -> Weight;
+  fn remove_recovery_contacts() -> Weight;
+  fn vouch_recovery() -> Weight;
+  fn cancel_recovery() -> Weight;
+  fn claim_recovery() -> Weight;
+}
+
+/// Weights for pallet_social_network using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+  fn onboard_account() -> Weight {
+    (41_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(4 as Weight))
+      .saturating_add(T::DbWeight::get().writes(3 as Weight))
+  }
+  fn close_account() -> Weight {
+    (38_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(12 as Weight))
+      .saturating_add(T::DbWeight::get().writes(19 as Weight))
+  }
+  fn freeze_account() -> Weight {
+    (24_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn unfreeze_account() -> Weight {
+    (23_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn request_role_change() -> Weight {
+    (22_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn approve_role_change() -> Weight {
+    (31_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(4 as Weight))
+      .saturating_add(T::DbWeight::get().writes(2 as Weight))
+  }
+  fn reject_role_change() -> Weight {
+    (21_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn request_connection() -> Weight {
+    (36_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(3 as Weight))
+      .saturating_add(T::DbWeight::get().writes(4 as Weight))
+  }
+  fn accept_connection() -> Weight {
+    (19_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(1 as Weight))
+      .saturating_add(T::DbWeight::get().writes(2 as Weight))
+  }
+  fn remove_connection() -> Weight {
+    (39_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(4 as Weight))
+      .saturating_add(T::DbWeight::get().writes(6 as Weight))
+  }
+  fn create_group() -> Weight {
+    (68_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(10 as Weight))
+      .saturating_add(T::DbWeight::get().writes(10 as Weight))
+  }
+  fn set_group_policy() -> Weight {
+    (27_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(4 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn join_group() -> Weight {
+    (42_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(4 as Weight))
+      .saturating_add(T::DbWeight::get().writes(5 as Weight))
+  }
+  fn request_join() -> Weight {
+    (30_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(5 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn cancel_join_request() -> Weight {
+    (17_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(1 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn approve_join() -> Weight {
+    (51_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(6 as Weight))
+      .saturating_add(T::DbWeight::get().writes(6 as Weight))
+  }
+  fn reject_join() -> Weight {
+    (24_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(3 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn invite() -> Weight {
+    (35_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(7 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn revoke_invitation() -> Weight {
+    (24_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(3 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn accept_invitation() -> Weight {
+    (47_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(5 as Weight))
+      .saturating_add(T::DbWeight::get().writes(6 as Weight))
+  }
+  fn decline_invitation() -> Weight {
+    (17_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(1 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn remove_member() -> Weight {
+    (49_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(7 as Weight))
+      .saturating_add(T::DbWeight::get().writes(5 as Weight))
+  }
+  fn leave_group() -> Weight {
+    (38_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(4 as Weight))
+      .saturating_add(T::DbWeight::get().writes(5 as Weight))
+  }
+  fn change_access_control() -> Weight {
+    (33_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(4 as Weight))
+      .saturating_add(T::DbWeight::get().writes(2 as Weight))
+  }
+  fn transfer_group_ownership() -> Weight {
+    (46_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(7 as Weight))
+      .saturating_add(T::DbWeight::get().writes(4 as Weight))
+  }
+  fn dissolve_group(g: u32) -> Weight {
+    (29_000_000 as Weight)
+      .saturating_add((37_000_000 as Weight).saturating_mul(g as Weight))
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(g as Weight)))
+      .saturating_add(T::DbWeight::get().writes(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes((10 as Weight).saturating_mul(g as Weight)))
+  }
+  fn add_guardian() -> Weight {
+    (29_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(4 as Weight))
+      .saturating_add(T::DbWeight::get().writes(2 as Weight))
+  }
+  fn appoint_guardian() -> Weight {
+    (31_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(5 as Weight))
+      .saturating_add(T::DbWeight::get().writes(2 as Weight))
+  }
+  fn remove_guardian() -> Weight {
+    (18_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(1 as Weight))
+      .saturating_add(T::DbWeight::get().writes(2 as Weight))
+  }
+  fn act_for_ward() -> Weight {
+    (21_000_000 as Weight).saturating_add(T::DbWeight::get().reads(3 as Weight))
+  }
+  fn create_organization() -> Weight {
+    (26_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(3 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn add_organization_member() -> Weight {
+    (34_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(5 as Weight))
+      .saturating_add(T::DbWeight::get().writes(3 as Weight))
+  }
+  fn remove_organization_member() -> Weight {
+    (30_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(3 as Weight))
+      .saturating_add(T::DbWeight::get().writes(3 as Weight))
+  }
+  fn register_issuer() -> Weight {
+    (21_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn deregister_issuer() -> Weight {
+    (20_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn issue_credential() -> Weight {
+    (25_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn revoke_credential() -> Weight {
+    (22_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn open_channel() -> Weight {
+    (32_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(5 as Weight))
+      .saturating_add(T::DbWeight::get().writes(2 as Weight))
+  }
+  fn close_channel(m: u32) -> Weight {
+    (27_000_000 as Weight)
+      .saturating_add((1_000_000 as Weight).saturating_mul(m as Weight))
+      .saturating_add(T::DbWeight::get().reads(1 as Weight))
+      .saturating_add(T::DbWeight::get().writes(6 as Weight))
+      .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(m as Weight)))
+  }
+  fn send_message(l: u32) -> Weight {
+    (44_000_000 as Weight)
+      .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+      .saturating_add(T::DbWeight::get().reads(7 as Weight))
+      .saturating_add(T::DbWeight::get().writes(3 as Weight))
+  }
+  fn mark_read() -> Weight {
+    (21_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(3 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn set_recovery_contacts(c: u32) -> Weight {
+    (26_000_000 as Weight)
+      .saturating_add((4_000_000 as Weight).saturating_mul(c as Weight))
+      .saturating_add(T::DbWeight::get().reads(2 as Weight))
+      .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn remove_recovery_contacts() -> Weight {
+    (19_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(1 as Weight))
+      .saturating_add(T::DbWeight::get().writes(2 as Weight))
+  }
+  fn vouch_recovery() -> Weight {
+    (32_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(3 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn cancel_recovery() -> Weight {
+    (18_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(1 as Weight))
+      .saturating_add(T::DbWeight::get().writes(1 as Weight))
+  }
+  fn claim_recovery() -> Weight {
+    (86_000_000 as Weight)
+      .saturating_add(T::DbWeight::get().reads(9 as Weight))
+      .saturating_add(T::DbWeight::get().writes(12 as Weight))
+  }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+  fn onboard_account() -> Weight {
+    (41_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+  }
+  fn close_account() -> Weight {
+    (38_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(19 as Weight))
+  }
+  fn freeze_account() -> Weight {
+    (24_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn unfreeze_account() -> Weight {
+    (23_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn request_role_change() -> Weight {
+    (22_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn approve_role_change() -> Weight {
+    (31_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+  }
+  fn reject_role_change() -> Weight {
+    (21_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn request_connection() -> Weight {
+    (36_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+  }
+  fn accept_connection() -> Weight {
+    (19_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+  }
+  fn remove_connection() -> Weight {
+    (39_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+  }
+  fn create_group() -> Weight {
+    (68_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+  }
+  fn set_group_policy() -> Weight {
+    (27_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn join_group() -> Weight {
+    (42_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+  }
+  fn request_join() -> Weight {
+    (30_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn cancel_join_request() -> Weight {
+    (17_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn approve_join() -> Weight {
+    (51_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+  }
+  fn reject_join() -> Weight {
+    (24_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn invite() -> Weight {
+    (35_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn revoke_invitation() -> Weight {
+    (24_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn accept_invitation() -> Weight {
+    (47_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+  }
+  fn decline_invitation() -> Weight {
+    (17_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn remove_member() -> Weight {
+    (49_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+  }
+  fn leave_group() -> Weight {
+    (38_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+  }
+  fn change_access_control() -> Weight {
+    (33_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+  }
+  fn transfer_group_ownership() -> Weight {
+    (46_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+  }
+  fn dissolve_group(g: u32) -> Weight {
+    (29_000_000 as Weight)
+      .saturating_add((37_000_000 as Weight).saturating_mul(g as Weight))
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(g as Weight)))
+      .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes((10 as Weight).saturating_mul(g as Weight)))
+  }
+  fn add_guardian() -> Weight {
+    (29_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+  }
+  fn appoint_guardian() -> Weight {
+    (31_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+  }
+  fn remove_guardian() -> Weight {
+    (18_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+  }
+  fn act_for_ward() -> Weight {
+    (21_000_000 as Weight).saturating_add(RocksDbWeight::get().reads(3 as Weight))
+  }
+  fn create_organization() -> Weight {
+    (26_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn add_organization_member() -> Weight {
+    (34_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+  }
+  fn remove_organization_member() -> Weight {
+    (30_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+  }
+  fn register_issuer() -> Weight {
+    (21_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn deregister_issuer() -> Weight {
+    (20_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn issue_credential() -> Weight {
+    (25_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn revoke_credential() -> Weight {
+    (22_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn open_channel() -> Weight {
+    (32_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+  }
+  fn close_channel(m: u32) -> Weight {
+    (27_000_000 as Weight)
+      .saturating_add((1_000_000 as Weight).saturating_mul(m as Weight))
+      .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+      .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(m as Weight)))
+  }
+  fn send_message(l: u32) -> Weight {
+    (44_000_000 as Weight)
+      .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+      .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+  }
+  fn mark_read() -> Weight {
+    (21_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn set_recovery_contacts(c: u32) -> Weight {
+    (26_000_000 as Weight)
+      .saturating_add((4_000_000 as Weight).saturating_mul(c as Weight))
+      .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+      .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn remove_recovery_contacts() -> Weight {
+    (19_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+  }
+  fn vouch_recovery() -> Weight {
+    (32_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn cancel_recovery() -> Weight {
+    (18_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+  }
+  fn claim_recovery() -> Weight {
+    (86_000_000 as Weight)
+      .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+      .saturating_add(RocksDbWeight::get().writes(12 as Weight))
+  }
+}
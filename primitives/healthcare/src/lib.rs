@@ -1,11 +1,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_std::cmp::{Eq, PartialEq};
 
 // Enum declaration for User role.
-#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[derive(Copy, Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Role {
     Custodian = 1,
     Virtual = 2,
@@ -20,7 +24,8 @@ pub enum Role {
     EnterpriseEmployee = 11,
 }
 
-#[derive(Copy, Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[derive(Copy, Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum RecordFormat {
     Hl7v2 = 0,
     Dicom = 1,
@@ -32,7 +37,8 @@ pub enum RecordFormat {
     FhirR5 = 7,
 }
 
-#[derive(Copy, Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[derive(Copy, Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum TherapeuticArea {
     Ageing,
     BoneJointConnectiveTissue,
@@ -3,9 +3,12 @@
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::pallet_prelude::*;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_std::cmp::{Eq, PartialEq};
 
 #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[repr(u16)]
 pub enum Country {
   Afghanistan,
@@ -260,6 +263,7 @@ pub enum Country {
 }
 
 #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Region {
   Africa,
   Americas,
@@ -270,6 +274,7 @@ pub enum Region {
 }
 
 #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum SubRegion {
   AustraliaAndNewZealand,
   CentralAsia,
@@ -291,7 +296,7 @@ pub enum SubRegion {
   WesternEurope,
 }
 
-pub fn region_of<T>(country: Country) -> Region {
+pub fn region_of(country: Country) -> Region {
   match country {
     Country::Antarctica => Region::Antarctica,
     Country::Algeria => Region::Africa,
@@ -15,7 +15,7 @@ pub use frame_support::{
     constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
     IdentityFee, Weight,
   },
-  StorageValue,
+  PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 /// Delete later
@@ -23,8 +23,12 @@ pub use pallet_example_offchain_worker;
 use pallet_grandpa::{fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList};
 pub use pallet_key_registry;
 pub use pallet_marketplace;
+use pallet_marketplace::types::{DataRequest, DataRequestCursor, DataRequestId, Listing, ListingFilter, Purchase};
 pub use pallet_meta_registry;
+use pallet_meta_registry::types::RegistryId;
 pub use pallet_social_network;
+use pallet_social_network::types::{AccessControl, AccountDetail, ConnectionInfo, Group, GroupId, Relation};
+use region::Country;
 pub use pallet_template;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::CurrencyAdapter;
@@ -35,7 +39,7 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 pub use sp_runtime::BuildStorage;
 use sp_runtime::{
   create_runtime_str, generic, impl_opaque_keys,
-  traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
+  traits::{AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
   transaction_validity::{TransactionSource, TransactionValidity},
   ApplyExtrinsicResult, MultiSignature, SaturatedConversion,
 };
@@ -229,6 +233,30 @@ parameter_types! {
     pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
 }
 
+parameter_types! {
+    pub const AssetDeposit: Balance = 100_000;
+    pub const ApprovalDeposit: Balance = 500;
+    pub const AssetStringLimit: u32 = 50;
+    pub const MetadataDepositBase: Balance = 10_000;
+    pub const MetadataDepositPerByte: Balance = 1_000;
+}
+
+impl pallet_assets::Config for Runtime {
+  type Event = Event;
+  type Balance = Balance;
+  type AssetId = pallet_marketplace::types::AssetId;
+  type Currency = Balances;
+  type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+  type AssetDeposit = AssetDeposit;
+  type MetadataDepositBase = MetadataDepositBase;
+  type MetadataDepositPerByte = MetadataDepositPerByte;
+  type ApprovalDeposit = ApprovalDeposit;
+  type StringLimit = AssetStringLimit;
+  type Freezer = ();
+  type Extra = ();
+  type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_timestamp::Config for Runtime {
   /// A timestamp: milliseconds since the unix epoch.
   type Moment = u64;
@@ -274,18 +302,90 @@ impl pallet_sudo::Config for Runtime {
 
 // Aria pallets configuration
 
+parameter_types! {
+    pub const MembershipExpiry: BlockNumber = 7 * DAYS;
+    pub const MaxGuardianshipPeriod: BlockNumber = 365 * DAYS;
+    pub const RecoveryDelay: BlockNumber = 3 * DAYS;
+    pub const AccountDeposit: Balance = 10_000;
+    pub const ConnectionDeposit: Balance = 1_000;
+    pub const GroupDeposit: Balance = 5_000;
+    pub const MemberDeposit: Balance = 2_000;
+    pub const JoinRequestDeposit: Balance = 1_000;
+    pub const EscrowPeriod: BlockNumber = 3 * DAYS;
+    pub const IssuerShare: Permill = Permill::from_percent(10);
+    pub const AggregatorCut: Permill = Permill::from_percent(15);
+    pub const TreasuryFee: Permill = Permill::from_percent(2);
+    pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+    pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account();
+    pub const MarketplacePalletId: PalletId = PalletId(*b"py/mrkpl");
+    pub const DisputeDeposit: Balance = 10_000;
+    pub const DisputePeriod: BlockNumber = 7 * DAYS;
+}
+
+/// The calls a guardian can sign on behalf of its ward: connections, group memberships and
+/// registry access. Managing guardians themselves is left to the ward and the custodians.
+pub struct GuardianCalls;
+
+impl frame_support::traits::Contains<Call> for GuardianCalls {
+  fn contains(call: &Call) -> bool {
+    matches!(
+      call,
+      Call::SocialNetwork(pallet_social_network::Call::request_connection { .. })
+        | Call::SocialNetwork(pallet_social_network::Call::accept_connection { .. })
+        | Call::SocialNetwork(pallet_social_network::Call::remove_connection { .. })
+        | Call::SocialNetwork(pallet_social_network::Call::request_join { .. })
+        | Call::SocialNetwork(pallet_social_network::Call::cancel_join_request { .. })
+        | Call::SocialNetwork(pallet_social_network::Call::accept_invitation { .. })
+        | Call::SocialNetwork(pallet_social_network::Call::decline_invitation { .. })
+        | Call::SocialNetwork(pallet_social_network::Call::leave_group { .. })
+        | Call::MetaRegistry(pallet_meta_registry::Call::share_registry { .. })
+        | Call::MetaRegistry(pallet_meta_registry::Call::unshare_registry { .. })
+    )
+  }
+}
+
 impl pallet_social_network::Config for Runtime {
   type Event = Event;
   type ConnectionRules = ();
+  type MembershipExpiry = MembershipExpiry;
+  type Call = Call;
+  type GuardianFilter = GuardianCalls;
+  type MaxGuardianshipPeriod = MaxGuardianshipPeriod;
+  type Keys = KeyRegistry;
+  type OnAccountRecovered = (MetaRegistry, Marketplace);
+  type RecoveryDelay = RecoveryDelay;
+  type Currency = Balances;
+  type AccountDeposit = AccountDeposit;
+  type ConnectionDeposit = ConnectionDeposit;
+  type GroupDeposit = GroupDeposit;
+  type MemberDeposit = MemberDeposit;
+  type JoinRequestDeposit = JoinRequestDeposit;
+  type WeightInfo = pallet_social_network::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_marketplace::Config for Runtime {
   type Event = Event;
+  type Currency = Balances;
+  type Assets = Assets;
+  type PalletId = MarketplacePalletId;
+  type EscrowPeriod = EscrowPeriod;
+  type Registries = MetaRegistry;
+  type Roles = SocialNetwork;
+  type IssuerShare = IssuerShare;
+  type AggregatorCut = AggregatorCut;
+  type TreasuryFee = TreasuryFee;
+  type TreasuryAccount = TreasuryAccount;
+  type Custodians = SocialNetwork;
+  type DisputeDeposit = DisputeDeposit;
+  type DisputePeriod = DisputePeriod;
+  type WeightInfo = pallet_marketplace::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_meta_registry::Config for Runtime {
   type Event = Event;
   type IssuerRules = ();
+  type GroupMembership = SocialNetwork;
+  type Organizations = SocialNetwork;
 }
 
 impl pallet_key_registry::Config for Runtime {
@@ -377,11 +477,12 @@ construct_runtime!(
         Aura: pallet_aura::{Pallet, Config<T>},
         Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event},
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
         TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
         Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
-        SocialNetwork: pallet_social_network::{Pallet, Call, Event<T>},
-        MetaRegistry: pallet_meta_registry::{Pallet,Event<T>},
-        Marketplace: pallet_marketplace::{Pallet,Event<T>},
+        SocialNetwork: pallet_social_network::{Pallet, Call, Storage, Event<T>},
+        MetaRegistry: pallet_meta_registry::{Pallet, Call, Storage, Event<T>},
+        Marketplace: pallet_marketplace::{Pallet, Call, Storage, Event<T>},
         TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
         KeyRegistry: pallet_key_registry::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
         OcwExample: pallet_example_offchain_worker::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
@@ -542,6 +643,58 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_social_network_runtime_api::SocialNetworkApi<Block, AccountId> for Runtime {
+        fn connections_of(account: AccountId, status: Option<Relation>, cursor: u32, limit: u32) -> Vec<ConnectionInfo<AccountId>> {
+            SocialNetwork::connections_of(&account, &status, cursor, limit)
+        }
+
+        fn groups_of(account: AccountId) -> Vec<(GroupId, AccessControl)> {
+            SocialNetwork::groups_of(&account)
+        }
+
+        fn members_of(group: GroupId, cursor: u32, limit: u32) -> Vec<(AccountId, AccessControl)> {
+            SocialNetwork::members_of(&group, cursor, limit)
+        }
+
+        fn account(account: AccountId) -> Option<AccountDetail<AccountId>> {
+            SocialNetwork::accounts(&account)
+        }
+
+        fn mutual_connections(a: AccountId, b: AccountId) -> Vec<AccountId> {
+            SocialNetwork::mutual_connections(&a, &b)
+        }
+
+        fn nearby_groups(country: Country, cursor: u32, limit: u32) -> Vec<(GroupId, Group<AccountId>)> {
+            SocialNetwork::nearby_groups(&country, cursor, limit)
+        }
+    }
+
+    impl pallet_marketplace_runtime_api::MarketplaceApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn listings(
+            filter: ListingFilter<Balance>,
+            cursor: Option<RegistryId>,
+            limit: u32,
+        ) -> Vec<(RegistryId, Listing<Balance, BlockNumber>)> {
+            Marketplace::listings_matching(&filter, &cursor, limit)
+        }
+
+        fn purchases_of(
+            buyer: AccountId,
+            cursor: Option<RegistryId>,
+            limit: u32,
+        ) -> Vec<(RegistryId, Purchase<AccountId, Balance, BlockNumber>)> {
+            Marketplace::purchases_of(&buyer, &cursor, limit)
+        }
+
+        fn data_requests_for(
+            patient: AccountId,
+            cursor: Option<DataRequestCursor>,
+            limit: u32,
+        ) -> Vec<(DataRequestId, DataRequest<AccountId, Balance, BlockNumber>, Vec<RegistryId>)> {
+            Marketplace::data_requests_for(&patient, &cursor, limit)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn benchmark_metadata(extra: bool) -> (
@@ -558,6 +711,8 @@ impl_runtime_apis! {
             list_benchmark!(list, extra, pallet_balances, Balances);
             list_benchmark!(list, extra, pallet_timestamp, Timestamp);
             list_benchmark!(list, extra, pallet_template, TemplateModule);
+            list_benchmark!(list, extra, pallet_social_network, SocialNetwork);
+            list_benchmark!(list, extra, pallet_marketplace, Marketplace);
 
             let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -592,6 +747,8 @@ impl_runtime_apis! {
             add_benchmark!(params, batches, pallet_balances, Balances);
             add_benchmark!(params, batches, pallet_timestamp, Timestamp);
             add_benchmark!(params, batches, pallet_template, TemplateModule);
+            add_benchmark!(params, batches, pallet_social_network, SocialNetwork);
+            add_benchmark!(params, batches, pallet_marketplace, Marketplace);
 
             if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
             Ok(batches)